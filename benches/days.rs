@@ -0,0 +1,149 @@
+//! Parsing and solving each day on its input, measured by criterion.
+//! Run with `cargo bench`, or `cargo bench -- day7` for a single day.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// The inputs are compiled in, so the numbers don't depend on where the
+/// benchmark runs from. Without any day, there is nothing to measure.
+macro_rules! days {
+    ($($day:literal => $feature:literal, $module:ident::$solution:ident;)*) => {
+        /// Benchmarks parsing and both parts of `S` separately, so a slow
+        /// parser doesn't hide in the time of a part.
+        #[cfg(any($(feature = $feature),*))]
+        fn bench_day<S: aoc2023::solution::Solution>(c: &mut Criterion, day: u8, input: &str) {
+            let mut group = c.benchmark_group(format!("day{day}"));
+            let parsed = S::parse(input).expect("the input parses");
+
+            group.bench_function("parse", |b| b.iter(|| S::parse(input)));
+            group.bench_function("part1", |b| b.iter(|| S::part1(&parsed)));
+            if S::PARTS >= 2 {
+                group.bench_function("part2", |b| b.iter(|| S::part2(&parsed)));
+            }
+
+            group.finish();
+        }
+
+        fn days(
+            #[cfg_attr(not(any($(feature = $feature),*)), allow(unused_variables))] c: &mut Criterion,
+        ) {
+            $(
+                #[cfg(feature = $feature)]
+                bench_day::<aoc2023::aoc2023::$module::$solution>(
+                    c,
+                    $day,
+                    include_str!(concat!("../inputs/2023/day", $day, ".txt")),
+                );
+            )*
+        }
+    };
+}
+
+days! {
+    1 => "day1", day1::Day1;
+    2 => "day2", day2::Day2;
+    3 => "day3", day3::Day3;
+    4 => "day4", day4::Day4;
+    5 => "day5", day5::Day5;
+    6 => "day6", day6::Day6;
+    7 => "day7", day7::Day7;
+    8 => "day8", day8::Day8;
+    9 => "day9", day9::Day9;
+    10 => "day10", day10::Day10;
+    11 => "day11", day11::Day11;
+    12 => "day12", day12::Day12;
+    13 => "day13", day13::Day13;
+}
+
+/// All of rayon's threads against a single one, on a generated input
+/// large enough for splitting it to pay off.
+#[cfg(feature = "day1")]
+fn day1_giant(c: &mut Criterion) {
+    use aoc2023::{aoc2023::day1::Day1, gen, solution::Solution};
+
+    let giant = Day1::generate(&mut gen::rng(1), 1_000_000).expect("lines can be generated");
+    let serial = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .expect("a thread can be started");
+
+    let mut group = c.benchmark_group("day1-giant");
+    group.sample_size(10);
+    group.bench_function("serial", |b| {
+        b.iter(|| serial.install(|| Day1::part1(&giant)));
+    });
+    group.bench_function("parallel", |b| b.iter(|| Day1::part1(&giant)));
+    group.finish();
+}
+
+/// Thousands of generated mazes, which are scored in parallel.
+#[cfg(feature = "day13")]
+fn day13_many(c: &mut Criterion) {
+    use aoc2023::{aoc2023::day13::Day13, gen, solution::Solution};
+
+    // A hundred generated inputs of a hundred mazes each.
+    let input = (0..100)
+        .map(|seed| Day13::generate(&mut gen::rng(seed), 17).expect("mazes can be generated"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let parsed = Day13::parse(&input).expect("generated mazes parse");
+    let serial = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .expect("a thread can be started");
+
+    let mut group = c.benchmark_group("day13-many");
+    group.sample_size(10);
+    group.bench_function("serial", |b| {
+        b.iter(|| serial.install(|| Day13::part1(&parsed)));
+    });
+    group.bench_function("parallel", |b| b.iter(|| Day13::part1(&parsed)));
+    group.finish();
+}
+
+/// The default parser against the one for inputs too large for memory, on
+/// a map with one long path.
+#[cfg(feature = "large-input")]
+fn day8_large(c: &mut Criterion) {
+    use aoc2023::{
+        aoc2023::day8::{large, Day8},
+        solution::Solution,
+    };
+
+    let map = large::generate_map();
+
+    let mut group = c.benchmark_group("day8-long-path");
+    group.bench_function("default", |b| {
+        b.iter(|| Day8::part1(&Day8::parse(&map).expect("the map parses")));
+    });
+    group.bench_function("large-input", |b| {
+        b.iter(|| {
+            large::Graph::parse(map.as_bytes())
+                .expect("the map parses")
+                .solve_part1()
+        });
+    });
+    group.finish();
+}
+
+/// Serial against parallel runs of days that split their work.
+fn scaling(
+    #[cfg_attr(not(any(feature = "day1", feature = "day13")), allow(unused_variables))]
+    c: &mut Criterion,
+) {
+    #[cfg(feature = "day1")]
+    day1_giant(c);
+    #[cfg(feature = "day13")]
+    day13_many(c);
+}
+
+/// Parsers for inputs too large to read into memory against the default
+/// ones.
+fn large_input(
+    #[cfg_attr(not(feature = "large-input"), allow(unused_variables))] c: &mut Criterion,
+) {
+    #[cfg(feature = "large-input")]
+    day8_large(c);
+}
+
+criterion_group!(benches, days, scaling, large_input);
+criterion_main!(benches);
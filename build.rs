@@ -0,0 +1,64 @@
+//! Collects which day modules exist and what they implement, for the
+//! `list` subcommand. This only looks at the source text.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+/// Whether there is a test whose name mentions an example, or tests
+/// generated by `example_test!`.
+fn has_example_test(source: &str) -> bool {
+    source.contains("example_test!(")
+        || source.split("#[test]").skip(1).any(|rest| {
+            rest.split_once('(')
+                .is_some_and(|(head, _)| head.contains("example"))
+        })
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+
+    let mut modules = vec![];
+
+    for event in fs::read_dir("src").unwrap().flatten() {
+        let name = event.file_name().into_string().unwrap_or_default();
+        let Some(year) = name.strip_prefix("aoc").and_then(|y| y.parse::<u16>().ok()) else {
+            continue;
+        };
+
+        for module in fs::read_dir(event.path()).unwrap().flatten() {
+            let file = module.file_name().into_string().unwrap_or_default();
+            let Some(day) = file
+                .strip_prefix("day")
+                .and_then(|f| f.strip_suffix(".rs"))
+                .and_then(|d| d.parse::<u8>().ok())
+            else {
+                continue;
+            };
+
+            let source = fs::read_to_string(module.path()).unwrap();
+            modules.push((
+                year,
+                day,
+                source.contains("fn part1("),
+                source.contains("fn part2("),
+                has_example_test(&source),
+            ));
+        }
+    }
+
+    modules.sort_unstable();
+
+    let mut out = String::from("&[\n");
+    for (year, day, part1, part2, example_tests) in modules {
+        let _ = writeln!(
+            out,
+            "    ModuleInfo {{ year: {year}, day: {day}, part1: {part1}, part2: {part2}, example_tests: {example_tests} }},"
+        );
+    }
+    out.push(']');
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("modules.rs"),
+        out,
+    )
+    .unwrap();
+}
@@ -0,0 +1,131 @@
+//! Known correct answers, stored as TOML:
+//!
+//! ```toml
+//! [2023.1]
+//! part1 = "54304"
+//! part2 = "54418"
+//! ```
+
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+impl DayAnswers {
+    fn part_mut(&mut self, part: u8) -> Result<&mut Option<String>> {
+        match part {
+            1 => Ok(&mut self.part1),
+            2 => Ok(&mut self.part2),
+            _ => bail!("Puzzles only have two parts, not {part}"),
+        }
+    }
+}
+
+/// TOML keys are always strings, so years and days are kept as such.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, DayAnswers>>);
+
+impl Answers {
+    /// Loads the answers from `path`. A missing file is the same as an
+    /// empty one.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => s
+                .parse()
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    #[must_use]
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        let answers = self.0.get(&year.to_string())?.get(&day.to_string())?;
+
+        match part {
+            1 => answers.part1.as_deref(),
+            2 => answers.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, year: u16, day: u8, part: u8, answer: impl Into<String>) -> Result<()> {
+        *self
+            .0
+            .entry(year.to_string())
+            .or_default()
+            .entry(day.to_string())
+            .or_default()
+            .part_mut(part)? = Some(answer.into());
+
+        Ok(())
+    }
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let answers: Self = toml::from_str(s)?;
+
+        for (year, days) in &answers.0 {
+            year.parse::<u16>()
+                .with_context(|| format!("Invalid year: {year}"))?;
+
+            for day in days.keys() {
+                day.parse::<u8>()
+                    .with_context(|| format!("Invalid day in {year}: {day}"))?;
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&toml::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_roundtrip() -> Result<()> {
+        let mut answers = Answers::from_str("[2023.1]\npart1 = \"142\"\n")?;
+
+        assert_eq!(answers.get(2023, 1, 1), Some("142"));
+        assert_eq!(answers.get(2023, 1, 2), None);
+        assert_eq!(answers.get(2022, 1, 1), None);
+
+        answers.set(2023, 1, 2, "281")?;
+        answers.set(2023, 10, 1, "8")?;
+        assert!(answers.set(2023, 10, 3, "?").is_err());
+
+        assert_eq!(Answers::from_str(&answers.to_string())?, answers);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_bogus_keys() {
+        assert!(Answers::from_str("[twenty.1]\npart1 = \"1\"\n").is_err());
+        assert!(Answers::from_str("[2023.first]\npart1 = \"1\"\n").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use std::convert::Infallible;
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use rand::Rng as _;
+
+use crate::{
+    gen::Rng,
+    input::line_chunks,
+    lint::{self, Diagnostic},
+    parallel::maybe_par_map,
+    solution::{Answer, Solution},
+};
+
+fn line_calibration_value(line: &str) -> Option<u32> {
+    if let Some(first_digit) = line.chars().find_map(|c| c.to_digit(10)) {
+        if let Some(last_digit) = line.chars().rev().find_map(|c| c.to_digit(10)) {
+            return Some(first_digit * 10 + last_digit);
+        }
+    }
+
+    None
+}
+
+/// Inputs are processed in chunks of this many bytes, so giant inputs
+/// are spread over all cores.
+const CHUNK_SIZE: usize = 1 << 20;
+
+fn sum_lines(input: &str, line_value: fn(&str) -> Option<u32>) -> Result<u64> {
+    let opt_sum: Option<u64> = maybe_par_map(&line_chunks(input, CHUNK_SIZE), |chunk| {
+        chunk
+            .lines()
+            .map(|l| line_value(l).map(u64::from))
+            .sum::<Option<u64>>()
+    })
+    .into_iter()
+    .sum();
+
+    opt_sum.ok_or_else(|| anyhow!("Failed to parse some lines?"))
+}
+
+fn sum_of_calibrations(input: &str) -> Result<u64> {
+    sum_lines(input, line_calibration_value)
+}
+
+const DIGIT_NAMES: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Replace the first character of digit names with the digit itself.
+///
+/// For example: 1two3 -> 12wo3
+fn replace_digit_names(line: &str) -> String {
+    let mut result = line.chars().collect_vec();
+
+    for (digit, positions) in DIGIT_NAMES
+        .iter()
+        .enumerate()
+        .map(|(digit, name)| (digit, line.match_indices(name).map(|m| m.0).collect_vec()))
+    {
+        let d = digit.to_string().chars().collect_vec()[0];
+
+        for pos in positions {
+            result[pos] = d;
+        }
+    }
+
+    result.into_iter().collect()
+}
+
+fn line_calibration_value_with_strings(line: &str) -> Option<u32> {
+    let digit_str = replace_digit_names(line);
+
+    line_calibration_value(&digit_str)
+}
+
+fn sum_of_calibrations_with_strings(input: &str) -> Result<u64> {
+    sum_lines(input, line_calibration_value_with_strings)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = String;
+    type Error = Infallible;
+
+    fn parse(input: &str) -> Result<String, Infallible> {
+        Ok(input.to_owned())
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        lint::each_line(input, |c| {
+            c.only(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit())
+                .map(|_| ())
+        })
+    }
+
+    fn part1(input: &String) -> Result<Answer> {
+        Ok(sum_of_calibrations(input)?.into())
+    }
+
+    fn part2(input: &String) -> Result<Option<Answer>> {
+        Ok(Some(sum_of_calibrations_with_strings(input)?.into()))
+    }
+
+    /// `size` lines of letters and spelled out digits with at least one
+    /// digit each, so both parts find a value on every line. Written into
+    /// a single string, as inputs with a hundred million lines are what
+    /// the parallel sums are for.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        const PIECES: usize = 8;
+
+        let mut input = String::with_capacity(size * 4 * PIECES);
+        for _ in 0..size {
+            let digit_at = rng.gen_range(0..PIECES);
+
+            for piece in 0..PIECES {
+                if piece == digit_at {
+                    input.push(char::from(b'0' + rng.gen_range(1..10)));
+                }
+                match rng.gen_range(0..4) {
+                    0 => input.push_str(DIGIT_NAMES[rng.gen_range(1..10)]),
+                    _ => input.push(char::from(b'a' + rng.gen_range(0..26))),
+                }
+            }
+            input.push('\n');
+        }
+
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_digit_names_works() {
+        assert_eq!(replace_digit_names("1234"), "1234".to_owned());
+        assert_eq!(replace_digit_names("1one34"), "11ne34".to_owned());
+        assert_eq!(
+            replace_digit_names("1eightwotwo4"),
+            "18igh2wo2wo4".to_owned()
+        );
+        assert_eq!(
+            replace_digit_names("seven91eightwo"),
+            "7even918igh2wo".to_owned()
+        );
+    }
+
+    #[test]
+    fn handling_line_works() {
+        assert_eq!(line_calibration_value("a1b2c"), Some(12));
+        assert_eq!(
+            line_calibration_value_with_strings("seven91eightwo"),
+            Some(72)
+        );
+    }
+
+    crate::example_test!(day1, part1 = 142);
+    crate::example_test!(day1_part2, part2 = 281);
+
+    #[test]
+    fn generated_lines_have_values() -> Result<()> {
+        let input = Day1::generate(&mut crate::gen::rng(1), 1000)?;
+
+        assert_eq!(input.lines().count(), 1000);
+        assert!(input.lines().all(|l| line_calibration_value(l).is_some()));
+        assert!(Day1::part1(&input).is_ok() && Day1::part2(&input).is_ok());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,613 @@
+use std::{collections::HashSet, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use colored::{Color, Colorize};
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{
+    dump::Table,
+    error::DayError,
+    geometry::{self, Direction, Point2, Vec2},
+    grid::Grid,
+    lint::{self, Diagnostic, Linter},
+    solution::{Answer, ExplainQuery, Report, Solution},
+    viz::{self, Drawing, Image, Rgb, PALETTE},
+};
+
+/// The ways a sketch of the pipes can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Invalid tile {0}")]
+    Tile(char),
+    #[error("Row {row} is {len} wide, but the first is {width}")]
+    Ragged {
+        row: usize,
+        len: usize,
+        width: usize,
+    },
+    #[error("There is no start tile")]
+    NoStart,
+}
+
+impl DayError for Error {}
+
+/// How many tiles of the main loop `--animate` adds per frame.
+const TRACE_FRAME_TILES: usize = 100;
+
+/// A single tile of the pipe maze. One byte instead of a four byte
+/// `char` keeps the grid compact.
+#[repr(u8)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Tile {
+    Ground,
+    /// `|`
+    NorthSouth,
+    /// `-`
+    EastWest,
+    /// `L`
+    NorthEast,
+    /// `J`
+    NorthWest,
+    /// `7`
+    SouthWest,
+    /// `F`
+    SouthEast,
+    /// `S`
+    Start,
+}
+
+/// The pipes, for guessing what is under the start tile.
+const PIPES: [Tile; 6] = [
+    Tile::NorthSouth,
+    Tile::EastWest,
+    Tile::NorthEast,
+    Tile::NorthWest,
+    Tile::SouthWest,
+    Tile::SouthEast,
+];
+
+impl Tile {
+    /// The directions of the two tiles a pipe connects.
+    fn connections(self) -> Option<[Direction; 2]> {
+        use Direction::{East, North, South, West};
+
+        match self {
+            Tile::NorthSouth => Some([North, South]),
+            Tile::EastWest => Some([West, East]),
+            Tile::NorthEast => Some([North, East]),
+            Tile::NorthWest => Some([North, West]),
+            Tile::SouthWest => Some([West, South]),
+            Tile::SouthEast => Some([South, East]),
+            Tile::Start | Tile::Ground => None,
+        }
+    }
+
+    /// Whether the pipe leaves its tile to the north. Crossing a row
+    /// through those tiles switches between inside and outside of a loop.
+    fn goes_north(self) -> bool {
+        matches!(self, Tile::NorthSouth | Tile::NorthEast | Tile::NorthWest)
+    }
+
+    /// How the tile looks when drawn.
+    fn symbol(self) -> char {
+        match self {
+            Tile::Ground => ' ',
+            Tile::NorthSouth => '│',
+            Tile::EastWest => '─',
+            Tile::NorthEast => '└',
+            Tile::NorthWest => '┘',
+            Tile::SouthWest => '┐',
+            Tile::SouthEast => '┌',
+            Tile::Start => 'S',
+        }
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            '.' => Tile::Ground,
+            '|' => Tile::NorthSouth,
+            '-' => Tile::EastWest,
+            'L' => Tile::NorthEast,
+            'J' => Tile::NorthWest,
+            '7' => Tile::SouthWest,
+            'F' => Tile::SouthEast,
+            'S' => Tile::Start,
+            tile => return Err(Error::Tile(tile)),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    tiles: Grid<Tile>,
+}
+
+impl FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .map(|l| l.chars().map(Tile::try_from).collect::<Result<Vec<_>, _>>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = rows.first().map_or(0, Vec::len);
+
+        if let Some(row) = rows.iter().position(|r| r.len() != width) {
+            return Err(Error::Ragged {
+                row: row + 1,
+                len: rows[row].len(),
+                width,
+            });
+        }
+        if !rows.iter().flatten().any(|t| *t == Tile::Start) {
+            return Err(Error::NoStart);
+        }
+
+        Ok(Self {
+            tiles: Grid::from_rows(rows).expect("the rows are as wide as the first"),
+        })
+    }
+}
+
+impl Input {
+    // Out of bounds accesses return ground.
+    fn get(&self, p: Vec2) -> Tile {
+        Point2::try_from(p).map_or(Tile::Ground, |p| self.tiles.get_or(p, Tile::Ground))
+    }
+
+    fn successors(&self, p: Vec2) -> Option<(Vec2, Vec2)> {
+        let [d1, d2] = self.get(p).connections()?;
+
+        Some((p + d1.offset(), p + d2.offset()))
+    }
+
+    fn start_point(&self) -> Vec2 {
+        let (start, _) = self
+            .tiles
+            .enumerate()
+            .find(|(_, t)| **t == Tile::Start)
+            .unwrap();
+
+        start.into()
+    }
+
+    /// The tiles of the loop through the start tile, beginning with it,
+    /// and the pipe that is hidden under the start tile.
+    fn main_loop(&self) -> Result<(Vec<Vec2>, Tile)> {
+        let start = self.start_point();
+        let connects_to_start = |p: Vec2| {
+            self.successors(p)
+                .is_some_and(|(p1, p2)| p1 == start || p2 == start)
+        };
+
+        let first = Direction::ALL
+            .into_iter()
+            .map(|d| start + d.offset())
+            .find(|p| connects_to_start(*p))
+            .ok_or_else(|| anyhow!("No pipe connects to the start tile"))?;
+
+        let mut tiles = vec![start];
+        let (mut previous, mut current) = (start, first);
+
+        while current != start {
+            let (p1, p2) = self
+                .successors(current)
+                .filter(|(p1, p2)| *p1 == previous || *p2 == previous)
+                .ok_or_else(|| anyhow!("The loop breaks off at {current:?}"))?;
+
+            tiles.push(current);
+            if tiles.len() % TRACE_FRAME_TILES == 0 {
+                viz::frame(|| self.trace_frame(&tiles, None));
+            }
+            (previous, current) = (current, if p1 == previous { p2 } else { p1 });
+        }
+
+        let ends = [first - start, previous - start];
+        let start_tile = PIPES
+            .into_iter()
+            .find(|t| {
+                t.connections()
+                    .map(|c| c.map(Direction::offset))
+                    .is_some_and(|c| c == ends || c == [ends[1], ends[0]])
+            })
+            .ok_or_else(|| anyhow!("The loop doesn't close at the start tile"))?;
+
+        viz::frame(|| self.trace_frame(&tiles, Some(start_tile)));
+        Ok((tiles, start_tile))
+    }
+
+    /// Draws each tile as three by three pixels, so the pipes join up,
+    /// with `color` picking the color of each pipe. The start is red and
+    /// shows the pipe under it if that is known.
+    fn draw(&self, start: Option<Tile>, mut color: impl FnMut(Point2) -> Rgb) -> Image {
+        let mut image = Image::new(self.tiles.rows() * 3, self.tiles.cols() * 3, Rgb::NIGHT);
+
+        for (p, tile) in self.tiles.enumerate() {
+            let (pipe, color) = match *tile {
+                Tile::Ground => continue,
+                Tile::Start => (start.unwrap_or(Tile::Start), PALETTE[5]),
+                pipe => (pipe, color(p)),
+            };
+
+            let center = Point2::new(p.row * 3 + 1, p.col * 3 + 1);
+            image.set(center, color);
+            for d in pipe.connections().into_iter().flatten() {
+                image.set(center.checked_add(d.offset()).unwrap_or(center), color);
+            }
+        }
+
+        image
+    }
+
+    /// How far [`Self::main_loop`] got, with the tiles `found` so far in
+    /// white.
+    fn trace_frame(&self, found: &[Vec2], start: Option<Tile>) -> Image {
+        let found = found
+            .iter()
+            .filter_map(|&p| Point2::try_from(p).ok())
+            .collect::<HashSet<_>>();
+
+        self.draw(start, |p| {
+            if found.contains(&p) {
+                Rgb::WHITE
+            } else {
+                Rgb::GRAY
+            }
+        })
+    }
+
+    /// The maze with only the main loop left. All other pipes become
+    /// ground and the start tile becomes the pipe it hides.
+    fn pruned(&self) -> Result<Input> {
+        let (tiles, start_tile) = self.main_loop()?;
+        let mut pruned = self.tiles.map(|_| Tile::Ground);
+
+        for tile in tiles {
+            let cell = Point2::try_from(tile)?;
+
+            pruned[cell] = match self.tiles[cell] {
+                Tile::Start => start_tile,
+                tile => tile,
+            };
+        }
+
+        Ok(Input { tiles: pruned })
+    }
+
+    /// Whether each tile of a pruned maze is enclosed by the loop.
+    fn enclosed(&self) -> Grid<bool> {
+        self.tiles.map_rows(|row| {
+            row.iter()
+                .scan(false, |inside, tile| {
+                    if tile.goes_north() {
+                        *inside = !*inside;
+                    }
+
+                    Some(*inside && *tile == Tile::Ground)
+                })
+                .collect()
+        })
+    }
+
+    /// The farthest tile is halfway around the loop.
+    fn solve_part1(&self) -> Result<usize> {
+        let (tiles, _) = self.main_loop()?;
+
+        Ok(tiles.len() / 2)
+    }
+}
+
+/// A connected area of enclosed tiles.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Region {
+    id: usize,
+    size: usize,
+    /// The top left corner of its bounding box.
+    min: Point2,
+    /// The bottom right corner of its bounding box.
+    max: Point2,
+}
+
+/// Labels the enclosed tiles of a pruned maze by the connected region
+/// they belong to. Returns the region of each tile and the regions,
+/// ordered by their first tile.
+fn regions(enclosed: &Grid<bool>) -> (Grid<Option<usize>>, Vec<Region>) {
+    let mut labels = enclosed.map(|_| None);
+    let mut regions = vec![];
+
+    for (first, _) in enclosed.enumerate().filter(|(_, e)| **e) {
+        if labels[first].is_some() {
+            continue;
+        }
+
+        let id = regions.len();
+        let mut region = Region {
+            id,
+            size: 0,
+            min: Point2::new(usize::MAX, usize::MAX),
+            max: Point2::default(),
+        };
+        for p in enclosed.flood_fill(first, |_, e| *e).cells {
+            labels[p] = Some(id);
+            region.size += 1;
+            region.min = Point2::new(region.min.row.min(p.row), region.min.col.min(p.col));
+            region.max = Point2::new(region.max.row.max(p.row), region.max.col.max(p.col));
+        }
+
+        regions.push(region);
+    }
+
+    (labels, regions)
+}
+
+/// Colors to tell neighboring regions apart in drawings.
+const REGION_COLORS: [Color; 6] = [
+    Color::Yellow,
+    Color::Cyan,
+    Color::Magenta,
+    Color::Green,
+    Color::Blue,
+    Color::Red,
+];
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Input;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Input, Error> {
+        Input::from_str(input)
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        let mut linter = Linter::new();
+        let lines = lint::lines(&mut linter, input);
+
+        linter.grid(&lines, |c| "|-LJ7F.S".contains(c));
+
+        let starts = lines
+            .iter()
+            .flat_map(|(number, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == 'S')
+                    .map(|(column, _)| (*number, column + 1))
+            })
+            .collect::<Vec<_>>();
+
+        match starts[..] {
+            [] if !lines.is_empty() => linter.error(1, 1, "There is no start tile S"),
+            [_, (line, column), ..] => {
+                linter.error(line, column, "There is more than one start tile S")
+            }
+            _ => {}
+        }
+
+        linter.finish()
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(input.solve_part1()?.into())
+    }
+
+    /// The enclosed tiles are the lattice points inside the loop.
+    fn part2(input: &Input) -> Result<Option<Answer>> {
+        let (tiles, _) = input.main_loop()?;
+
+        Ok(Some(geometry::interior_points(&tiles).into()))
+    }
+
+    /// Draws the main loop without the junk pipes around it. Enclosed
+    /// tiles are dots, colored by their region, and the regions are
+    /// listed below.
+    fn explain(input: &Input, _query: &ExplainQuery) -> Result<Report> {
+        let pruned = input.pruned()?;
+        let (labels, regions) = regions(&pruned.enclosed());
+        let mut report = Report::new("The main loop");
+
+        for (row, labels) in pruned.tiles.iter_rows().zip(labels.iter_rows()) {
+            report.line(
+                row.iter()
+                    .zip(labels)
+                    .map(|(tile, label)| match label {
+                        Some(id) => "•"
+                            .color(REGION_COLORS[id % REGION_COLORS.len()])
+                            .to_string(),
+                        None => tile.symbol().to_string(),
+                    })
+                    .collect::<String>(),
+            );
+        }
+
+        for region in &regions {
+            report.line(format!(
+                "Region {}: {} tiles in columns {}..={}, rows {}..={}",
+                region.id,
+                region.size,
+                region.min.col,
+                region.max.col,
+                region.min.row,
+                region.max.row
+            ));
+        }
+
+        Ok(report)
+    }
+
+    /// Each tile as three by three pixels, so the pipes join up. The main
+    /// loop is white with the start in red, other pipes are gray, and
+    /// enclosed tiles have the color of their region. SVGs trace the loop
+    /// with a line on top.
+    fn render(input: &Input) -> Result<Drawing> {
+        let (tiles, _) = input.main_loop()?;
+        let pruned = input.pruned()?;
+        let start = pruned.tiles[Point2::try_from(input.start_point())?];
+        let mut image = input.draw(Some(start), |p| {
+            if pruned.tiles[p] == Tile::Ground {
+                Rgb::GRAY
+            } else {
+                Rgb::WHITE
+            }
+        });
+
+        let (labels, _) = regions(&pruned.enclosed());
+        for (p, id) in labels.enumerate() {
+            let Some(id) = id else { continue };
+
+            for (row, col) in (0..3).cartesian_product(0..3) {
+                let pixel = Point2::new(p.row * 3 + row, p.col * 3 + col);
+                image.set(pixel, PALETTE[id % PALETTE.len()]);
+            }
+        }
+
+        let centers = tiles
+            .into_iter()
+            .filter_map(|p| Point2::try_from(p).ok())
+            .map(|p| Point2::new(p.row * 3 + 1, p.col * 3 + 1))
+            .collect();
+        let mut drawing = Drawing::from(image);
+        drawing.polygon(centers, PALETTE[1]);
+
+        Ok(drawing)
+    }
+
+    /// The enclosed regions with their sizes and bounding boxes.
+    fn dump(input: &Input) -> Result<Table> {
+        let pruned = input.pruned()?;
+        let (_, regions) = regions(&pruned.enclosed());
+        let mut table = Table::new(&["region", "size", "min_col", "min_row", "max_col", "max_row"]);
+
+        for r in regions {
+            table.row(
+                [r.id, r.size, r.min.col, r.min.row, r.max.col, r.max.row]
+                    .map(Answer::from)
+                    .to_vec(),
+            );
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_works() -> Result<()> {
+        let input = Input::from_str(
+            "|-L
+J7S",
+        )?;
+
+        assert_eq!(input.get(Vec2::new(0, 0)), Tile::NorthSouth);
+        assert_eq!(input.get(Vec2::new(0, 1)), Tile::EastWest);
+        assert_eq!(input.get(Vec2::new(1, 0)), Tile::NorthWest);
+        assert_eq!(input.get(Vec2::new(0, -1)), Tile::Ground);
+        assert_eq!(input.get(Vec2::new(1, 3)), Tile::Ground);
+
+        assert_eq!(input.start_point(), Vec2::new(1, 2));
+
+        assert_eq!((input.tiles.rows(), input.tiles.cols()), (2, 3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn tiles_are_compact() {
+        assert_eq!(std::mem::size_of::<Tile>(), 1);
+        assert_eq!(Input::from_str("..x").err(), Some(Error::Tile('x')));
+        assert_eq!(
+            Input::from_str("S.\n.").err(),
+            Some(Error::Ragged {
+                row: 2,
+                len: 1,
+                width: 2
+            })
+        );
+        assert_eq!(Input::from_str("..\n..").err(), Some(Error::NoStart));
+    }
+
+    crate::example_test!(day10, part1 = 8);
+    crate::example_test!(day10_part2, part2 = 4);
+
+    #[test]
+    fn example_works() -> Result<()> {
+        let input = Input::from_str(include_str!("day10.example"))?;
+
+        let (tiles, start_tile) = input.main_loop()?;
+        assert_eq!(tiles.len(), 16);
+        assert_eq!(start_tile, Tile::SouthEast);
+
+        let pruned = input.pruned()?;
+        assert_eq!(pruned.get(Vec2::new(0, 0)), Tile::Ground);
+        assert_eq!(pruned.get(Vec2::new(2, 0)), Tile::SouthEast);
+        assert_eq!(pruned.get(Vec2::new(2, 4)), Tile::SouthWest);
+        assert_eq!(
+            pruned.tiles.iter().filter(|t| **t != Tile::Ground).count(),
+            16
+        );
+
+        // The start is an F, which joins the tiles below and to the right.
+        let image = Day10::render(&input)?.image;
+        assert_eq!((image.rows(), image.cols()), (15, 15));
+        for pixel in [Point2::new(7, 1), Point2::new(8, 1), Point2::new(7, 2)] {
+            assert_eq!(image.get(pixel), Some(PALETTE[5]));
+        }
+        assert_eq!(image.get(Point2::new(6, 1)), Some(Rgb::NIGHT));
+
+        // The loop is too short for more than the frame of the whole loop.
+        let animation = viz::Animation::default();
+        animation.attach();
+        input.main_loop()?;
+        let frames = animation.frames();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].get(Point2::new(8, 1)), Some(PALETTE[5]));
+        assert_eq!(frames[0].get(Point2::new(9, 1)), Some(Rgb::WHITE));
+        assert_eq!(frames[0].get(Point2::new(1, 1)), Some(Rgb::GRAY));
+
+        Ok(())
+    }
+
+    #[test]
+    fn example2_works() -> Result<()> {
+        let input = Input::from_str(include_str!("day10_part2.example"))?;
+
+        assert_eq!(
+            Day10::dump(&input)?.to_csv(),
+            "region,size,min_col,min_row,max_col,max_row\n0,2,2,6,3,6\n1,2,7,6,8,6\n"
+        );
+
+        // Junk pipes inside the loop count as enclosed, too.
+        let input = Input::from_str(
+            "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        )?;
+
+        assert_eq!(Day10::part2(&input)?, Some(Answer::UInt(10)));
+
+        let pruned = input.pruned()?;
+        let enclosed = pruned.enclosed();
+        let (labels, regions) = regions(&enclosed);
+
+        assert_eq!(regions.iter().map(|r| r.size).sum::<usize>(), 10);
+        assert_eq!(enclosed.iter().filter(|e| **e).count(), 10);
+        assert_eq!(labels.map(Option::is_some), enclosed);
+
+        Ok(())
+    }
+}
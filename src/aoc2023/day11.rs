@@ -0,0 +1,695 @@
+use std::{collections::BTreeSet, ops::Range, str::FromStr};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use itertools::Itertools;
+use rand::Rng as _;
+use thiserror::Error;
+
+use crate::{
+    bitgrid::BitGrid,
+    error::DayError,
+    gen::{self, Rng},
+    geometry::Point2,
+    grid::Grid,
+    iter::AocIteratorExt,
+    lint::{self, Diagnostic, Linter},
+    solution::{Answer, ExplainQuery, Report, Solution},
+    tool::{Invocation, Tool},
+    viz::{Drawing, Image, Rgb, PALETTE},
+};
+
+/// The ways an image of the sky can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Invalid character {0}")]
+    Cell(char),
+    #[error("Row {row} is {len} wide, but the first is {width}")]
+    Ragged {
+        row: usize,
+        len: usize,
+        width: usize,
+    },
+}
+
+impl DayError for Error {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Input {
+    grid: BitGrid,
+}
+
+impl FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .map(|l| {
+                l.chars()
+                    .map(|c| match c {
+                        '.' => Ok(false),
+                        '#' => Ok(true),
+                        c => Err(Error::Cell(c)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = rows.first().map_or(0, Vec::len);
+
+        if let Some(row) = rows.iter().position(|r| r.len() != width) {
+            return Err(Error::Ragged {
+                row: row + 1,
+                len: rows[row].len(),
+                width,
+            });
+        }
+
+        let grid = Grid::from_rows(rows).expect("the rows are as wide as the first");
+        Ok(Self {
+            grid: BitGrid::from(&grid),
+        })
+    }
+}
+
+/// Sum of the pairwise absolute differences of `values`.
+///
+/// After sorting, each value is larger than all values before it, so
+/// its contribution is `value * i - prefix_sum`.
+fn axis_distance_sum(mut values: Vec<usize>) -> usize {
+    values.sort_unstable();
+
+    let mut prefix_sum = 0;
+    let mut total = 0;
+
+    for (i, v) in values.into_iter().enumerate() {
+        total += v * i - prefix_sum;
+        prefix_sum += v;
+    }
+
+    total
+}
+
+/// Sum of the Manhattan distances of all pairs of coordinates in
+/// O(n log n). The distance splits into independent per-axis sums.
+fn pairwise_distance_sum(coords: &[Point2]) -> usize {
+    axis_distance_sum(coords.iter().map(|c| c.row).collect())
+        + axis_distance_sum(coords.iter().map(|c| c.col).collect())
+}
+
+/// Sum of the Chebyshev distances of all pairs of coordinates.
+///
+/// Rotating by 45 degrees to `r + c` and `r - c` turns the Chebyshev
+/// distance into half the Manhattan distance of the rotated points.
+/// `r - c` is shifted to stay positive.
+fn pairwise_chebyshev_sum(coords: &[Point2]) -> usize {
+    let shift = coords.iter().map(|c| c.col).max().unwrap_or_default();
+
+    (axis_distance_sum(coords.iter().map(|c| c.row + c.col).collect())
+        + axis_distance_sum(coords.iter().map(|c| c.row + shift - c.col).collect()))
+        / 2
+}
+
+/// How galaxies are allowed to travel. Either way, empty rows and
+/// columns expand before distances are measured.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Metric {
+    /// Steps along rows and columns, as in the puzzle.
+    #[default]
+    Manhattan,
+    /// Diagonal steps are allowed too, and cost as much as straight ones.
+    Chebyshev,
+}
+
+impl Metric {
+    #[must_use]
+    pub fn distance(self, a: Point2, b: Point2) -> usize {
+        match self {
+            Metric::Manhattan => a.manhattan(b),
+            Metric::Chebyshev => a.chebyshev(b),
+        }
+    }
+
+    /// Sum of the distances of all pairs of `coords`.
+    #[must_use]
+    pub fn pairwise_sum(self, coords: &[Point2]) -> usize {
+        match self {
+            Metric::Manhattan => pairwise_distance_sum(coords),
+            Metric::Chebyshev => pairwise_chebyshev_sum(coords),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manhattan" => Ok(Metric::Manhattan),
+            "chebyshev" => Ok(Metric::Chebyshev),
+            _ => bail!("Unknown metric {s}, try manhattan or chebyshev"),
+        }
+    }
+}
+
+/// A `size` by `size` image in which each line stays empty with `empty`
+/// percent probability and each cell on the other lines is a galaxy with
+/// `density` percent probability.
+fn generate_image(rng: &mut Rng, size: usize, density: u32, empty: u32) -> Result<String> {
+    ensure!(
+        density <= 100 && empty <= 100,
+        "density and empty are percentages"
+    );
+
+    let mut empty_lines = || {
+        (0..size)
+            .map(|_| rng.gen_ratio(empty, 100))
+            .collect::<Vec<_>>()
+    };
+    let (empty_rows, empty_cols) = (empty_lines(), empty_lines());
+
+    Ok(empty_rows
+        .iter()
+        .map(|&empty_row| {
+            empty_cols
+                .iter()
+                .map(|&empty_col| {
+                    if !empty_row && !empty_col && rng.gen_ratio(density, 100) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+                + "\n"
+        })
+        .collect())
+}
+
+/// Per-axis prefix counts of empty rows and columns.
+#[derive(Debug, Clone)]
+struct Expansion {
+    /// `rows[i]` is the number of empty rows before row `i`.
+    rows: Vec<usize>,
+    /// `cols[i]` is the number of empty columns before column `i`.
+    cols: Vec<usize>,
+}
+
+fn prefix_counts(len: usize, empty: &BTreeSet<usize>) -> Vec<usize> {
+    (0..len)
+        .scan(0, |count, i| {
+            let before = *count;
+            *count += usize::from(empty.contains(&i));
+            Some(before)
+        })
+        .collect()
+}
+
+impl Input {
+    #[must_use]
+    fn empty_rows(&self) -> BTreeSet<usize> {
+        (0..self.grid.rows())
+            .filter(|&i| self.grid.row_is_empty(i))
+            .collect::<BTreeSet<_>>()
+    }
+
+    #[must_use]
+    fn empty_cols(&self) -> BTreeSet<usize> {
+        (0..self.grid.cols())
+            .filter(|&i| self.grid.col_is_empty(i))
+            .collect::<BTreeSet<_>>()
+    }
+
+    #[must_use]
+    fn expand_rows(&self) -> Input {
+        let empty_rows = self.empty_rows();
+        let shift = prefix_counts(self.grid.rows(), &empty_rows);
+        let mut grid = BitGrid::new(self.grid.rows() + empty_rows.len(), self.grid.cols());
+
+        for galaxy in self.grid.ones() {
+            grid.set(
+                Point2::new(galaxy.row + shift[galaxy.row], galaxy.col),
+                true,
+            );
+        }
+
+        Self { grid }
+    }
+
+    #[must_use]
+    fn expand_cols(&self) -> Input {
+        let transposed = Input {
+            grid: self.grid.transpose(),
+        };
+
+        Self {
+            grid: transposed.expand_rows().grid.transpose(),
+        }
+    }
+
+    #[must_use]
+    fn expand(&self) -> Input {
+        self.expand_rows().expand_cols()
+    }
+
+    #[must_use]
+    fn galaxies(&self) -> Vec<Point2> {
+        self.grid.ones().collect()
+    }
+
+    #[must_use]
+    fn expansion(&self) -> Expansion {
+        Expansion {
+            rows: prefix_counts(self.grid.rows(), &self.empty_rows()),
+            cols: prefix_counts(self.grid.cols(), &self.empty_cols()),
+        }
+    }
+
+    fn expand_coords(&self, coords: &[Point2], factor: usize) -> Vec<Point2> {
+        let expansion = self.expansion();
+
+        coords
+            .iter()
+            .map(|c| {
+                Point2::new(
+                    c.row + expansion.rows[c.row] * factor,
+                    c.col + expansion.cols[c.col] * factor,
+                )
+            })
+            .collect()
+    }
+
+    /// Sum of the pairwise distances of all galaxies in the universe
+    /// where each empty row or column is expanded by `factor` additional
+    /// ones.
+    #[must_use]
+    pub fn distance_sum(&self, metric: Metric, factor: usize) -> usize {
+        metric.pairwise_sum(&self.expand_coords(&self.galaxies(), factor))
+    }
+
+    /// Like [`Input::distance_sum`], but only for the galaxies inside the
+    /// given rectangle of the unexpanded universe.
+    #[must_use]
+    pub fn distance_sum_in_rect(
+        &self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+        metric: Metric,
+        factor: usize,
+    ) -> usize {
+        let selected = self.galaxies_in_rect(&rows, &cols);
+
+        metric.pairwise_sum(&self.expand_coords(&selected, factor))
+    }
+
+    /// The galaxies in `rows` and `cols`, before the expansion.
+    fn galaxies_in_rect(&self, rows: &Range<usize>, cols: &Range<usize>) -> Vec<Point2> {
+        self.galaxies()
+            .into_iter()
+            .filter(|c| rows.contains(&c.row) && cols.contains(&c.col))
+            .collect()
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Input;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Input, Error> {
+        Input::from_str(input)
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        let mut linter = Linter::new();
+        let lines = lint::lines(&mut linter, input);
+
+        linter.grid(&lines, |c| c == '.' || c == '#');
+        linter.finish()
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(input.distance_sum(Metric::Manhattan, 1).into())
+    }
+
+    fn part2(input: &Input) -> Result<Option<Answer>> {
+        Ok(Some(
+            input.distance_sum(Metric::Manhattan, 1000000 - 1).into(),
+        ))
+    }
+
+    /// Expands the grid itself for part 1 and goes through all pairs of
+    /// galaxies.
+    fn reference(input: &Input) -> Result<[Option<Answer>; 2]> {
+        let pairs_sum = |galaxies: Vec<Point2>| {
+            galaxies
+                .into_iter()
+                .tuple_combinations()
+                .map(|(c1, c2)| c1.manhattan(c2))
+                .sum::<usize>()
+        };
+
+        Ok([
+            Some(pairs_sum(input.expand().galaxies()).into()),
+            Some(pairs_sum(input.expand_coords(&input.galaxies(), 1000000 - 1)).into()),
+        ])
+    }
+
+    /// A `size` by `size` image. The parameters `density` (default 2) and
+    /// `empty` (default 7) give the percentage of galaxies and of empty
+    /// rows and columns; the defaults are close to the real inputs.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        let params = gen::params();
+
+        generate_image(
+            rng,
+            size,
+            params.get_or("density", 2)?,
+            params.get_or("empty", 7)?,
+        )
+    }
+
+    /// Galaxies are white, and the empty rows and columns that expand
+    /// are tinted.
+    fn render(input: &Input) -> Result<Drawing> {
+        let (rows, cols) = (input.empty_rows(), input.empty_cols());
+
+        Ok(Drawing::from(Image::from_fn(
+            input.grid.rows(),
+            input.grid.cols(),
+            |p| {
+                if input.grid.get(p) {
+                    Rgb::WHITE
+                } else if rows.contains(&p.row) || cols.contains(&p.col) {
+                    PALETTE[4]
+                } else {
+                    Rgb::NIGHT
+                }
+            },
+        )))
+    }
+
+    /// Sums the galaxy distances inside `rows=a..b cols=c..d`, expanding
+    /// empty lines by `factor` (default 1). With `metric=chebyshev`,
+    /// galaxies may also travel diagonally.
+    fn explain(input: &Input, query: &ExplainQuery) -> Result<Report> {
+        let rows = query.range_or("rows", 0..input.grid.rows())?;
+        let cols = query.range_or("cols", 0..input.grid.cols())?;
+        let metric = query.get_or("metric", Metric::Manhattan)?;
+        let factor = query.get_or("factor", 1)?;
+
+        let selected = input.galaxies_in_rect(&rows, &cols);
+
+        let mut report = Report::new("Galaxy distances in a rectangle");
+        report
+            .line(format!(
+                "rows {rows:?}, columns {cols:?}, expansion {factor}, {metric:?} metric"
+            ))
+            .line(format!(
+                "{} galaxies, {} pairs",
+                selected.len(),
+                selected.iter().pairwise_combinations_count()
+            ))
+            .line(format!(
+                "distance sum {}",
+                input.distance_sum_in_rect(rows, cols, metric, factor)
+            ));
+
+        Ok(report)
+    }
+}
+
+/// `tool locate`: where a galaxy ends up after the expansion.
+pub const LOCATE: Tool = Tool {
+    year: super::YEAR,
+    day: 11,
+    name: "locate",
+    about: "Shows the position of a galaxy before and after expansion",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("galaxy")
+                .required(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of the galaxy, counting from 1 in reading order"),
+        )
+        .arg(
+            clap::Arg::new("factor")
+                .long("factor")
+                .default_value("1")
+                .value_parser(clap::value_parser!(usize))
+                .help("How many rows or columns are added for each empty one"),
+        )
+    },
+    run: locate,
+};
+
+fn locate(invocation: &Invocation) -> Result<Report> {
+    let input = invocation.parsed::<Day11>()?;
+    let galaxy = invocation.arg::<usize>("galaxy")?;
+    let factor = invocation.arg("factor")?;
+
+    let index = galaxy
+        .checked_sub(1)
+        .ok_or_else(|| anyhow!("Galaxies are numbered from 1"))?;
+    let position = input
+        .galaxies()
+        .get(index)
+        .copied()
+        .ok_or_else(|| anyhow!("There is no galaxy {galaxy}"))?;
+    let expanded = input.expand_coords(&[position], factor)[0];
+
+    let mut report = Report::new(format!("Galaxy {galaxy}"));
+    report
+        .line(position.to_string())
+        .line(format!("{expanded} after expansion by {factor}"));
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, iter::repeat_n};
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{geometry::Vec2, testutil};
+
+    const EXAMPLE: &str = include_str!("day11.example");
+
+    crate::example_test!(day11, part1 = 374, part2 = 82_000_210);
+
+    #[test]
+    fn can_parse() -> Result<()> {
+        let input = ".#.#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+        let input = Input::from_str(input)?;
+
+        assert!(input.grid[Point2::new(2, 0)]);
+
+        assert_eq!(Input::from_str("..\n.x"), Err(Error::Cell('x')));
+        assert_eq!(
+            Input::from_str("..\n#"),
+            Err(Error::Ragged {
+                row: 2,
+                len: 1,
+                width: 2
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn distance_works() {
+        assert_eq!(Point2::new(0, 10).manhattan(Point2::new(0, 2)), 8);
+        assert_eq!(Point2::new(0, 0).manhattan(Point2::new(1, 1)), 2);
+    }
+
+    #[test]
+    fn pairwise_distance_sum_matches_naive() -> Result<()> {
+        let input = Input::from_str(EXAMPLE)?;
+        let galaxies = input.expand().galaxies();
+
+        assert_eq!(
+            pairwise_distance_sum(&galaxies),
+            galaxies
+                .iter()
+                .copied()
+                .tuple_combinations()
+                .map(|(c1, c2)| c1.manhattan(c2))
+                .sum::<usize>()
+        );
+        assert_eq!(pairwise_distance_sum(&galaxies), 374);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reference_agrees_on_example() -> Result<()> {
+        let input = Input::from_str(EXAMPLE)?;
+        let [part1, part2] = Day11::reference(&input)?;
+
+        assert_eq!(part1, Some(Day11::part1(&input)?));
+        assert_eq!(part2, Day11::part2(&input)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rect_queries_work() -> Result<()> {
+        let input = Input::from_str(EXAMPLE)?;
+
+        assert_eq!(
+            input.distance_sum_in_rect(0..10, 0..10, Metric::Manhattan, 1),
+            374
+        );
+        assert_eq!(
+            input.distance_sum_in_rect(0..10, 0..10, Metric::Manhattan, 9),
+            1030
+        );
+        assert_eq!(
+            input.distance_sum_in_rect(0..10, 0..10, Metric::Manhattan, 99),
+            8410
+        );
+
+        // Galaxies at (0, 3), (1, 7) and (2, 0) expand to (0, 4), (1, 9)
+        // and (2, 0), because columns 2 and 5 are empty.
+        assert_eq!(
+            input.distance_sum_in_rect(0..3, 0..8, Metric::Manhattan, 1),
+            6 + 6 + 10
+        );
+        assert_eq!(
+            input.distance_sum_in_rect(5..5, 0..10, Metric::Manhattan, 1),
+            0
+        );
+
+        Ok(())
+    }
+
+    /// Distances by breadth-first search through the grid with its empty
+    /// lines repeated, stepping to the four or, with `diagonal`, eight
+    /// neighbouring cells.
+    fn bfs_distance_sum(input: &Input, factor: usize, diagonal: bool) -> usize {
+        let repeated = |len: usize, empty: BTreeSet<usize>| {
+            (0..len)
+                .flat_map(|i| repeat_n(i, if empty.contains(&i) { factor + 1 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let rows = repeated(input.grid.rows(), input.empty_rows());
+        let cols = repeated(input.grid.cols(), input.empty_cols());
+        let galaxy = |p: Point2| input.grid[Point2::new(rows[p.row], cols[p.col])];
+        let steps: &[Vec2] = if diagonal {
+            &Vec2::AROUND
+        } else {
+            &Vec2::SIDES
+        };
+
+        let mut sum = 0;
+        for (row, col) in (0..rows.len()).cartesian_product(0..cols.len()) {
+            let start = Point2::new(row, col);
+            if !galaxy(start) {
+                continue;
+            }
+
+            let mut distances = vec![vec![None; cols.len()]; rows.len()];
+            let mut queue = VecDeque::from([(start, 0)]);
+            distances[row][col] = Some(0);
+
+            while let Some((p, d)) = queue.pop_front() {
+                if galaxy(p) && p > start {
+                    sum += d;
+                }
+
+                for next in steps.iter().filter_map(|step| p.checked_add(*step)) {
+                    if next.row < rows.len()
+                        && next.col < cols.len()
+                        && distances[next.row][next.col].is_none()
+                    {
+                        distances[next.row][next.col] = Some(d + 1);
+                        queue.push_back((next, d + 1));
+                    }
+                }
+            }
+        }
+
+        sum
+    }
+
+    #[test]
+    fn metrics_agree_with_pathfinding() -> Result<()> {
+        let mut rng = gen::rng(1562);
+
+        for _ in 0..20 {
+            let input = Input::from_str(&generate_image(&mut rng, 7, 25, 25)?)?;
+
+            for factor in [0, 1, 3] {
+                assert_eq!(
+                    input.distance_sum(Metric::Manhattan, factor),
+                    bfs_distance_sum(&input, factor, false)
+                );
+                assert_eq!(
+                    input.distance_sum(Metric::Chebyshev, factor),
+                    bfs_distance_sum(&input, factor, true)
+                );
+            }
+        }
+
+        assert_eq!(
+            Metric::Chebyshev.distance(Point2::new(0, 4), Point2::new(9, 1)),
+            9
+        );
+        assert_eq!(Metric::from_str("chebyshev")?, Metric::Chebyshev);
+        assert!(Metric::from_str("euclid").is_err());
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn distance_sums_match_all_pairs(
+            image in testutil::grid("....#", 1..12, 1..12),
+            factor in 0..5usize,
+        ) {
+            let input = Input::from_str(&image).unwrap();
+            let galaxies = input.expand_coords(&input.galaxies(), factor);
+
+            for metric in [Metric::Manhattan, Metric::Chebyshev] {
+                prop_assert_eq!(
+                    input.distance_sum(metric, factor),
+                    galaxies
+                        .iter()
+                        .tuple_combinations()
+                        .map(|(a, b)| metric.distance(*a, *b))
+                        .sum::<usize>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generator_follows_parameters() -> Result<()> {
+        let mut rng = gen::rng(11);
+
+        assert_eq!(generate_image(&mut rng, 3, 100, 0)?, "###\n###\n###\n");
+        assert_eq!(generate_image(&mut rng, 3, 100, 100)?, "...\n...\n...\n");
+        assert!(generate_image(&mut rng, 3, 101, 0).is_err());
+
+        let input = Input::from_str(&generate_image(&mut rng, 60, 10, 20)?)?;
+        let [part1, part2] = Day11::reference(&input)?;
+
+        assert_eq!(part1, Some(Day11::part1(&input)?));
+        assert_eq!(part2, Day11::part2(&input)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,813 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    iter::repeat_n,
+    ops::AddAssign,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::{ensure, Context, Result};
+use itertools::Itertools;
+use rand::Rng as _;
+use rayon::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    cancel::CancelToken,
+    error::DayError,
+    gen::Rng,
+    input::{line_batches, line_chunks},
+    lint::{self, Diagnostic},
+    parallel::maybe_par_map,
+    solution::{Answer, Report, Solution},
+    tool::{Invocation, Tool},
+};
+
+/// The ways a row of the condition records can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Invalid input line: {0}")]
+    Line(String),
+    #[error("Invalid state: {0}")]
+    State(char),
+    #[error("Can't parse group {0}")]
+    Group(String),
+}
+
+impl DayError for Error {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum SpringState {
+    Operational,
+    Broken,
+}
+
+#[must_use]
+fn broken_groups(states: &[SpringState]) -> Vec<usize> {
+    states
+        .iter()
+        .copied()
+        .group_by(|state| *state == SpringState::Broken)
+        .into_iter()
+        .filter_map(|(is_broken, group)| is_broken.then_some(group.count()))
+        .collect()
+}
+
+#[must_use]
+fn missing_broken(a: &[usize], b: &[usize]) -> usize {
+    a.iter().sum::<usize>().abs_diff(b.iter().sum::<usize>())
+}
+
+struct SpringStateIterator<'a, 'b> {
+    input: &'a [Option<SpringState>],
+    broken_groups: &'b [usize],
+
+    backtrack_stack: Vec<Vec<SpringState>>,
+    cancel: CancelToken,
+}
+
+impl<'a, 'b> SpringStateIterator<'a, 'b> {
+    fn new(input: &'a [Option<SpringState>], broken_groups: &'b [usize]) -> Self {
+        Self {
+            input,
+            broken_groups,
+            backtrack_stack: vec![vec![]],
+            cancel: CancelToken::default(),
+        }
+    }
+
+    /// Stops the iteration early once `cancel` is cancelled.
+    fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+}
+
+impl<'a, 'b> SpringStateIterator<'a, 'b> {
+    fn maybe_backtrack(&mut self, backtrack_candidate: Vec<SpringState>) {
+        let mut candidate_groups = broken_groups(&backtrack_candidate);
+
+        // Full input, everything must match!
+        if backtrack_candidate.len() == self.input.len() {
+            if candidate_groups == self.broken_groups {
+                self.backtrack_stack.push(backtrack_candidate);
+            }
+
+            return;
+        }
+
+        let last_is_operational = backtrack_candidate
+            .last()
+            .map(|c| *c == SpringState::Operational)
+            .unwrap_or(false);
+
+        // More groups than the target?
+        if candidate_groups.len() > self.broken_groups.len() {
+            return;
+        }
+
+        // Can we fit all remaining groups?
+        let remaining_items = self.input.len() - backtrack_candidate.len();
+        let missing_broken = missing_broken(&candidate_groups, self.broken_groups);
+        let missing_groups = self.broken_groups.len() - candidate_groups.len();
+        let missing_operational = if missing_groups != 0 {
+            // Each group needs to be terminated by one operational tile except the last.
+            missing_groups - 1
+        } else {
+            0
+        };
+
+        if missing_broken + missing_operational > remaining_items {
+            return;
+        }
+
+        // The last group might not be complete.
+        if !last_is_operational && !candidate_groups.is_empty() {
+            candidate_groups.pop();
+        }
+
+        // Everything we found so far has to match.
+        if candidate_groups != self.broken_groups[0..candidate_groups.len()] {
+            return;
+        }
+
+        self.backtrack_stack.push(backtrack_candidate);
+    }
+}
+
+impl<'a, 'b> Iterator for SpringStateIterator<'a, 'b> {
+    type Item = Vec<SpringState>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.cancel.is_cancelled() {
+                return None;
+            }
+
+            let mut cur = self.backtrack_stack.pop()?;
+
+            if cur.len() == self.input.len() {
+                return Some(cur);
+            } else {
+                match self.input[cur.len()] {
+                    Some(s) => {
+                        cur.push(s);
+                        self.maybe_backtrack(cur);
+                    }
+                    None => {
+                        let mut other = cur.clone();
+
+                        cur.push(SpringState::Operational);
+                        other.push(SpringState::Broken);
+
+                        self.maybe_backtrack(cur);
+                        self.maybe_backtrack(other);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Line {
+    states: Vec<Option<SpringState>>,
+    broken_groups: Vec<usize>,
+}
+
+impl Line {
+    /// Counts the arrangements, or fails if `cancel` fires before we're done.
+    fn solutions(&self, cancel: &CancelToken) -> Result<usize> {
+        let count = SpringStateIterator::new(&self.states, &self.broken_groups)
+            .with_cancel(cancel.clone())
+            .inspect(|st| debug_assert_eq!(broken_groups(st), self.broken_groups))
+            .count();
+
+        cancel.check()?;
+        Ok(count)
+    }
+
+    /// The number of arrangements for lines of only unknown springs, by
+    /// stars and bars: the springs that aren't needed for the groups and
+    /// the gaps between them go into the `k + 1` gaps around the groups.
+    /// `None` if there are known springs or the count overflows.
+    #[must_use]
+    fn all_unknown_solutions(&self) -> Option<usize> {
+        if self.states.iter().any(Option::is_some) {
+            return None;
+        }
+
+        let groups = self.broken_groups.len();
+        let needed = self.broken_groups.iter().sum::<usize>() + groups.saturating_sub(1);
+
+        match self.states.len().checked_sub(needed) {
+            Some(free) => binomial(free + groups, groups),
+            None => Some(0),
+        }
+    }
+
+    /// Compares the dynamic programming with the closed form for lines
+    /// of only unknown springs, folded and unfolded. Other lines pass.
+    fn check_all_unknown(&self) -> Result<()> {
+        for line in [self.clone(), self.unfold()] {
+            if let Some(expected) = line.all_unknown_solutions() {
+                let rle = RleLine::from(&line).solutions();
+
+                ensure!(
+                    rle == expected,
+                    "Counted {rle} arrangements for {line:?}, stars and bars says {expected}"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[must_use]
+    fn unfold(&self) -> Line {
+        Line {
+            states: Itertools::intersperse(repeat_n(&self.states, 5), &vec![None])
+                .flatten()
+                .copied()
+                .collect::<Vec<Option<SpringState>>>(),
+            broken_groups: repeat_n(&self.broken_groups, 5)
+                .flatten()
+                .copied()
+                .collect::<Vec<usize>>(),
+        }
+    }
+}
+
+/// `n` choose `k`, unless it overflows.
+#[must_use]
+fn binomial(n: usize, k: usize) -> Option<usize> {
+    let k = k.min(n.checked_sub(k)?);
+
+    (0..k)
+        .try_fold(1u128, |acc, i| {
+            Some(acc.checked_mul(u128::try_from(n - i).ok()?)? / (i as u128 + 1))
+        })
+        .and_then(|c| usize::try_from(c).ok())
+}
+
+/// A stretch of springs in the same (possibly unknown) state.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Run {
+    state: Option<SpringState>,
+    len: usize,
+}
+
+/// A [`Line`] with its states run-length encoded. Counting arrangements
+/// works on the runs with memoization instead of enumerating them, which
+/// makes the unfolded lines tractable.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct RleLine {
+    runs: Vec<Run>,
+    broken_groups: Vec<usize>,
+}
+
+/// Position in an [`RleLine`]: a run and the offset into it.
+type Cursor = (usize, usize);
+
+impl RleLine {
+    #[must_use]
+    fn solutions(&self) -> usize {
+        self.count((0, 0), 0, &mut HashMap::new())
+    }
+
+    /// Moves `cursor` forward by `n` springs, which must all be broken or
+    /// unknown. Returns `None` if that's impossible.
+    #[must_use]
+    fn skip_broken(&self, (mut run, mut offset): Cursor, mut n: usize) -> Option<Cursor> {
+        while n > 0 {
+            let cur = self.runs.get(run)?;
+            if cur.state == Some(SpringState::Operational) {
+                return None;
+            }
+
+            let take = n.min(cur.len - offset);
+            n -= take;
+            offset += take;
+
+            if offset == cur.len {
+                (run, offset) = (run + 1, 0);
+            }
+        }
+
+        Some((run, offset))
+    }
+
+    #[must_use]
+    fn next(&self, (run, offset): Cursor) -> Cursor {
+        if offset + 1 == self.runs[run].len {
+            (run + 1, 0)
+        } else {
+            (run, offset + 1)
+        }
+    }
+
+    /// Counts the arrangements of `broken_groups[group..]` in the springs
+    /// starting at `cursor`.
+    fn count(
+        &self,
+        cursor: Cursor,
+        group: usize,
+        memo: &mut HashMap<(Cursor, usize), usize>,
+    ) -> usize {
+        let Some(run) = self.runs.get(cursor.0) else {
+            return usize::from(group == self.broken_groups.len());
+        };
+
+        crate::memoized!(
+            memo,
+            (cursor, group),
+            match run.state {
+                // Nothing can start in an operational run, skip it entirely.
+                Some(SpringState::Operational) => self.count((cursor.0 + 1, 0), group, memo),
+                state => {
+                    let as_operational = if state.is_none() {
+                        self.count(self.next(cursor), group, memo)
+                    } else {
+                        0
+                    };
+
+                    let as_group_start = self
+                        .broken_groups
+                        .get(group)
+                        .and_then(|&len| self.skip_broken(cursor, len))
+                        .map_or(0, |end| match self.runs.get(end.0) {
+                            None => self.count(end, group + 1, memo),
+                            // The group would be longer than it should be.
+                            Some(r) if r.state == Some(SpringState::Broken) => 0,
+                            Some(_) => self.count(self.next(end), group + 1, memo),
+                        });
+
+                    as_operational + as_group_start
+                }
+            }
+        )
+    }
+}
+
+impl From<&Line> for RleLine {
+    fn from(line: &Line) -> Self {
+        Self {
+            runs: line
+                .states
+                .iter()
+                .group_by(|s| **s)
+                .into_iter()
+                .map(|(state, run)| Run {
+                    state,
+                    len: run.count(),
+                })
+                .collect(),
+            broken_groups: line.broken_groups.clone(),
+        }
+    }
+}
+
+impl FromStr for Line {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (states_str, groups_str) =
+            s.split_once(' ').ok_or_else(|| Error::Line(s.to_owned()))?;
+
+        let states = states_str
+            .chars()
+            .map(|c| match c {
+                '.' => Ok(Some(SpringState::Operational)),
+                '#' => Ok(Some(SpringState::Broken)),
+                '?' => Ok(None),
+                c => Err(Error::State(c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let broken_groups = groups_str
+            .split(',')
+            .map(|s| s.parse::<usize>().map_err(|_| Error::Group(s.to_owned())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            states,
+            broken_groups,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Input {
+    lines: Vec<Line>,
+}
+
+impl FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            lines: s
+                .lines()
+                .map(Line::from_str)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+/// The answers of both parts as computed by [`stream`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Totals {
+    pub arrangements: usize,
+    pub unfolded: usize,
+}
+
+impl AddAssign for Totals {
+    fn add_assign(&mut self, other: Self) {
+        self.arrangements += other.arrangements;
+        self.unfolded += other.unfolded;
+    }
+}
+
+/// Inputs are read in batches of this many bytes, which bounds the
+/// memory [`stream`] needs.
+const BATCH_SIZE: usize = 1 << 24;
+
+/// Batches are split into chunks of this many bytes to spread them over
+/// all cores.
+const CHUNK_SIZE: usize = 1 << 18;
+
+/// Solves both parts of the lines in `chunk`.
+fn solve_chunk(chunk: &str) -> Result<Totals> {
+    let mut totals = Totals::default();
+
+    for line in chunk.lines() {
+        let line = Line::from_str(line)?;
+        totals += Totals {
+            arrangements: RleLine::from(&line).solutions(),
+            unfolded: RleLine::from(&line.unfold()).solutions(),
+        };
+    }
+
+    Ok(totals)
+}
+
+/// Solves both parts while only holding a batch of lines in memory, so
+/// inputs can be larger than memory. Lines are independent, so the
+/// counts of batches and chunks just add up.
+pub fn stream(reader: impl BufRead) -> Result<Totals> {
+    stream_batches(reader, BATCH_SIZE)
+}
+
+fn stream_batches(reader: impl BufRead, batch_size: usize) -> Result<Totals> {
+    let cancel = CancelToken::current();
+    let mut totals = Totals::default();
+
+    for batch in line_batches(reader, batch_size) {
+        let batch = batch.context("Failed to read input")?;
+
+        for chunk in maybe_par_map(&line_chunks(&batch, CHUNK_SIZE), |c| solve_chunk(c)) {
+            totals += chunk?;
+        }
+
+        cancel.check()?;
+    }
+
+    Ok(totals)
+}
+
+/// `tool stream`: both parts of inputs that are too large to parse as a
+/// whole, like the ones `generate` makes with millions of lines.
+pub const STREAM: Tool = Tool {
+    year: super::YEAR,
+    day: 12,
+    name: "stream",
+    about: "Solves an input of any size line by line",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("file")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("The input to solve, read from stdin if missing"),
+        )
+    },
+    run: stream_file,
+};
+
+fn stream_file(invocation: &Invocation) -> Result<Report> {
+    let totals = match invocation.matches.get_one::<PathBuf>("file") {
+        Some(path) => {
+            stream(BufReader::new(File::open(path).with_context(|| {
+                format!("Failed to open {}", path.display())
+            })?))?
+        }
+        None => stream(io::stdin().lock())?,
+    };
+
+    let mut report = Report::new("Arrangements");
+    report.line(format!("Part 1: {}", totals.arrangements));
+    report.line(format!("Part 2: {}", totals.unfolded));
+
+    Ok(report)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = Input;
+    type Error = Error;
+
+    /// Backtracking is too slow for part 2, so it is only kept as the
+    /// reference.
+    fn parse(input: &str) -> Result<Input, Error> {
+        Input::from_str(input)
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        lint::each_line(input, |c| {
+            c.many(|ch| ".#?".contains(ch), "a spring")?;
+            c.literal(" ")?;
+
+            loop {
+                c.number()?;
+                if c.is_at_end() {
+                    return Ok(());
+                }
+                c.literal(",")?;
+            }
+        })
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(input
+            .lines
+            .par_iter()
+            .map(|l| RleLine::from(l).solutions())
+            .sum::<usize>()
+            .into())
+    }
+
+    /// Backtracking for part 1. It is too slow for part 2, but lines of
+    /// only unknown springs are checked against their closed form.
+    fn reference(input: &Input) -> Result<[Option<Answer>; 2]> {
+        let cancel = CancelToken::current();
+
+        for line in &input.lines {
+            line.check_all_unknown()?;
+        }
+
+        let sum = input
+            .lines
+            .iter()
+            .map(|l| l.solutions(&cancel))
+            .sum::<Result<usize>>()?;
+
+        Ok([Some(sum.into()), None])
+    }
+
+    fn part2(input: &Input) -> Result<Option<Answer>> {
+        Ok(Some(
+            input
+                .lines
+                .par_iter()
+                .map(|l| RleLine::from(&l.unfold()).solutions())
+                .sum::<usize>()
+                .into(),
+        ))
+    }
+
+    /// `size` rows of up to 20 springs. The row is drawn first and then
+    /// partly hidden, so there is at least one arrangement.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok((0..size)
+            .map(|_| {
+                let len = rng.gen_range(1..=20);
+                let mut states = (0..len)
+                    .map(|_| {
+                        if rng.gen_ratio(1, 2) {
+                            SpringState::Broken
+                        } else {
+                            SpringState::Operational
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                states[rng.gen_range(0..len)] = SpringState::Broken;
+
+                let row = states
+                    .iter()
+                    .map(|state| match (rng.gen_ratio(2, 5), state) {
+                        (true, _) => '?',
+                        (false, SpringState::Broken) => '#',
+                        (false, SpringState::Operational) => '.',
+                    })
+                    .collect::<String>();
+
+                format!("{row} {}\n", broken_groups(&states).iter().join(","))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broken_groups_works() {
+        use SpringState::*;
+
+        assert_eq!(broken_groups(&[]), [0usize; 0]);
+        assert_eq!(
+            broken_groups(&[Operational, Broken, Broken, Operational, Broken]),
+            [2, 1]
+        );
+        assert_eq!(broken_groups(&[Operational]), [0usize; 0]);
+        assert_eq!(broken_groups(&[Broken]), [1]);
+    }
+
+    #[test]
+    fn spring_state_iterator_works() {
+        use SpringState::*;
+
+        let ex1 = [Some(Operational)];
+        let bg1: [usize; 0] = [];
+        let re1 = vec![vec![Operational]];
+        assert_eq!(
+            SpringStateIterator::new(&ex1, &bg1).collect::<Vec<_>>(),
+            re1
+        );
+
+        let ex2 = [None];
+        let bg2: [usize; 0] = [];
+        let re2 = vec![vec![Operational]];
+        assert_eq!(
+            SpringStateIterator::new(&ex2, &bg2).collect::<Vec<_>>(),
+            re2
+        );
+
+        let ex3 = [None];
+        let bg3: [usize; 1] = [1];
+        let re3 = vec![vec![Broken]];
+        assert_eq!(
+            SpringStateIterator::new(&ex3, &bg3).collect::<Vec<_>>(),
+            re3
+        );
+    }
+
+    #[test]
+    fn binomial_works() {
+        assert_eq!(binomial(5, 0), Some(1));
+        assert_eq!(binomial(5, 2), Some(10));
+        assert_eq!(binomial(5, 5), Some(1));
+        assert_eq!(binomial(2, 3), None);
+        assert_eq!(binomial(1000, 500), None);
+    }
+
+    #[test]
+    fn stars_and_bars_agree_with_solvers() -> Result<()> {
+        let cancel = CancelToken::default();
+
+        for len in 1..=10 {
+            for groups in (1..=4).flat_map(|k| repeat_n(1..=3, k).multi_cartesian_product()) {
+                let line =
+                    Line::from_str(&format!("{} {}", "?".repeat(len), groups.iter().join(",")))?;
+                let expected = line.all_unknown_solutions();
+
+                assert_eq!(expected, Some(line.solutions(&cancel)?), "{line:?}");
+                line.check_all_unknown()?;
+            }
+        }
+
+        assert_eq!(Line::from_str("?#? 1")?.all_unknown_solutions(), None);
+        assert_eq!(Line::from_str("?#?"), Err(Error::Line("?#?".to_owned())));
+        assert_eq!(Line::from_str("?x? 1"), Err(Error::State('x')));
+        assert_eq!(Line::from_str("?#? 1,a"), Err(Error::Group("a".to_owned())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unfold_works() {
+        use SpringState::*;
+
+        let line = Line {
+            states: vec![Some(Operational), Some(Broken)],
+            broken_groups: vec![1],
+        };
+
+        assert_eq!(
+            line.unfold(),
+            Line {
+                states: vec![
+                    Some(Operational),
+                    Some(Broken),
+                    None,
+                    Some(Operational),
+                    Some(Broken),
+                    None,
+                    Some(Operational),
+                    Some(Broken),
+                    None,
+                    Some(Operational),
+                    Some(Broken),
+                    None,
+                    Some(Operational),
+                    Some(Broken)
+                ],
+                broken_groups: vec![1, 1, 1, 1, 1],
+            }
+        );
+    }
+
+    const EXAMPLE: &str = include_str!("day12.example");
+
+    crate::example_test!(day12, part1 = 21, part2 = 525_152);
+
+    #[test]
+    fn rle_works() -> Result<()> {
+        let input = Input::from_str(EXAMPLE)?;
+
+        assert_eq!(
+            input
+                .lines
+                .iter()
+                .map(|l| RleLine::from(l).solutions())
+                .collect::<Vec<_>>(),
+            [1, 4, 1, 1, 4, 10]
+        );
+
+        assert_eq!(
+            input
+                .lines
+                .iter()
+                .map(|l| RleLine::from(&l.unfold()).solutions())
+                .sum::<usize>(),
+            525152
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn streaming_agrees_with_parsing() -> Result<()> {
+        let input = Day12::generate(&mut crate::gen::rng(12), 2000)?;
+        let parsed = Input::from_str(&input)?;
+
+        let totals = stream_batches(input.as_bytes(), 1000)?;
+        let [part1, _] = Day12::reference(&parsed)?;
+        assert_eq!(Some(Answer::from(totals.arrangements)), part1);
+        assert_eq!(
+            totals.unfolded,
+            parsed
+                .lines
+                .iter()
+                .map(|l| RleLine::from(&l.unfold()).solutions())
+                .sum::<usize>()
+        );
+
+        assert_eq!(
+            stream(EXAMPLE.as_bytes())?,
+            Totals {
+                arrangements: 21,
+                unfolded: 525_152
+            }
+        );
+        assert!(stream("???.### 1,x".as_bytes()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rle_agrees_with_backtracking() -> Result<()> {
+        let mut rng = crate::gen::rng(12);
+        let cancel = CancelToken::default();
+
+        for _ in 0..500 {
+            let springs = (0..12)
+                .map(|_| ['.', '#', '?', '?'][rng.gen_range(0..4)])
+                .collect::<String>();
+
+            // Take the groups from a random arrangement, so there is at
+            // least one.
+            let groups = springs
+                .replace('?', "#")
+                .split('.')
+                .filter(|g| !g.is_empty())
+                .map(|g| g.len().to_string())
+                .join(",");
+            if groups.is_empty() {
+                continue;
+            }
+
+            let line = Line::from_str(&format!("{springs} {groups}"))?;
+
+            assert_eq!(
+                RleLine::from(&line).solutions(),
+                line.solutions(&cancel)?,
+                "{line:?}"
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,739 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use rand::{seq::index, Rng as _};
+use thiserror::Error;
+
+use crate::{
+    bitgrid::BitGrid,
+    chart,
+    error::DayError,
+    gen::{self, Rng},
+    geometry::Point2,
+    grid::Grid,
+    iter::AocIteratorExt,
+    lint::{self, Diagnostic, Linter},
+    parallel::par_map_timed,
+    shrink::{self, Fails},
+    solution::{Answer, ExplainQuery, Report, Solution},
+    tool::{Invocation, Tool},
+    viz::{Drawing, Image, Rgb, PALETTE},
+};
+
+/// The ways a pattern of ash and rocks can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Invalid input character {0}")]
+    Cell(char),
+    #[error("Row {row} is {len} wide, but the first is {width}")]
+    Ragged {
+        row: usize,
+        len: usize,
+        width: usize,
+    },
+}
+
+impl DayError for Error {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Field {
+    Ash,
+    Rock,
+}
+
+/// Rocks are the set cells.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Maze {
+    grid: BitGrid,
+}
+
+fn mirror_point<T: PartialEq>(list: &[T]) -> Option<usize> {
+    assert!(!list.is_empty());
+
+    for i in 1..list.len() {
+        let (before, after) = list.split_at(i);
+        let len = std::cmp::min(before.len(), after.len());
+
+        if before.iter().rev().take(len).collect::<Vec<_>>()
+            == after[0..len].iter().collect::<Vec<_>>()
+        {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+/// How many cells differ between the two sides of the line before
+/// `i`.
+fn smudges(list: &[Vec<Field>], i: usize) -> usize {
+    let (before, after) = list.split_at(i);
+
+    before
+        .iter()
+        .rev()
+        .zip(after)
+        .map(|(a, b)| a.iter().zip(b).filter(|(a, b)| a != b).count())
+        .sum()
+}
+
+/// All lines across which `list` mirrors with exactly `count` smudges.
+fn axes(list: &[Vec<Field>], count: usize) -> Vec<usize> {
+    (1..list.len())
+        .filter(|&i| smudges(list, i) == count)
+        .collect()
+}
+
+fn transpose(rows: &[Vec<Field>]) -> Vec<Vec<Field>> {
+    Grid::from_rows(rows.to_vec())
+        .expect("patterns are rectangular")
+        .transpose()
+        .to_rows()
+}
+
+/// A pattern that mirrors perfectly along one row, or `None` if chance
+/// added further axes. With a `smudge`, it also mirrors along one column
+/// but for a single cell, like the puzzle's, and without one along no
+/// other line.
+fn generate_pattern(
+    rng: &mut Rng,
+    rows: usize,
+    cols: usize,
+    smudge: bool,
+) -> Option<Vec<Vec<Field>>> {
+    // The row axis must leave rows outside of its reflection for the
+    // smudge to go to, or the smudge would break it.
+    let row_axes = (1..rows)
+        .filter(|&a| !smudge || 2 * a.min(rows - a) < rows)
+        .collect::<Vec<_>>();
+    let row_axis = row_axes[rng.gen_range(0..row_axes.len())];
+    let col_axis = rng.gen_range(1..cols);
+    let row_span = row_axis.min(rows - row_axis);
+    let col_span = if smudge {
+        col_axis.min(cols - col_axis)
+    } else {
+        0
+    };
+
+    let mut pattern = (0..rows)
+        .map(|_| {
+            let mut row = (0..cols)
+                .map(|_| {
+                    if rng.gen_ratio(1, 2) {
+                        Field::Rock
+                    } else {
+                        Field::Ash
+                    }
+                })
+                .collect::<Vec<_>>();
+            for k in 0..col_span {
+                row[col_axis + k] = row[col_axis - 1 - k];
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+    for k in 0..row_span {
+        pattern[row_axis + k] = pattern[row_axis - 1 - k].clone();
+    }
+
+    if !smudge {
+        let columns = transpose(&pattern);
+        return (axes(&pattern, 0) == [row_axis]
+            && axes(&columns, 0).is_empty()
+            && axes(&pattern, 1).is_empty()
+            && axes(&columns, 1).is_empty())
+        .then_some(pattern);
+    }
+
+    let free_rows = (0..rows)
+        .filter(|r| !(row_axis - row_span..row_axis + row_span).contains(r))
+        .collect::<Vec<_>>();
+    let row = free_rows[rng.gen_range(0..free_rows.len())];
+    let col = rng.gen_range(col_axis - col_span..col_axis + col_span);
+    pattern[row][col] = match pattern[row][col] {
+        Field::Ash => Field::Rock,
+        Field::Rock => Field::Ash,
+    };
+
+    let columns = transpose(&pattern);
+    (axes(&pattern, 0) == [row_axis]
+        && axes(&columns, 0).is_empty()
+        && axes(&pattern, 1).is_empty()
+        && axes(&columns, 1) == [col_axis])
+    .then_some(pattern)
+}
+
+/// `count` patterns of 5 to `side` rows and columns, or just 5 if `side`
+/// is smaller. Each mirrors along exactly one line, and `smudged` percent
+/// of them also along exactly one other line with a single smudge. Which
+/// of them is a row is up to chance.
+fn generate_patterns(rng: &mut Rng, count: usize, side: usize, smudged: u32) -> String {
+    let side = side.max(5);
+    (0..count)
+        .map(|_| {
+            let smudge = rng.gen_ratio(smudged.min(100), 100);
+            let pattern = loop {
+                let (rows, cols) = (rng.gen_range(5..=side), rng.gen_range(5..=side));
+                if let Some(pattern) = generate_pattern(rng, rows, cols, smudge) {
+                    break pattern;
+                }
+            };
+            let pattern = if rng.gen_ratio(1, 2) {
+                transpose(&pattern)
+            } else {
+                pattern
+            };
+
+            pattern
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|field| match field {
+                            Field::Ash => '.',
+                            Field::Rock => '#',
+                        })
+                        .collect::<String>()
+                        + "\n"
+                })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+/// A line a pattern mirrors across: after this many rows, or after this
+/// many columns.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Axis {
+    Row(usize),
+    Col(usize),
+}
+
+/// All axes of `rows` with exactly `count` smudges, in either direction.
+fn all_axes(rows: &[Vec<Field>], count: usize) -> Vec<Axis> {
+    axes(rows, count)
+        .into_iter()
+        .map(Axis::Row)
+        .chain(axes(&transpose(rows), count).into_iter().map(Axis::Col))
+        .collect()
+}
+
+/// What flipping cells did to the axis of a pattern.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+struct Stability {
+    trials: usize,
+    /// The axis is still perfect.
+    survived: usize,
+    /// The axis broke, but the pattern mirrors perfectly across another
+    /// one now.
+    moved: usize,
+    /// The axis that had a single smudge became perfect.
+    smudge_fixed: usize,
+}
+
+/// Flips `flips` distinct random cells of `rows` in each of `trials`
+/// trials and checks its axes after each.
+fn perturb(rng: &mut Rng, rows: &[Vec<Field>], flips: usize, trials: usize) -> Stability {
+    let cols = rows[0].len();
+    let before = all_axes(rows, 0);
+    let smudged = all_axes(rows, 1);
+    let mut stability = Stability {
+        trials,
+        ..Stability::default()
+    };
+
+    for _ in 0..trials {
+        let mut flipped = rows.to_vec();
+        for cell in index::sample(rng, rows.len() * cols, flips.min(rows.len() * cols)) {
+            let field = &mut flipped[cell / cols][cell % cols];
+            *field = match field {
+                Field::Ash => Field::Rock,
+                Field::Rock => Field::Ash,
+            };
+        }
+
+        let after = all_axes(&flipped, 0);
+        if before.iter().all(|a| after.contains(a)) {
+            stability.survived += 1;
+        } else if !after.is_empty() {
+            stability.moved += 1;
+        }
+        if smudged.iter().any(|a| after.contains(a)) {
+            stability.smudge_fixed += 1;
+        }
+    }
+
+    stability
+}
+
+impl Maze {
+    /// Compares whole rows of bits at once.
+    fn mirror_row(&self) -> Option<usize> {
+        mirror_point(
+            &(0..self.grid.rows())
+                .map(|r| self.grid.row(r))
+                .collect_vec(),
+        )
+    }
+
+    fn mirror_col(&self) -> Option<usize> {
+        Maze {
+            grid: self.grid.transpose(),
+        }
+        .mirror_row()
+    }
+
+    /// The fields cell by cell, for flipping them.
+    fn rows(&self) -> Vec<Vec<Field>> {
+        (0..self.grid.rows())
+            .map(|row| {
+                (0..self.grid.cols())
+                    .map(|col| {
+                        if self.grid[Point2::new(row, col)] {
+                            Field::Rock
+                        } else {
+                            Field::Ash
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Rocks are bright and ash is dark. Cells that have a mirror image
+    /// across the axis are tinted, so the axis is where the tint is split
+    /// in half.
+    fn render(&self) -> Image {
+        let (rows, cols) = (self.grid.rows(), self.grid.cols());
+        let axis = self
+            .mirror_row()
+            .map(Axis::Row)
+            .or_else(|| self.mirror_col().map(Axis::Col));
+        let mirrored = |i: usize, axis: usize, len: usize| {
+            let width = axis.min(len - axis);
+            (axis - width..axis + width).contains(&i)
+        };
+
+        Image::from_fn(rows, cols, |p| {
+            let tinted = match axis {
+                Some(Axis::Row(axis)) => mirrored(p.row, axis, rows),
+                Some(Axis::Col(axis)) => mirrored(p.col, axis, cols),
+                None => false,
+            };
+
+            match (self.grid.get(p), tinted) {
+                (true, false) => Rgb::WHITE,
+                (true, true) => PALETTE[0],
+                (false, false) => Rgb::NIGHT,
+                (false, true) => Rgb::new(0x4c, 0x40, 0x00),
+            }
+        })
+    }
+
+    fn mirror_score(&self) -> usize {
+        self.mirror_row()
+            .map(|v| v * 100)
+            .or_else(|| self.mirror_col())
+            .unwrap_or(0)
+    }
+}
+
+impl FromStr for Maze {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .map(|l| {
+                l.chars()
+                    .map(|c| match c {
+                        '.' => Ok(false),
+                        '#' => Ok(true),
+                        c => Err(Error::Cell(c)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = rows.first().map_or(0, Vec::len);
+
+        if let Some(row) = rows.iter().position(|r| r.len() != width) {
+            return Err(Error::Ragged {
+                row: row + 1,
+                len: rows[row].len(),
+                width,
+            });
+        }
+
+        let grid = Grid::from_rows(rows).expect("the rows are as wide as the first");
+        Ok(Maze {
+            grid: BitGrid::from(&grid),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Input {
+    mazes: Vec<Maze>,
+}
+
+impl Input {
+    /// Scores all mazes in parallel. Returns the score of each maze
+    /// together with how long it took to compute.
+    #[must_use]
+    pub fn mirror_scores_timed(&self) -> Vec<(usize, Duration)> {
+        par_map_timed(&self.mazes, Maze::mirror_score)
+    }
+}
+
+/// How many of `times` fall into each power of two of microseconds, from
+/// the fastest to the slowest, including empty ones in between.
+fn timing_histogram(times: &[Duration]) -> Vec<(String, u128)> {
+    let bucket = |t: &Duration| 128 - t.as_micros().leading_zeros();
+    let (Some(first), Some(last)) = (
+        times.iter().map(bucket).min(),
+        times.iter().map(bucket).max(),
+    ) else {
+        return vec![];
+    };
+
+    (first..=last)
+        .map(|b| {
+            let count = times.iter().filter(|t| bucket(t) == b).count();
+            (format!("< {} µs", 1u128 << b), count as u128)
+        })
+        .collect()
+}
+
+impl FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Input {
+            mazes: s
+                .lines()
+                .split_on_blank_lines()
+                .map(|lines| Maze::from_str(&lines.join("\n")))
+                .collect::<Result<Vec<Maze>, _>>()?,
+        })
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Input;
+    type Error = Error;
+
+    const PARTS: u8 = 1;
+
+    fn parse(input: &str) -> Result<Input, Error> {
+        Input::from_str(input)
+    }
+
+    /// Dropping single rows would make the patterns ragged, so whole
+    /// patterns go.
+    fn shrink_input(input: &str, fails: &mut Fails<'_>) -> String {
+        shrink::blocks(input, fails)
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        let mut linter = Linter::new();
+        let lines = lint::lines(&mut linter, input);
+
+        for pattern in lint::blocks(&lines) {
+            linter.grid(&pattern, |c| c == '.' || c == '#');
+        }
+
+        linter.finish()
+    }
+
+    /// Patterns like the puzzle's, with up to `size` rows and columns.
+    /// The parameter `patterns` (default 100) gives how many, and
+    /// `smudged` (default 100) the percentage of them with a smudge; part
+    /// 2 has no answer for the others.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        let params = gen::params();
+        Ok(generate_patterns(
+            rng,
+            params.get_or("patterns", 100)?,
+            size,
+            params.get_or("smudged", 100)?,
+        ))
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(input
+            .mirror_scores_timed()
+            .into_iter()
+            .map(|(score, _)| score)
+            .sum::<usize>()
+            .into())
+    }
+
+    /// How long each maze took to score, as a histogram. The mazes are
+    /// scored in parallel, so the total is the sum of all threads.
+    fn explain(input: &Input, query: &ExplainQuery) -> Result<Report> {
+        let width = query.get_or("width", 50usize)?;
+        let timed = input.mirror_scores_timed();
+        let times = timed.iter().map(|(_, t)| *t).sorted().collect::<Vec<_>>();
+        let (slowest, _) = timed
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, (_, t))| *t)
+            .ok_or_else(|| anyhow!("There are no mazes"))?;
+
+        let mut report = Report::new("Time per maze");
+        report
+            .line(format!(
+                "{} mazes in {:?}, median {:?}, slowest {:?} (maze {})",
+                times.len(),
+                times.iter().sum::<Duration>(),
+                times[times.len() / 2],
+                times[times.len() - 1],
+                slowest + 1
+            ))
+            .line("");
+        for line in chart::bars(&timing_histogram(&times), width) {
+            report.line(line);
+        }
+
+        Ok(report)
+    }
+
+    /// The mazes side by side, ten to a row.
+    fn render(input: &Input) -> Result<Drawing> {
+        let mazes = input.mazes.iter().map(Maze::render).collect::<Vec<_>>();
+        Ok(Image::tile(&mazes, 10, 1, Rgb::BLACK).into())
+    }
+}
+
+/// `tool axis-stability`: how often the axis of generated patterns
+/// survives flipping a few random cells.
+pub const AXIS_STABILITY: Tool = Tool {
+    year: super::YEAR,
+    day: 13,
+    name: "axis-stability",
+    about: "Flips random cells of generated patterns and checks their axes",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("flips")
+                .long("flips")
+                .default_value("3")
+                .value_parser(clap::value_parser!(usize))
+                .help("Checks flipping 1 up to this many cells per pattern"),
+        )
+        .arg(
+            clap::Arg::new("patterns")
+                .long("patterns")
+                .default_value("100")
+                .value_parser(clap::value_parser!(usize))
+                .help("How many patterns to generate"),
+        )
+        .arg(
+            clap::Arg::new("trials")
+                .long("trials")
+                .default_value("20")
+                .value_parser(clap::value_parser!(usize))
+                .help("How often to flip cells of each pattern"),
+        )
+        .arg(
+            clap::Arg::new("seed")
+                .long("seed")
+                .default_value("0")
+                .value_parser(clap::value_parser!(u64))
+                .help("The same seed always gives the same patterns and flips"),
+        )
+    },
+    run: axis_stability,
+};
+
+fn axis_stability(invocation: &Invocation) -> Result<Report> {
+    let mut rng = gen::rng(invocation.arg("seed")?);
+    let input = Input::from_str(&generate_patterns(
+        &mut rng,
+        invocation.arg("patterns")?,
+        17,
+        100,
+    ))?;
+    let trials = invocation.arg::<usize>("trials")?;
+
+    let mut report = Report::new(format!(
+        "Axes after flipping cells of {} patterns, {trials} times each",
+        input.mazes.len()
+    ));
+    report.line(format!(
+        "{:>5}  {:>8}  {:>6}  {:>12}",
+        "flips", "survived", "moved", "smudge fixed"
+    ));
+
+    for flips in 1..=invocation.arg("flips")? {
+        let total = input
+            .mazes
+            .iter()
+            .map(|maze| perturb(&mut rng, &maze.rows(), flips, trials))
+            .fold(Stability::default(), |total, s| Stability {
+                trials: total.trials + s.trials,
+                survived: total.survived + s.survived,
+                moved: total.moved + s.moved,
+                smudge_fixed: total.smudge_fixed + s.smudge_fixed,
+            });
+        let percent = |n: usize| format!("{}%", n * 100 / total.trials.max(1));
+
+        report.line(format!(
+            "{flips:>5}  {:>8}  {:>6}  {:>12}",
+            percent(total.survived),
+            percent(total.moved),
+            percent(total.smudge_fixed)
+        ));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen;
+
+    crate::example_test!(day13, part1 = 405);
+
+    #[test]
+    fn broken_patterns_are_reported() {
+        assert_eq!(Input::from_str("#.\n.#\n\n#o"), Err(Error::Cell('o')));
+        assert_eq!(
+            Input::from_str("#.\n.#\n\n##\n#"),
+            Err(Error::Ragged {
+                row: 2,
+                len: 1,
+                width: 2
+            })
+        );
+    }
+
+    #[test]
+    fn timings_are_explained() -> Result<()> {
+        let micros = |us| Duration::from_micros(us);
+        assert_eq!(
+            timing_histogram(&[micros(3), micros(0), micros(17), micros(2)]),
+            [
+                ("< 1 µs".to_owned(), 1),
+                ("< 2 µs".to_owned(), 0),
+                ("< 4 µs".to_owned(), 2),
+                ("< 8 µs".to_owned(), 0),
+                ("< 16 µs".to_owned(), 0),
+                ("< 32 µs".to_owned(), 1),
+            ]
+        );
+        assert!(timing_histogram(&[]).is_empty());
+
+        let input = Input::from_str(include_str!("day13.example"))?;
+        let report = Day13::explain(&input, &ExplainQuery::default())?;
+        assert!(report.lines[0].starts_with("2 mazes in "));
+
+        Ok(())
+    }
+
+    /// Generates `count` mazes, all of them with a smudge.
+    fn generate_mazes(count: usize) -> String {
+        generate_patterns(&mut gen::rng(13), count, 17, 100)
+    }
+
+    #[test]
+    fn size_bounds_the_sides_of_generated_mazes() -> Result<()> {
+        for size in [1, 8, 40] {
+            let input = Input::from_str(&Day13::generate(&mut gen::rng(13), size)?)?;
+
+            assert_eq!(input.mazes.len(), 100);
+            for maze in &input.mazes {
+                let rows = maze.rows();
+                assert!((5..=size.max(5)).contains(&rows.len()));
+                assert!((5..=size.max(5)).contains(&rows[0].len()));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn generated_mazes_have_one_axis_and_one_smudge() -> Result<()> {
+        let input = Input::from_str(&generate_mazes(200))?;
+
+        for maze in &input.mazes {
+            let rows = maze.rows();
+            let cols = transpose(&rows);
+
+            assert_eq!(axes(&rows, 0).len() + axes(&cols, 0).len(), 1);
+            assert_eq!(axes(&rows, 1).len() + axes(&cols, 1).len(), 1);
+            assert_ne!(maze.mirror_score(), 0);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn generated_mazes_may_leave_out_the_smudge() -> Result<()> {
+        let smudges = |smudged| -> Result<Vec<bool>> {
+            let input = Input::from_str(&generate_patterns(&mut gen::rng(13), 100, 17, smudged))?;
+            Ok(input
+                .mazes
+                .iter()
+                .map(|maze| {
+                    let rows = maze.rows();
+                    assert_eq!(all_axes(&rows, 0).len(), 1);
+                    !all_axes(&rows, 1).is_empty()
+                })
+                .collect())
+        };
+
+        assert!(smudges(0)?.iter().all(|&smudged| !smudged));
+        assert!(smudges(100)?.iter().all(|&smudged| smudged));
+        let some = smudges(50)?;
+        assert!(some.contains(&true) && some.contains(&false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn flipping_cells_breaks_axes() {
+        let input = Input::from_str(&generate_mazes(20)).unwrap();
+        let mut rng = gen::rng(1);
+
+        for maze in &input.mazes {
+            let rows = maze.rows();
+            let stability = perturb(&mut rng, &rows, 1, 10);
+
+            assert_eq!(all_axes(&rows, 0).len(), 1);
+            assert_eq!(stability.trials, 10);
+            assert!(stability.survived + stability.moved <= 10);
+            assert_eq!(perturb(&mut rng, &rows, 0, 3).survived, 3);
+        }
+
+        // Flipping the smudge is the only single flip that fixes it.
+        let rows = vec![
+            vec![Field::Rock, Field::Ash],
+            vec![Field::Rock, Field::Rock],
+        ];
+        assert_eq!(all_axes(&rows, 1), [Axis::Row(1), Axis::Col(1)]);
+        assert!(all_axes(&rows, 0).is_empty());
+    }
+
+    #[test]
+    fn parallel_scores_match_serial() -> Result<()> {
+        let input = Input::from_str(&generate_mazes(100))?;
+
+        assert_eq!(
+            input
+                .mirror_scores_timed()
+                .into_iter()
+                .map(|(score, _)| score)
+                .collect::<Vec<_>>(),
+            input
+                .mazes
+                .iter()
+                .map(Maze::mirror_score)
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,452 @@
+use std::{cmp::max, fmt::Display, num::ParseIntError, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use rand::Rng as _;
+use thiserror::Error;
+
+use crate::{
+    dump::Table,
+    error::DayError,
+    gen::Rng,
+    lint::{self, Diagnostic},
+    solution::{Answer, ExplainQuery, Report, Solution},
+};
+
+/// The ways a game record can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Expected a count and a color, got {0:?}")]
+    Cubes(String),
+    #[error("Can't parse number {token}")]
+    Number {
+        token: String,
+        #[source]
+        error: ParseIntError,
+    },
+    #[error("Unknown color {0}")]
+    Color(String),
+    #[error("Expected a game like `Game 1: 3 blue`, got {0:?}")]
+    Game(String),
+}
+
+impl DayError for Error {}
+
+/// The cubes shown at once, or the cubes in a bag.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Reveal {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+/// The bag of part 1.
+const BAG: Reveal = Reveal {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+impl Reveal {
+    fn is_superset_of(&self, other: &Self) -> bool {
+        self.red >= other.red && self.green >= other.green && self.blue >= other.blue
+    }
+
+    fn merge_max(&self, other: &Self) -> Self {
+        Reveal {
+            red: max(self.red, other.red),
+            green: max(self.green, other.green),
+            blue: max(self.blue, other.blue),
+        }
+    }
+
+    fn power(&self) -> u32 {
+        self.red * self.green * self.blue
+    }
+}
+
+impl FromStr for Reveal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut red = 0;
+        let mut green = 0;
+        let mut blue = 0;
+
+        for m in s.trim().split(',') {
+            let (num_str, color_str) = m
+                .trim()
+                .split_once(' ')
+                .ok_or_else(|| Error::Cubes(m.to_owned()))?;
+            let num = u32::from_str(num_str).map_err(|error| Error::Number {
+                token: num_str.to_owned(),
+                error,
+            })?;
+
+            match color_str {
+                "red" => red = num,
+                "green" => green = num,
+                "blue" => blue = num,
+                _ => return Err(Error::Color(color_str.to_owned())),
+            }
+        }
+
+        Ok(Reveal { red, green, blue })
+    }
+}
+
+impl Display for Reveal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} red, {} green, {} blue",
+            self.red, self.green, self.blue
+        )
+    }
+}
+
+/// Parses a bag given as `red/green/blue`, e.g. `12/13/14`.
+fn parse_bag(s: &str) -> Result<Reveal> {
+    let counts = s
+        .split('/')
+        .map(u32::from_str)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid bag: {s}"))?;
+
+    let [red, green, blue] = counts[..] else {
+        bail!("Expected a bag as red/green/blue, got: {s}");
+    };
+
+    Ok(Reveal { red, green, blue })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Game {
+    id: u32,
+    reveals: Vec<Reveal>,
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, game_id, reveal_str) = s
+            .split_once(':')
+            .and_then(|(game, reveals)| {
+                let (prefix, id) = game.split_once(' ')?;
+                Some((prefix, id, reveals))
+            })
+            .ok_or_else(|| Error::Game(s.to_owned()))?;
+
+        if prefix != "Game" {
+            return Err(Error::Game(s.to_owned()));
+        }
+
+        let reveals = reveal_str
+            .split(';')
+            .map(Reveal::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Game {
+            id: game_id.parse().map_err(|error| Error::Number {
+                token: game_id.to_owned(),
+                error,
+            })?,
+            reveals,
+        })
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+
+        for (i, reveal) in self.reveals.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{reveal}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_games(input: &str) -> Result<Vec<Game>, Error> {
+    input.lines().map(Game::from_str).collect()
+}
+
+/// The sum of the ids of the games that are possible with each of
+/// `bags`. Goes over the games only once, however many bags there are.
+#[must_use]
+pub fn sum_of_possibles(bags: &[Reveal], games: &[Game]) -> Vec<u32> {
+    let mut sums = vec![0; bags.len()];
+
+    for game in games {
+        let needed = minimal_bag(&game.reveals);
+
+        for (sum, bag) in sums.iter_mut().zip(bags) {
+            if bag.is_superset_of(&needed) {
+                *sum += game.id;
+            }
+        }
+    }
+
+    sums
+}
+
+fn minimal_bag(reveals: &[Reveal]) -> Reveal {
+    reveals
+        .iter()
+        .fold(Reveal::default(), |acc, val| acc.merge_max(val))
+}
+
+fn sum_power(games: &[Game]) -> u32 {
+    games.iter().map(|g| minimal_bag(&g.reveals).power()).sum()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Game>;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<Game>, Error> {
+        parse_games(input)
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        lint::each_line(input, |c| {
+            c.literal("Game ")?;
+            c.number()?;
+            c.literal(": ")?;
+
+            loop {
+                c.number()?;
+                c.literal(" ")?;
+                c.one_of(&["red", "green", "blue"])?;
+
+                if c.is_at_end() {
+                    return Ok(());
+                }
+                c.one_of(&[", ", "; "])?;
+            }
+        })
+    }
+
+    fn part1(games: &Vec<Game>) -> Result<Answer> {
+        Ok(sum_of_possibles(&[BAG], games)[0].into())
+    }
+
+    fn part2(games: &Vec<Game>) -> Result<Option<Answer>> {
+        Ok(Some(sum_power(games).into()))
+    }
+
+    /// How many games are possible with each of `bags=12/13/14,20/5/5`,
+    /// given as red/green/blue. Defaults to the bag of part 1.
+    fn explain(games: &Vec<Game>, query: &ExplainQuery) -> Result<Report> {
+        let bags = query.get("bags").map_or(Ok(vec![BAG]), |bags| {
+            bags.split(',').map(parse_bag).collect()
+        })?;
+
+        let mut report = Report::new("Sum of possible game ids per bag");
+        for (bag, sum) in bags.iter().zip(sum_of_possibles(&bags, games)) {
+            report.line(format!("{bag}: {sum}"));
+        }
+
+        Ok(report)
+    }
+
+    /// `size` games with up to six reveals each.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok((1..=size)
+            .map(|id| {
+                let reveals = (0..rng.gen_range(1..=6))
+                    .map(|_| {
+                        let mut counts = [0; 3].map(|_| rng.gen_range(0..=20));
+                        if counts.iter().all(|c| *c == 0) {
+                            counts[rng.gen_range(0..3)] = 1;
+                        }
+
+                        counts
+                            .iter()
+                            .zip(["red", "green", "blue"])
+                            .filter(|(count, _)| **count > 0)
+                            .map(|(count, color)| format!("{count} {color}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .collect::<Vec<_>>();
+
+                format!("Game {id}: {}\n", reveals.join("; "))
+            })
+            .collect())
+    }
+
+    /// The minimal bag of each game and its power, which add up to the
+    /// answer of part 2.
+    fn dump(games: &Vec<Game>) -> Result<Table> {
+        let mut table = Table::new(&["game", "red", "green", "blue", "power"]);
+
+        for game in games {
+            let bag = minimal_bag(&game.reveals);
+            table.row(
+                [game.id, bag.red, bag.green, bag.blue, bag.power()]
+                    .map(Answer::from)
+                    .to_vec(),
+            );
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{error::ParseError, testutil};
+
+    #[test]
+    fn can_parse_reveals() {
+        assert_eq!(
+            Reveal::from_str("1 blue, 2 green").unwrap(),
+            Reveal {
+                blue: 1,
+                green: 2,
+                red: 0,
+            }
+        );
+
+        assert_eq!(
+            Reveal::from_str("12 red, 13 green, 14 blue").unwrap(),
+            Reveal {
+                red: 12,
+                green: 13,
+                blue: 14,
+            }
+        );
+    }
+
+    #[test]
+    fn can_parse_games() {
+        assert_eq!(
+            Game::from_str("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red").unwrap(),
+            Game {
+                id: 2,
+                reveals: vec![
+                    Reveal {
+                        red: 0,
+                        green: 2,
+                        blue: 1,
+                    },
+                    Reveal {
+                        red: 1,
+                        green: 3,
+                        blue: 4,
+                    }
+                ]
+            }
+        );
+
+        assert_eq!(
+            Game::from_str("Game 2: 1 blue, 2 purple"),
+            Err(Error::Color("purple".to_owned()))
+        );
+        assert_eq!(
+            Game::from_str("Round 2: 1 blue"),
+            Err(Error::Game("Round 2: 1 blue".to_owned()))
+        );
+        assert_eq!(
+            ParseError::new(&Game::from_str("Game x: 1 blue").unwrap_err(), &[]),
+            ParseError {
+                line: None,
+                column: None,
+                message: "Can't parse number x: invalid digit found in string".to_owned()
+            }
+        );
+    }
+
+    crate::example_test!(day2, part1 = 8, part2 = 2286);
+
+    proptest! {
+        #[test]
+        fn games_roundtrip(text in testutil::game()) {
+            let game = Game::from_str(&text).unwrap();
+
+            prop_assert_eq!(Game::from_str(&game.to_string()).unwrap(), game);
+        }
+
+        #[test]
+        fn minimal_bag_is_minimal(text in testutil::game()) {
+            let game = Game::from_str(&text).unwrap();
+            let bag = minimal_bag(&game.reveals);
+
+            prop_assert!(game.reveals.iter().all(|r| bag.is_superset_of(r)));
+            // Taking away any cube makes one of the reveals impossible.
+            let smaller = [
+                bag.red.checked_sub(1).map(|red| Reveal { red, ..bag }),
+                bag.green.checked_sub(1).map(|green| Reveal { green, ..bag }),
+                bag.blue.checked_sub(1).map(|blue| Reveal { blue, ..bag }),
+            ];
+            for smaller in smaller.into_iter().flatten() {
+                prop_assert!(!game.reveals.iter().all(|r| smaller.is_superset_of(r)));
+            }
+        }
+    }
+
+    #[test]
+    fn checks_several_bags_at_once() -> Result<()> {
+        let games = parse_games(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        )?;
+        let bags = [BAG, parse_bag("20/13/15")?, parse_bag("0/0/0")?];
+
+        assert_eq!(sum_of_possibles(&bags, &games), [1, 8, 0]);
+        assert!(parse_bag("1/2").is_err());
+
+        let report = Day2::explain(&games, &ExplainQuery::from_str("bags=12/13/14,20/13/15")?)?;
+        assert_eq!(
+            report.lines,
+            [
+                "12 red, 13 green, 14 blue: 1",
+                "20 red, 13 green, 15 blue: 8"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lint_points_at_problems() {
+        let diagnostics =
+            Day2::lint("Game 1: 3 blue, 4 red\nGame 2: 3 blue; 4 purple\nGame x: 1 red\n")
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+        assert_eq!(
+            diagnostics,
+            [
+                "2:19: Expected one of [\"red\", \"green\", \"blue\"], got 'p'",
+                "3:6: Expected a number, got 'x'",
+            ]
+        );
+    }
+
+    #[test]
+    fn dump_lists_minimal_bags() -> Result<()> {
+        let games = parse_games(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        )?;
+
+        assert_eq!(
+            Day2::dump(&games)?.to_csv(),
+            "game,red,green,blue,power\n1,4,2,6,48\n3,20,13,6,1560\n"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,865 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    ops::RangeInclusive,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use rand::Rng as _;
+use thiserror::Error;
+
+use crate::{
+    dump::Table,
+    error::DayError,
+    gen::Rng,
+    geometry::Point2,
+    graphviz::Graph,
+    grid::Grid,
+    lint::{self, Diagnostic, Linter},
+    solution::{self, Answer, ExplainQuery, Report, Solution},
+    tool::{Invocation, Tool},
+    viz::{Drawing, Image, Rgb, PALETTE},
+};
+
+pub struct Array {
+    grid: Grid<char>,
+}
+
+#[derive(Debug, Clone)]
+struct PartNumber {
+    number: u32,
+    /// Where the first digit is.
+    position: Point2,
+    /// Positions of all adjacent symbols.
+    symbols: BTreeSet<Point2>,
+}
+
+/// How many part numbers a kind of symbol validates.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct SymbolStats {
+    pub parts: usize,
+    /// Like [`Totals`], this outgrows `u32` on long schematics.
+    pub sum: u64,
+}
+
+/// The ways a schematic can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("The schematic is empty")]
+    Empty,
+    #[error("Row {row} is {len} wide, but the first is {width}")]
+    Ragged {
+        row: usize,
+        len: usize,
+        width: usize,
+    },
+}
+
+impl DayError for Error {}
+
+impl FromStr for Array {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s.lines().map(|l| l.chars().collect_vec()).collect_vec();
+        let width = rows.first().ok_or(Error::Empty)?.len();
+
+        if let Some(row) = rows.iter().position(|r| r.len() != width) {
+            return Err(Error::Ragged {
+                row: row + 1,
+                len: rows[row].len(),
+                width,
+            });
+        }
+
+        Ok(Array {
+            grid: Grid::from_rows(rows).expect("the rows are as wide as the first"),
+        })
+    }
+}
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+impl Array {
+    /// Returns the char at the given posistion. Returns '.' if out of bounds.
+    fn get(&self, p: Point2) -> char {
+        self.grid.get_or(p, '.')
+    }
+
+    fn adjacent_symbols(&self, p: Point2) -> BTreeSet<Point2> {
+        self.grid
+            .neighbors8(p)
+            .filter(|p| is_symbol(self.get(*p)))
+            .collect()
+    }
+
+    /// Returns all part numbers together with the positions of the
+    /// symbols they are adjacent to.
+    fn find_part_numbers(&self) -> Vec<PartNumber> {
+        enum NumberState {
+            NoNumber,
+            ValidNumber {
+                number: u32,
+                position: Point2,
+                symbols: BTreeSet<Point2>,
+            },
+        }
+
+        let mut result = vec![];
+
+        for (lpos, line) in self.grid.iter_rows().enumerate() {
+            let mut state = NumberState::NoNumber;
+
+            for (cpos, c) in line.iter().enumerate() {
+                let is_digit = c.is_ascii_digit();
+                let adjacent_symbols = self.adjacent_symbols(Point2::new(lpos, cpos));
+
+                state = match state {
+                    NumberState::NoNumber => {
+                        if is_digit {
+                            NumberState::ValidNumber {
+                                number: c.to_digit(10).unwrap(),
+                                position: Point2::new(lpos, cpos),
+                                symbols: adjacent_symbols,
+                            }
+                        } else {
+                            NumberState::NoNumber
+                        }
+                    }
+                    NumberState::ValidNumber {
+                        number,
+                        position,
+                        symbols,
+                    } => {
+                        if is_digit {
+                            NumberState::ValidNumber {
+                                number: number * 10 + c.to_digit(10).unwrap(),
+                                position,
+                                // Non-destructive set merge.
+                                symbols: symbols.into_iter().chain(adjacent_symbols).collect(),
+                            }
+                        } else {
+                            if !symbols.is_empty() {
+                                result.push(PartNumber {
+                                    number,
+                                    position,
+                                    symbols,
+                                });
+                            }
+                            NumberState::NoNumber
+                        }
+                    }
+                };
+            }
+
+            if let NumberState::ValidNumber {
+                number,
+                position,
+                symbols,
+            } = state
+            {
+                if !symbols.is_empty() {
+                    result.push(PartNumber {
+                        number,
+                        position,
+                        symbols,
+                    });
+                }
+            }
+        }
+
+        result
+    }
+
+    fn parts_graph(&self) -> PartsGraph {
+        let parts = self.find_part_numbers();
+        let mut symbols: BTreeMap<Point2, Symbol> = BTreeMap::new();
+
+        for (i, part) in parts.iter().enumerate() {
+            for &p in &part.symbols {
+                symbols
+                    .entry(p)
+                    .or_insert_with(|| Symbol {
+                        kind: self.get(p),
+                        parts: vec![],
+                    })
+                    .parts
+                    .push(i);
+            }
+        }
+
+        PartsGraph { parts, symbols }
+    }
+
+    fn find_gears(&self) -> Vec<(u32, u32)> {
+        self.parts_graph().gears()
+    }
+
+    /// Reports, for each kind of symbol, how many part numbers it
+    /// validates and what they sum up to. A part number next to
+    /// several symbols counts once for each kind of symbol.
+    #[must_use]
+    pub fn symbol_breakdown(&self) -> BTreeMap<char, SymbolStats> {
+        let mut breakdown: BTreeMap<char, SymbolStats> = BTreeMap::new();
+
+        for part in self.find_part_numbers() {
+            let kinds = part
+                .symbols
+                .iter()
+                .map(|p| self.get(*p))
+                .collect::<BTreeSet<_>>();
+
+            for kind in kinds {
+                let stats = breakdown.entry(kind).or_default();
+
+                stats.parts += 1;
+                stats.sum += u64::from(part.number);
+            }
+        }
+
+        breakdown
+    }
+
+    /// A pixel per character: digits of part numbers in green, other
+    /// digits gray, symbols yellow and gears red. Each number and symbol
+    /// is written on top, which only shows in SVGs.
+    fn draw(&self) -> Drawing {
+        let graph = self.parts_graph();
+        let mut colors = self.grid.map(|&c| {
+            if c == '.' {
+                Rgb::NIGHT
+            } else if c.is_ascii_digit() {
+                Rgb::GRAY
+            } else {
+                PALETTE[0]
+            }
+        });
+
+        let mut spans = vec![];
+        for part in &graph.parts {
+            let digits = (part.position.col..self.grid.cols())
+                .take_while(|&col| {
+                    self.get(Point2::new(part.position.row, col))
+                        .is_ascii_digit()
+                })
+                .count();
+            for col in part.position.col..part.position.col + digits {
+                colors[Point2::new(part.position.row, col)] = PALETTE[3];
+            }
+            spans.push((part.position, digits, part.number));
+        }
+        for (p, symbol) in &graph.symbols {
+            if symbol.kind == '*' && symbol.parts.len() == 2 {
+                colors[*p] = PALETTE[5];
+            }
+        }
+
+        let mut drawing = Drawing::from(Image::from_grid(&colors, |c| *c));
+        for (p, digits, number) in spans {
+            drawing.label(p, digits, &number.to_string(), Rgb::BLACK);
+        }
+        for (p, &c) in self.grid.enumerate().filter(|(_, c)| is_symbol(**c)) {
+            drawing.label(p, 1, &c.to_string(), Rgb::BLACK);
+        }
+
+        drawing
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Symbol {
+    kind: char,
+    /// The indices of the adjacent part numbers, in order.
+    parts: Vec<usize>,
+}
+
+/// Part numbers and symbols, with an edge between each part number and
+/// each symbol next to it.
+#[derive(Debug, Clone)]
+struct PartsGraph {
+    parts: Vec<PartNumber>,
+    /// The symbols next to any part number, by position.
+    symbols: BTreeMap<Point2, Symbol>,
+}
+
+impl PartsGraph {
+    /// The pairs of part numbers that share a gear: a `*` with exactly
+    /// two neighbors. Pairs that share several gears count once.
+    fn gears(&self) -> Vec<(u32, u32)> {
+        self.symbols
+            .values()
+            .filter(|s| s.kind == '*' && s.parts.len() == 2)
+            .map(|s| (s.parts[0], s.parts[1]))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|(a, b)| (self.parts[a].number, self.parts[b].number))
+            .collect()
+    }
+
+    /// Part numbers are boxes, symbols circles. Node ids are `n` or `s`
+    /// followed by line and column.
+    fn to_graphviz(&self) -> Graph {
+        let mut graph = Graph::new("schematic");
+        let part_id = |part: &PartNumber| format!("n{}_{}", part.position.row, part.position.col);
+
+        for part in &self.parts {
+            graph.node(
+                part_id(part),
+                &[("label", &part.number.to_string()), ("shape", "box")],
+            );
+        }
+
+        for (p, symbol) in &self.symbols {
+            let id = format!("s{}_{}", p.row, p.col);
+            graph.node(
+                id.clone(),
+                &[("label", &symbol.kind.to_string()), ("shape", "circle")],
+            );
+
+            for &part in &symbol.parts {
+                graph.edge(part_id(&self.parts[part]), id.clone());
+            }
+        }
+
+        graph
+    }
+}
+
+/// A number in a row, covering columns `start..end`.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    number: u32,
+    start: usize,
+    end: usize,
+}
+
+impl Span {
+    fn touches(&self, column: usize) -> bool {
+        self.start <= column + 1 && column <= self.end
+    }
+}
+
+/// A row of the schematic and the numbers in it.
+#[derive(Debug, Default)]
+struct Row {
+    chars: Vec<char>,
+    numbers: Vec<Span>,
+}
+
+impl Row {
+    fn new(line: &str) -> Self {
+        let chars = line.chars().collect_vec();
+        let mut numbers: Vec<Span> = vec![];
+
+        for (column, c) in chars.iter().enumerate() {
+            let Some(digit) = c.to_digit(10) else {
+                continue;
+            };
+
+            match numbers.last_mut() {
+                Some(span) if span.end == column => {
+                    span.number = span.number * 10 + digit;
+                    span.end += 1;
+                }
+                _ => numbers.push(Span {
+                    number: digit,
+                    start: column,
+                    end: column + 1,
+                }),
+            }
+        }
+
+        Self { chars, numbers }
+    }
+
+    fn get(&self, column: usize) -> char {
+        self.chars.get(column).copied().unwrap_or('.')
+    }
+
+    fn has_symbol(&self, columns: RangeInclusive<usize>) -> bool {
+        columns.into_iter().any(|c| is_symbol(self.get(c)))
+    }
+}
+
+/// The answers of both parts as computed by [`scan`]. Long schematics
+/// outgrow `u32`.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Totals {
+    pub part_numbers: u64,
+    pub gear_ratios: u64,
+}
+
+/// Computes both parts while only ever holding three rows, so the
+/// schematic can be arbitrarily long.
+pub fn scan<I, S>(lines: I) -> Result<Totals, Error>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut lines = lines.into_iter().map(|l| Row::new(l.as_ref()));
+    let mut totals = Totals::default();
+
+    // Pairs of numbers, by row and start column, that already contributed
+    // a gear ratio. They may share several gears.
+    let mut paired: BTreeSet<(Point2, Point2)> = BTreeSet::new();
+
+    let first = lines.next().ok_or(Error::Empty)?;
+    let width = first.chars.len();
+    let mut window = [Row::default(), first, Row::default()];
+
+    for row in 0.. {
+        window[2] = match lines.next() {
+            Some(next) if next.chars.len() != width => {
+                return Err(Error::Ragged {
+                    row: row + 2,
+                    len: next.chars.len(),
+                    width,
+                })
+            }
+            Some(next) => next,
+            None => Row::default(),
+        };
+
+        let [above, current, below] = &window;
+
+        for span in &current.numbers {
+            let columns = span.start.saturating_sub(1)..=span.end;
+
+            if window.iter().any(|r| r.has_symbol(columns.clone())) {
+                totals.part_numbers += u64::from(span.number);
+            }
+        }
+
+        // Only gears from this row on can be shared by what is left.
+        paired.retain(|(n1, _)| n1.row + 1 >= row);
+
+        for column in current.chars.iter().positions(|c| *c == '*') {
+            let adjacent = [
+                (row.checked_sub(1), above),
+                (Some(row), current),
+                (Some(row + 1), below),
+            ]
+            .into_iter()
+            .filter_map(|(r, numbers)| Some((r?, numbers)))
+            .flat_map(|(r, numbers)| {
+                numbers
+                    .numbers
+                    .iter()
+                    .filter(|s| s.touches(column))
+                    .map(move |s| (Point2::new(r, s.start), s.number))
+            })
+            .collect_vec();
+
+            // Only a `*` next to exactly two numbers is a gear.
+            if let [(p1, n1), (p2, n2)] = adjacent[..] {
+                if paired.insert((p1.min(p2), p1.max(p2))) {
+                    totals.gear_ratios += u64::from(n1) * u64::from(n2);
+                }
+            }
+        }
+
+        if window[2].chars.is_empty() {
+            break;
+        }
+        window.rotate_left(1);
+    }
+
+    Ok(totals)
+}
+
+/// Scans the lines of `reader` as they are read, so the schematic never
+/// has to be in memory as a whole.
+pub fn stream(reader: impl BufRead) -> Result<Totals> {
+    let mut failure = None;
+    let lines = reader
+        .lines()
+        .map_while(|line| line.map_err(|e| failure = Some(e)).ok());
+    let totals = scan(lines);
+
+    if let Some(error) = failure {
+        return Err(error).context("Failed to read input");
+    }
+    Ok(totals?)
+}
+
+/// The schematic as a grid, or, with `--algo streaming`, just the totals
+/// of scanning it three rows at a time.
+pub enum Schematic {
+    Grid(Array),
+    Scanned(Totals),
+}
+
+impl Schematic {
+    /// The grid, which a scan doesn't keep.
+    fn array(&self) -> Result<&Array> {
+        match self {
+            Schematic::Grid(array) => Ok(array),
+            Schematic::Scanned(_) => {
+                Err(anyhow!("The streaming scan keeps no grid, try --algo grid"))
+            }
+        }
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Schematic;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Schematic, Error> {
+        match solution::algo() {
+            Some("streaming") => scan(input.lines()).map(Schematic::Scanned),
+            _ => Array::from_str(input).map(Schematic::Grid),
+        }
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        let mut linter = Linter::new();
+        let lines = lint::lines(&mut linter, input);
+
+        linter.grid(&lines, |c| c.is_ascii_graphic());
+        linter.finish()
+    }
+
+    fn part1(schematic: &Schematic) -> Result<Answer> {
+        Ok(match schematic {
+            Schematic::Grid(array) => array
+                .find_part_numbers()
+                .into_iter()
+                .map(|p| u64::from(p.number))
+                .sum::<u64>(),
+            Schematic::Scanned(totals) => totals.part_numbers,
+        }
+        .into())
+    }
+
+    fn part2(schematic: &Schematic) -> Result<Option<Answer>> {
+        Ok(Some(
+            match schematic {
+                Schematic::Grid(array) => array
+                    .find_gears()
+                    .into_iter()
+                    .map(|(n1, n2)| u64::from(n1) * u64::from(n2))
+                    .sum::<u64>(),
+                Schematic::Scanned(totals) => totals.gear_ratios,
+            }
+            .into(),
+        ))
+    }
+
+    /// `size` rows of 140 characters with numbers and symbols strewn
+    /// about.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        const WIDTH: usize = 140;
+
+        Ok((0..size)
+            .map(|_| {
+                let mut row = String::new();
+                while row.len() < WIDTH {
+                    match rng.gen_range(0..10) {
+                        0 => row.push_str(&format!("{}.", rng.gen_range(1..1000))),
+                        1 => row.push(['*', '#', '+', '$', '/', '='][rng.gen_range(0..6)]),
+                        _ => row.push('.'),
+                    }
+                }
+
+                row.truncate(WIDTH);
+                row + "\n"
+            })
+            .collect())
+    }
+
+    fn explain(schematic: &Schematic, _query: &ExplainQuery) -> Result<Report> {
+        let array = schematic.array()?;
+        let mut report = Report::new("Part numbers by adjacent symbol");
+
+        for (symbol, stats) in array.symbol_breakdown() {
+            report.line(format!(
+                "{symbol}  {:>4} part numbers, sum {:>7}",
+                stats.parts, stats.sum
+            ));
+        }
+
+        Ok(report)
+    }
+
+    /// The part numbers next to each kind of symbol, like `explain`.
+    fn breakdown(schematic: &Schematic) -> Result<Option<Table>> {
+        let array = schematic.array()?;
+        let mut table = Table::new(&["symbol", "part numbers", "sum"]);
+
+        for (symbol, stats) in array.symbol_breakdown() {
+            table.row(vec![
+                Answer::Text(symbol.to_string()),
+                stats.parts.into(),
+                stats.sum.into(),
+            ]);
+        }
+
+        Ok(Some(table))
+    }
+
+    fn render(schematic: &Schematic) -> Result<Drawing> {
+        Ok(schematic.array()?.draw())
+    }
+}
+
+/// `tool parts-graph`: the part numbers and symbols as Graphviz graph.
+pub const PARTS_GRAPH: Tool = Tool {
+    year: super::YEAR,
+    day: 3,
+    name: "parts-graph",
+    about: "Exports part numbers and adjacent symbols as Graphviz graph",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("output")
+                .long("output")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Writes the graph to this file instead of showing it"),
+        )
+    },
+    run: parts_graph,
+};
+
+fn parts_graph(invocation: &Invocation) -> Result<Report> {
+    let graph = invocation.parsed::<Day3>()?.array()?.parts_graph();
+    let dot = graph.to_graphviz().to_dot();
+
+    let mut report = Report::new(format!(
+        "{} part numbers, {} symbols",
+        graph.parts.len(),
+        graph.symbols.len()
+    ));
+    match invocation.matches.get_one::<PathBuf>("output") {
+        Some(path) => {
+            fs::write(path, dot).with_context(|| format!("Failed to write {}", path.display()))?;
+            report.line(format!("written to {}", path.display()));
+        }
+        None => {
+            for line in dot.lines() {
+                report.line(line.to_owned());
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// `tool stream-schematic`: both parts of schematics that are too large
+/// to parse as a whole, like the ones `generate` makes with millions of
+/// rows.
+pub const STREAM_SCHEMATIC: Tool = Tool {
+    year: super::YEAR,
+    day: 3,
+    name: "stream-schematic",
+    about: "Solves a schematic of any size three rows at a time",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("file")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("The schematic to solve, read from stdin if missing"),
+        )
+    },
+    run: stream_file,
+};
+
+fn stream_file(invocation: &Invocation) -> Result<Report> {
+    let totals = match invocation.matches.get_one::<PathBuf>("file") {
+        Some(path) => {
+            stream(BufReader::new(File::open(path).with_context(|| {
+                format!("Failed to open {}", path.display())
+            })?))?
+        }
+        None => stream(io::stdin().lock())?,
+    };
+
+    let mut report = Report::new("Part numbers and gear ratios");
+    report.line(format!("Part 1: {}", totals.part_numbers));
+    report.line(format!("Part 2: {}", totals.gear_ratios));
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::bail;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::testutil;
+
+    const EXAMPLE: &str = include_str!("day3.example");
+
+    crate::example_test!(day3, part1 = 4361, part2 = 467_835);
+
+    #[test]
+    fn example_works() -> Result<()> {
+        let array = Array::from_str(EXAMPLE)?;
+
+        assert_eq!(
+            array
+                .find_part_numbers()
+                .iter()
+                .map(|p| p.number)
+                .collect_vec(),
+            [467, 35, 633, 617, 592, 755, 664, 598]
+        );
+
+        assert_eq!(array.find_gears(), [(467, 35), (755, 598)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn gears_have_two_neighbors() -> Result<()> {
+        let graph = Array::from_str(EXAMPLE)?.parts_graph();
+
+        // The `*` next to 617 alone isn't a gear.
+        assert_eq!(graph.symbols[&Point2::new(4, 3)].parts.len(), 1);
+        assert_eq!(graph.symbols.len(), 6);
+
+        let dot = graph.to_graphviz().to_dot();
+        assert!(dot.contains("  \"n0_0\" [label=\"467\", shape=\"box\"];\n"));
+        assert!(dot.contains("  \"n0_0\" -- \"s1_3\";\n"));
+
+        let svg = Day3::render(&Schematic::Grid(Array::from_str(EXAMPLE)?))?.to_svg(100);
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"3\" height=\"1\" fill=\"#33cc33\"/>"));
+        assert!(svg.contains("<rect x=\"3\" y=\"1\" width=\"1\" height=\"1\" fill=\"#ee3333\"/>"));
+        assert!(svg.contains(">467</text>"));
+
+        let three = "1.2\n.*.\n..3";
+        assert_eq!(Array::from_str(three)?.find_gears(), []);
+        assert_eq!(scan(three.lines())?.gear_ratios, 0);
+
+        Ok(())
+    }
+
+    fn grid_totals(input: &str) -> Result<Totals> {
+        let schematic = Schematic::Grid(Array::from_str(input)?);
+        let (Answer::UInt(part_numbers), Some(Answer::UInt(gear_ratios))) =
+            (Day3::part1(&schematic)?, Day3::part2(&schematic)?)
+        else {
+            bail!("Unexpected answers");
+        };
+
+        Ok(Totals {
+            part_numbers,
+            gear_ratios,
+        })
+    }
+
+    #[test]
+    fn streaming_agrees_with_grid() -> Result<()> {
+        assert_eq!(
+            scan(EXAMPLE.lines())?,
+            Totals {
+                part_numbers: 4361,
+                gear_ratios: 467835
+            }
+        );
+
+        // Both numbers share both gears, which only counts once.
+        assert_eq!(scan("1*\n*2".lines())?, grid_totals("1*\n*2")?);
+        assert_eq!(scan("1*\n*2".lines())?.gear_ratios, 2);
+
+        for seed in 0..20 {
+            let input = Day3::generate(&mut crate::gen::rng(seed), 30)?;
+            assert_eq!(scan(input.lines())?, grid_totals(&input)?, "seed {seed}");
+        }
+
+        assert_eq!(
+            scan(["..", "..."]),
+            Err(Error::Ragged {
+                row: 2,
+                len: 3,
+                width: 2
+            })
+        );
+        assert_eq!(scan([""; 0]), Err(Error::Empty));
+        assert_eq!(
+            Array::from_str("..\n..\n.").err(),
+            Some(Error::Ragged {
+                row: 3,
+                len: 1,
+                width: 2
+            })
+        );
+        assert_eq!(Array::from_str("").err(), Some(Error::Empty));
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn streaming_agrees_with_grid_on_any_schematic(
+            input in testutil::grid("......0123456789*#", 1..8, 1..8),
+        ) {
+            prop_assert_eq!(scan(input.lines()).unwrap(), grid_totals(&input).unwrap());
+        }
+    }
+
+    #[test]
+    fn streaming_needs_no_grid() -> Result<()> {
+        // The hundred thousand rows are never in memory at the same time.
+        let rows = EXAMPLE
+            .lines()
+            .cycle()
+            .take(EXAMPLE.lines().count() * 10_000);
+
+        assert_eq!(
+            scan(rows)?,
+            Totals {
+                part_numbers: 4361 * 10_000,
+                gear_ratios: 467835 * 10_000,
+            }
+        );
+
+        assert_eq!(stream(EXAMPLE.as_bytes())?, scan(EXAMPLE.lines())?);
+        assert!(stream("..\n...".as_bytes()).is_err());
+        assert!(stream(&[b'.', 0xff][..]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn symbol_breakdown_works() -> Result<()> {
+        let array = Array::from_str(EXAMPLE)?;
+
+        assert_eq!(
+            array.symbol_breakdown().into_iter().collect_vec(),
+            [
+                ('#', SymbolStats { parts: 1, sum: 633 }),
+                ('$', SymbolStats { parts: 1, sum: 664 }),
+                (
+                    '*',
+                    SymbolStats {
+                        parts: 5,
+                        sum: 467 + 35 + 617 + 755 + 598
+                    }
+                ),
+                ('+', SymbolStats { parts: 1, sum: 592 }),
+            ]
+        );
+
+        let table = Day3::breakdown(&Day3::parse(EXAMPLE)?)?.unwrap();
+        assert_eq!(table.columns, ["symbol", "part numbers", "sum"]);
+        assert_eq!(
+            table.rows[2],
+            [
+                Answer::Text("*".to_owned()),
+                Answer::UInt(5),
+                Answer::UInt(2472)
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,369 @@
+use std::{collections::BTreeSet, fs, path::PathBuf, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    chart,
+    error::DayError,
+    lint::{self, Diagnostic},
+    parse,
+    solution::{Answer, ExplainQuery, Report, Solution},
+    tool::{Invocation, Tool},
+};
+
+/// The ways a scratchcard can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Expected a card like `Card 1: 41 48 | 83 86`, got {0:?}")]
+    Card(String),
+    #[error(transparent)]
+    Numbers(#[from] parse::Error),
+}
+
+impl DayError for Error {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Card {
+    id: u32,
+    winning_numbers: Vec<u32>,
+    your_numbers: Vec<u32>,
+}
+
+impl FromStr for Card {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let re = Regex::new(r"^Card +([0-9]+): ([ 0-9]+) \| ([ 0-9]+)$")
+            .expect("the card pattern is valid");
+
+        let captures = re.captures(s).ok_or_else(|| Error::Card(s.to_owned()))?;
+
+        let capture_group_to_int_vec = |g| parse::ints(captures.get(g).unwrap().as_str());
+
+        Ok(Self {
+            id: u32::from_str(captures.get(1).unwrap().as_str())
+                .map_err(|_| Error::Card(s.to_owned()))?,
+            winning_numbers: capture_group_to_int_vec(2)?,
+            your_numbers: capture_group_to_int_vec(3)?,
+        })
+    }
+}
+
+impl Card {
+    /// Returns the number of wins.
+    fn wins(&self) -> usize {
+        BTreeSet::from_iter(self.winning_numbers.iter())
+            .intersection(&BTreeSet::from_iter(self.your_numbers.iter()))
+            .count()
+    }
+
+    /// The number of points this card is worth.
+    fn win_points(&self) -> usize {
+        match self.wins() {
+            0 => 0,
+            n => 1 << (n - 1),
+        }
+    }
+}
+
+/// How a list of cards is written down: like the puzzle input, or as a
+/// JSON array of objects with the fields of [`Card`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CardFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for CardFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(CardFormat::Text),
+            "json" => Ok(CardFormat::Json),
+            _ => bail!("Unsupported format: {s}, use text or json"),
+        }
+    }
+}
+
+impl CardFormat {
+    /// JSON starts with the array, text with a card.
+    #[must_use]
+    pub fn detect(s: &str) -> Self {
+        if s.trim_start().starts_with('[') {
+            CardFormat::Json
+        } else {
+            CardFormat::Text
+        }
+    }
+
+    pub fn read(self, s: &str) -> Result<Vec<Card>> {
+        match self {
+            CardFormat::Text => Ok(Day4::parse(s)?),
+            CardFormat::Json => Ok(serde_json::from_str(s)?),
+        }
+    }
+
+    /// Text comes out aligned like the puzzle inputs, so converting an
+    /// input there and back gives the same input.
+    pub fn write(self, cards: &[Card]) -> Result<String> {
+        match self {
+            CardFormat::Text => {
+                let id_width = cards.iter().map(|c| c.id.to_string().len()).max();
+                let numbers = |numbers: &[u32]| numbers.iter().map(|n| format!("{n:>2}")).join(" ");
+
+                Ok(cards
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            "Card {:>width$}: {} | {}\n",
+                            c.id,
+                            numbers(&c.winning_numbers),
+                            numbers(&c.your_numbers),
+                            width = id_width.unwrap_or(0)
+                        )
+                    })
+                    .collect())
+            }
+            CardFormat::Json => Ok(serde_json::to_string_pretty(cards)? + "\n"),
+        }
+    }
+}
+
+/// How many copies of each card we end up with, in order of the ids.
+fn copies(cards: &[Card]) -> Vec<usize> {
+    let card_values = cards
+        .iter()
+        .sorted_by_key(|c| c.id)
+        .map(|c| c.wins())
+        .collect::<Vec<_>>();
+
+    // For each of the cards, we remember how many copies we have. We
+    // start with one of each.
+    let mut card_counts: Vec<usize> = vec![1; card_values.len()];
+
+    for pos in 0..card_values.len() {
+        let new_cards = card_values[pos];
+        let new_copies = card_counts[pos];
+
+        for c in &mut card_counts[(pos + 1)..(pos + 1 + new_cards)] {
+            *c += new_copies;
+        }
+    }
+
+    card_counts
+}
+
+fn part2_solve(cards: &[Card]) -> Result<usize> {
+    Ok(copies(cards).into_iter().sum())
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<Card>;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<Card>, Error> {
+        input.lines().map(Card::from_str).collect()
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        lint::each_line(input, |c| {
+            c.literal("Card")?;
+            c.spaces()?;
+            c.number()?;
+            c.literal(":")?;
+            c.spaces()?;
+
+            // Winning numbers up to the bar, then the numbers we have.
+            loop {
+                c.number()?;
+                c.spaces()?;
+                if c.eat("|") {
+                    break;
+                }
+            }
+
+            loop {
+                c.spaces()?;
+                c.number()?;
+                if c.is_at_end() {
+                    return Ok(());
+                }
+            }
+        })
+    }
+
+    fn part1(cards: &Vec<Card>) -> Result<Answer> {
+        Ok(cards.iter().map(Card::win_points).sum::<usize>().into())
+    }
+
+    fn part2(cards: &Vec<Card>) -> Result<Option<Answer>> {
+        Ok(Some(part2_solve(cards)?.into()))
+    }
+
+    /// How many cards have how many wins, and how the copies of part 2
+    /// pile up card by card. Bars are up to `width=50` cells long.
+    fn explain(cards: &Vec<Card>, query: &ExplainQuery) -> Result<Report> {
+        let width = query.get_or("width", 50usize)?;
+        let wins = cards.iter().map(Card::wins).collect::<Vec<_>>();
+
+        let mut report = Report::new("Wins per card");
+        let histogram = (0..=wins.iter().copied().max().unwrap_or_default())
+            .map(|n| {
+                (
+                    format!("{n} wins"),
+                    wins.iter().filter(|&&w| w == n).count() as u128,
+                )
+            })
+            .collect::<Vec<_>>();
+        for line in chart::bars(&histogram, width) {
+            report.line(line);
+        }
+
+        report.line("");
+        report.line("Copies so far");
+        let cumulative = cards
+            .iter()
+            .sorted_by_key(|c| c.id)
+            .zip(copies(cards))
+            .scan(0, |total, (card, copies)| {
+                *total += copies as u128;
+                Some((format!("card {}", card.id), *total))
+            })
+            .collect::<Vec<_>>();
+        for line in chart::bars(&cumulative, width) {
+            report.line(line);
+        }
+
+        Ok(report)
+    }
+}
+
+/// `tool convert-cards`: the cards as JSON for other tools, and back.
+pub const CONVERT_CARDS: Tool = Tool {
+    year: super::YEAR,
+    day: 4,
+    name: "convert-cards",
+    about: "Converts cards between the puzzle's text and JSON",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("file")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Reads the cards from this file, as text or JSON, instead of the input"),
+        )
+        .arg(
+            clap::Arg::new("to")
+                .long("to")
+                .default_value("json")
+                .value_parser(CardFormat::from_str)
+                .help("The format to convert to: text or json"),
+        )
+        .arg(
+            clap::Arg::new("output")
+                .long("output")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Writes the cards to this file instead of showing them"),
+        )
+    },
+    run: convert_cards,
+};
+
+fn convert_cards(invocation: &Invocation) -> Result<Report> {
+    let cards = match invocation.matches.get_one::<PathBuf>("file") {
+        Some(path) => {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            CardFormat::detect(&contents).read(&contents)?
+        }
+        None => invocation.parsed::<Day4>()?,
+    };
+    let converted = invocation.arg::<CardFormat>("to")?.write(&cards)?;
+
+    let mut report = Report::new(format!("{} cards", cards.len()));
+    match invocation.matches.get_one::<PathBuf>("output") {
+        Some(path) => {
+            fs::write(path, converted)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            report.line(format!("written to {}", path.display()));
+        }
+        None => {
+            for line in converted.lines() {
+                report.line(line.to_owned());
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_works() -> Result<()> {
+        assert_eq!(
+            Card::from_str("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1")?,
+            Card {
+                id: 3,
+                winning_numbers: vec![1, 21, 53, 59, 44],
+                your_numbers: vec![69, 82, 63, 72, 16, 21, 14, 1],
+            }
+        );
+        assert_eq!(
+            Card::from_str("Card 3: 1 | 2 x"),
+            Err(Error::Card("Card 3: 1 | 2 x".to_owned()))
+        );
+        assert_eq!(
+            Card::from_str("Card 3: 1 | 99999999999")
+                .unwrap_err()
+                .to_string(),
+            "Can't parse number 99999999999: number too large to fit in target type"
+        );
+
+        Ok(())
+    }
+
+    crate::example_test!(day4, part1 = 13, part2 = 30);
+
+    #[test]
+    fn explain_charts_wins_and_copies() -> Result<()> {
+        let cards = Day4::parse(include_str!("day4.example"))?;
+
+        assert_eq!(copies(&cards), [1, 2, 4, 8, 14, 1]);
+
+        let report = Day4::explain(&cards, &ExplainQuery::from_str("width=4")?)?;
+        assert_eq!(report.lines[0], "0 wins │████ 2");
+        assert_eq!(report.lines[4], "4 wins │██ 1");
+        assert_eq!(
+            report.lines.last().map(String::as_str),
+            Some("card 6 │████ 30")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn formats_roundtrip() -> Result<()> {
+        let text = "Card  9: 41 48 | 83  6
+Card 10: 13  2 | 61 30
+";
+        let cards = CardFormat::Text.read(text)?;
+        let json = CardFormat::Json.write(&cards)?;
+
+        assert_eq!(CardFormat::detect(&json), CardFormat::Json);
+        assert_eq!(CardFormat::detect(text), CardFormat::Text);
+        assert!(json.contains("\"winning_numbers\": [\n      41,"));
+        assert_eq!(CardFormat::Json.read(&json)?, cards);
+        assert_eq!(CardFormat::Text.write(&cards)?, text);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,642 @@
+use std::{cmp::min, collections::BTreeMap, ops::Range, str::FromStr};
+
+use anyhow::{ensure, Result};
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{
+    error::DayError,
+    lint::{self, Diagnostic, Linter},
+    parse,
+    solution::{Answer, Report, Solution},
+    tool::{Invocation, Tool},
+};
+
+/// The ways an almanac can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("The almanac is empty")]
+    Empty,
+    #[error("Expected a map entry of three numbers, got {0:?}")]
+    Entry(String),
+    #[error("Missing {0}")]
+    MissingMap(String),
+    #[error(transparent)]
+    Numbers(#[from] parse::Error),
+}
+
+impl DayError for Error {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct MapEntry {
+    dst_range_start: u64,
+    src_range_start: u64,
+    len: u64,
+}
+
+impl MapEntry {
+    fn src_range(&self) -> Range<u64> {
+        self.src_range_start..(self.src_range_start + self.len)
+    }
+
+    fn map_value(&self, v: u64) -> Option<u64> {
+        self.src_range().contains(&v).then_some(
+            v.overflowing_sub(self.src_range_start)
+                .0
+                .overflowing_add(self.dst_range_start)
+                .0,
+        )
+    }
+}
+
+impl FromStr for MapEntry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numbers = parse::ints::<u64>(s)?;
+
+        if numbers.len() != 3 {
+            return Err(Error::Entry(s.to_owned()));
+        }
+
+        Ok(MapEntry {
+            dst_range_start: numbers[0],
+            src_range_start: numbers[1],
+            len: numbers[2],
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+struct MapEntries(Vec<MapEntry>);
+
+impl From<Vec<MapEntry>> for MapEntries {
+    fn from(mut value: Vec<MapEntry>) -> Self {
+        value.sort_by_key(|me| me.src_range_start);
+        Self(value)
+    }
+}
+
+impl MapEntries {
+    fn map_value(&self, v: u64) -> u64 {
+        // Entries are sorted and don't overlap, so only the last entry
+        // starting at or before v can contain it.
+        self.0[..self.0.partition_point(|me| me.src_range_start <= v)]
+            .last()
+            .and_then(|me| me.map_value(v))
+            .unwrap_or(v)
+    }
+
+    /// Entries covering all values up to `u64::MAX`, with identity
+    /// entries for the gaps.
+    fn total(&self) -> Vec<MapEntry> {
+        let mut entries = vec![];
+        let mut next = 0;
+
+        for me in &self.0 {
+            if next < me.src_range_start {
+                entries.push(MapEntry {
+                    dst_range_start: next,
+                    src_range_start: next,
+                    len: me.src_range_start - next,
+                });
+            }
+
+            entries.push(me.clone());
+            next = me.src_range().end;
+        }
+
+        entries.push(MapEntry {
+            dst_range_start: next,
+            src_range_start: next,
+            len: u64::MAX - next,
+        });
+
+        entries
+    }
+
+    /// A single map that does the same as applying `self` and then
+    /// `next`.
+    fn compose(&self, next: &MapEntries) -> MapEntries {
+        let next = next.total();
+        let mut composed = vec![];
+
+        for me in self.total() {
+            let mut src = me.src_range_start;
+            let mut dst = me.dst_range_start;
+            let end = dst + me.len;
+
+            // Split the image of the entry where it crosses entries of
+            // the next map.
+            while dst < end {
+                let Some(next_me) = next.get(next.partition_point(|n| n.src_range().end <= dst))
+                else {
+                    break;
+                };
+
+                let len = min(end, next_me.src_range().end) - dst;
+
+                composed.push(MapEntry {
+                    dst_range_start: next_me.dst_range_start + (dst - next_me.src_range_start),
+                    src_range_start: src,
+                    len,
+                });
+
+                src += len;
+                dst += len;
+            }
+        }
+
+        // Values that aren't covered stay as they are anyway.
+        composed.retain(|me| me.src_range_start != me.dst_range_start);
+        composed.into()
+    }
+
+    fn map_range(&self, r: Range<u64>) -> Range<u64> {
+        assert!(!r.is_empty());
+
+        for me in &self.0 {
+            let me_range = me.src_range();
+            assert!(!me_range.is_empty());
+
+            // r is completely before me_range
+            if r.end <= me_range.start {
+                return r;
+            }
+
+            // r ends in me_range or covers all of me_range. (Could be
+            // unified with the case above.)
+            assert!(r.end >= me_range.start);
+            if r.start < me_range.start {
+                return r.start..me_range.start;
+            }
+
+            // r starts in me_range.
+            if me_range.contains(&r.start) {
+                let me_range_offset = r.start - me_range.start;
+                let new_len = min(
+                    me_range.end - me_range.start - me_range_offset,
+                    r.end - r.start,
+                );
+                assert!(new_len != 0);
+
+                let dst_start = me.dst_range_start + me_range_offset;
+
+                return dst_start..(dst_start + new_len);
+            }
+        }
+
+        // The range doesn't intersect with any map entry.
+        r
+    }
+}
+
+/// What the values are at each stage. The maps go from one category to
+/// the next.
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Input {
+    seeds: Vec<u64>,
+
+    maps: [MapEntries; 7],
+}
+
+impl Input {
+    // TODO We could translate a whole range here. This would make part 2 of the problem much more efficient.
+    fn seed_to_location(&self, seed: u64) -> u64 {
+        self.maps
+            .iter()
+            .fold(seed, |value, map| map.map_value(value))
+    }
+
+    /// The value of `seed` in every category, ending with its location.
+    fn trace(&self, seed: u64) -> Vec<u64> {
+        std::iter::once(seed)
+            .chain(self.maps.iter().scan(seed, |value, map| {
+                *value = map.map_value(*value);
+                Some(*value)
+            }))
+            .collect()
+    }
+
+    /// All seven maps as one.
+    fn composed(&self) -> MapEntries {
+        self.maps
+            .iter()
+            .fold(MapEntries::default(), |composed, map| composed.compose(map))
+    }
+}
+
+impl FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sections = parse::sections(s);
+        let (seeds, maps) = sections.split_first().ok_or(Error::Empty)?;
+        let seeds = parse::ints(parse::labeled(seeds, "seeds:")?)?;
+
+        // Each map is a section with its name in the first line.
+        let map: BTreeMap<&str, Vec<&str>> = maps
+            .iter()
+            .filter_map(|section| {
+                let mut lines = section.lines();
+                Some((lines.next()?, lines.collect()))
+            })
+            .collect();
+
+        let mut maps: [MapEntries; 7] = Default::default();
+        for (i, entries) in maps.iter_mut().enumerate() {
+            let name = format!("{}-to-{} map:", CATEGORIES[i], CATEGORIES[i + 1]);
+
+            *entries = map
+                .get(name.as_str())
+                .ok_or(Error::MissingMap(name))?
+                .iter()
+                .map(|l| MapEntry::from_str(l))
+                .collect::<Result<Vec<_>, _>>()?
+                .into();
+        }
+
+        Ok(Input { seeds, maps })
+    }
+}
+
+fn find_closest_seed_location(input: &Input, seed_to_location: impl Fn(u64) -> u64) -> Option<u64> {
+    input.seeds.iter().map(|s| seed_to_location(*s)).min()
+}
+
+/// `map_range` translates the first contiguous part of a range of seeds
+/// to locations.
+fn find_closest_seed_location_2(
+    input: &Input,
+    map_range: impl Fn(Range<u64>) -> Range<u64>,
+) -> Option<u64> {
+    input
+        .seeds
+        .iter()
+        .copied()
+        .tuples::<(u64, u64)>()
+        .map(|(start, len)| {
+            assert!(len != 0);
+            let seed_range = start..(start + len);
+
+            let mut cur = start;
+            let mut candidate_location = u64::MAX;
+            loop {
+                let range = map_range(cur..seed_range.end);
+
+                // We've managed to translate some
+                cur += range.end - range.start;
+
+                candidate_location = min(candidate_location, range.start);
+
+                if !seed_range.contains(&cur) {
+                    break;
+                }
+            }
+
+            candidate_location
+        })
+        .min()
+}
+
+/// A lower bound for part 2 that needs no splitting of ranges: every
+/// seed lies in one of the entries of `seed_to_location` that overlap its
+/// range and maps to at least the destination start of that entry. It
+/// is exact unless an entry starts before the range.
+fn location_lower_bound(input: &Input, seed_to_location: &MapEntries) -> Option<u64> {
+    let entries = seed_to_location.total();
+
+    input
+        .seeds
+        .iter()
+        .copied()
+        .tuples::<(u64, u64)>()
+        .filter_map(|(start, len)| {
+            let seeds = start..(start + len);
+
+            entries
+                .iter()
+                .filter(|me| me.src_range_start < seeds.end && seeds.start < me.src_range().end)
+                .map(|me| me.dst_range_start)
+                .min()
+        })
+        .min()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = Input;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Input, Error> {
+        Input::from_str(input)
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        let mut linter = Linter::new();
+        let lines = lint::lines(&mut linter, input);
+        let blocks = lint::blocks(&lines);
+
+        let Some((seeds, maps)) = blocks.split_first() else {
+            return linter.finish();
+        };
+
+        linter.line(seeds[0], |c| {
+            c.literal("seeds:")?;
+            loop {
+                c.spaces()?;
+                c.number()?;
+                if c.is_at_end() {
+                    return Ok(());
+                }
+            }
+        });
+        for (number, _) in &seeds[1..] {
+            linter.error(*number, 1, "Expected an empty line after the seeds");
+        }
+
+        for (i, map) in maps.iter().enumerate() {
+            match CATEGORIES.get(i..i + 2) {
+                Some([from, to]) => linter.line(map[0], |c| {
+                    c.literal(&format!("{from}-to-{to} map:"))?;
+                    c.end()
+                }),
+                _ => {
+                    linter.error(map[0].0, 1, "Expected no more maps");
+                    continue;
+                }
+            };
+
+            for line in &map[1..] {
+                linter.line(*line, |c| {
+                    c.number()?;
+                    c.spaces()?;
+                    c.number()?;
+                    c.spaces()?;
+                    c.number()?;
+                    c.end()
+                });
+            }
+        }
+
+        let missing = (CATEGORIES.len() - 1).saturating_sub(maps.len());
+        if missing > 0 {
+            linter.error(lines.len() + 1, 1, format!("Expected {missing} more maps"));
+        }
+
+        linter.finish()
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        let seed_to_location = input.composed();
+
+        Ok(
+            find_closest_seed_location(input, |s| seed_to_location.map_value(s))
+                .unwrap()
+                .into(),
+        )
+    }
+
+    fn part2(input: &Input) -> Result<Option<Answer>> {
+        let seed_to_location = input.composed();
+
+        Ok(Some(
+            find_closest_seed_location_2(input, |r| seed_to_location.map_range(r))
+                .unwrap()
+                .into(),
+        ))
+    }
+
+    /// Goes through the seven maps one after the other instead of
+    /// composing them first. Part 2 must also respect the lower bound.
+    fn reference(input: &Input) -> Result<[Option<Answer>; 2]> {
+        let part1 = find_closest_seed_location(input, |s| input.seed_to_location(s));
+        let part2 = find_closest_seed_location_2(input, |r| {
+            input.maps.iter().fold(r, |r, map| map.map_range(r))
+        });
+
+        if let (Some(bound), Some(location)) =
+            (location_lower_bound(input, &input.composed()), part2)
+        {
+            ensure!(
+                bound <= location,
+                "Part 2 found location {location} below the lower bound {bound}"
+            );
+        }
+
+        Ok([part1.map(Answer::from), part2.map(Answer::from)])
+    }
+}
+
+/// `tool map-seed`: follows a single seed through all maps.
+pub const MAP_SEED: Tool = Tool {
+    year: super::YEAR,
+    day: 5,
+    name: "map-seed",
+    about: "Shows the value of a seed in every category",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("seed")
+                .required(true)
+                .value_parser(clap::value_parser!(u64)),
+        )
+    },
+    run: map_seed,
+};
+
+fn map_seed(invocation: &Invocation) -> Result<Report> {
+    let input = invocation.parsed::<Day5>()?;
+    let seed = invocation.arg("seed")?;
+
+    let mut report = Report::new(format!("Mapping seed {seed}"));
+    for (category, value) in CATEGORIES.iter().zip(input.trace(seed)) {
+        report.line(format!("{category:>11} {value}"));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::testutil;
+
+    const DAY5_EXAMPLE: &str = std::include_str!("day5.example");
+
+    crate::example_test!(day5, part1 = 35, part2 = 46);
+
+    #[test]
+    fn can_parse_map_entry() -> Result<()> {
+        assert_eq!(
+            MapEntry::from_str("1 2 3")?,
+            MapEntry {
+                dst_range_start: 1,
+                src_range_start: 2,
+                len: 3
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn can_map_values() -> Result<()> {
+        let me = MapEntry {
+            dst_range_start: 10,
+            src_range_start: 20,
+            len: 5,
+        };
+
+        assert_eq!(me.map_value(22), Some(12));
+        assert_eq!(me.map_value(25), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn can_parse_example() -> Result<()> {
+        let example = Input::from_str(DAY5_EXAMPLE)?;
+
+        eprintln!("{example:?}");
+
+        assert_eq!(example.trace(79), [79, 81, 81, 81, 74, 78, 78, 82]);
+        assert_eq!(example.seed_to_location(79), 82);
+
+        let broken = DAY5_EXAMPLE.replace("52 50 48", "1 2 x");
+        assert!(matches!(
+            Input::from_str(&broken),
+            Err(Error::Numbers(parse::Error::Number { .. }))
+        ));
+        let short = DAY5_EXAMPLE.replace("52 50 48", "52 50");
+        assert_eq!(
+            Input::from_str(&short).err(),
+            Some(Error::Entry("52 50".to_owned()))
+        );
+        let missing = DAY5_EXAMPLE.replace("water-to-light", "water-to-lamp");
+        assert_eq!(
+            Input::from_str(&missing).err(),
+            Some(Error::MissingMap("water-to-light map:".to_owned()))
+        );
+        assert_eq!(Input::from_str("").err(), Some(Error::Empty));
+
+        Ok(())
+    }
+
+    #[test]
+    fn reference_agrees_on_example() -> Result<()> {
+        let example = Input::from_str(DAY5_EXAMPLE)?;
+
+        assert_eq!(
+            Day5::reference(&example)?,
+            [Some(Answer::UInt(35)), Some(Answer::UInt(46))]
+        );
+        assert_eq!(Day5::part1(&example)?, Answer::UInt(35));
+        assert_eq!(Day5::part2(&example)?, Some(Answer::UInt(46)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn lower_bound_holds() -> Result<()> {
+        let example = Input::from_str(DAY5_EXAMPLE)?;
+        let composed = example.composed();
+
+        assert_eq!(location_lower_bound(&example, &composed), Some(46));
+
+        // The entry starts before the seeds, so its first locations are
+        // out of reach.
+        let mut maps: [MapEntries; 7] = Default::default();
+        maps[0] = vec![MapEntry::from_str("100 10 10")?].into();
+        let input = Input {
+            seeds: vec![15, 2],
+            maps,
+        };
+
+        assert_eq!(location_lower_bound(&input, &input.composed()), Some(100));
+        assert_eq!(Day5::part2(&input)?, Some(Answer::UInt(105)));
+
+        Ok(())
+    }
+
+    /// Random maps with non-overlapping entries, like in the puzzle.
+    fn map_entries() -> impl Strategy<Value = MapEntries> {
+        prop::collection::vec((0..10_000u64, 1..500u64, 0..10_000u64), 0..8).prop_map(|raw| {
+            let mut entries = vec![];
+            let mut next = 0;
+
+            for (src_range_start, len, dst_range_start) in raw.into_iter().sorted() {
+                if src_range_start >= next {
+                    entries.push(MapEntry {
+                        dst_range_start,
+                        src_range_start,
+                        len,
+                    });
+                    next = src_range_start + len;
+                }
+            }
+
+            entries.into()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn composed_map_matches_sequential_maps(
+            maps in prop::array::uniform7(map_entries()),
+            seeds in testutil::range(0..12_000, 1..1_000),
+        ) {
+            let input = Input { seeds: vec![], maps };
+            let composed = input.composed();
+            let (start, len) = (seeds.start, seeds.end - seeds.start);
+
+            prop_assert_eq!(composed.map_value(start), input.seed_to_location(start));
+
+            // map_range only translates the first contiguous part, but
+            // that part has to agree with mapping its ends one by one.
+            let range = composed.map_range(start..start + len);
+            let translated = range.end - range.start;
+
+            prop_assert!(translated >= 1 && translated <= len);
+            prop_assert_eq!(range.start, input.seed_to_location(start));
+            prop_assert_eq!(range.end - 1, input.seed_to_location(start + translated - 1));
+        }
+
+        #[test]
+        fn lower_bound_is_below_every_location(
+            maps in prop::array::uniform7(map_entries()),
+            seeds in testutil::range(0..12_000, 1..200),
+        ) {
+            let input = Input { seeds: vec![seeds.start, seeds.end - seeds.start], maps };
+            let bound = location_lower_bound(&input, &input.composed());
+
+            for seed in seeds {
+                prop_assert!(bound.is_some_and(|b| b <= input.seed_to_location(seed)));
+            }
+        }
+    }
+
+    #[test]
+    fn lint_accepts_example() {
+        assert_eq!(Day5::lint(DAY5_EXAMPLE), []);
+
+        let broken = DAY5_EXAMPLE.replace("soil-to-fertilizer", "soil-to-fertiliser");
+        let diagnostics = Day5::lint(&broken);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].column, 1);
+    }
+}
@@ -0,0 +1,372 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+use anyhow::{anyhow, bail, Result};
+use num_bigint::BigUint;
+use thiserror::Error;
+
+use crate::{
+    error::DayError,
+    lint::{self, Diagnostic, Linter},
+    math,
+    parallel::maybe_par_map,
+    parse,
+    solution::{Answer, ExplainQuery, Report, Solution},
+    tool::{Invocation, Tool},
+};
+
+/// The ways a race sheet can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Got {times} times, but {distances} distances")]
+    Unpaired { times: usize, distances: usize },
+    #[error(transparent)]
+    Numbers(#[from] parse::Error),
+}
+
+impl DayError for Error {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Race {
+    time_ms: u64,
+    record_distance_ms: u64,
+}
+
+impl Race {
+    const fn new(time_ms: u64, record_distance_ms: u64) -> Self {
+        Self {
+            time_ms,
+            record_distance_ms,
+        }
+    }
+
+    fn distance(&self, button_press_ms: u64) -> u64 {
+        assert!(button_press_ms <= self.time_ms);
+
+        (self.time_ms - button_press_ms) * button_press_ms
+    }
+
+    /// Presses of `p` win if `(time_ms - p) * p > record_distance_ms`,
+    /// which is `p² - time_ms * p + record_distance_ms + 1 <= 0`.
+    fn winning_moves(&self) -> usize {
+        let time = i128::from(self.time_ms);
+        let record = i128::from(self.record_distance_ms);
+
+        math::quadratic_below(1, -time, record + 1)
+            .map_or(0, |presses| (presses.end() - presses.start() + 1) as usize)
+    }
+}
+
+/// The records that leave exactly `wins` winning moves in a race of
+/// `time_ms`, the inverse of [`Race::winning_moves`].
+///
+/// Distances grow towards the middle of the race and are symmetric
+/// around it, so the winning moves are the presses from some `a` to
+/// `time_ms - a`. That makes `time_ms + 1 - 2a` moves, so `wins` needs
+/// the parity of `time_ms + 1`, and the record has to be at least the
+/// distance of `a - 1` but below that of `a`. A race can't be won by
+/// every move, as standing still never beats a record, and no move wins
+/// against the longest distance.
+#[must_use]
+pub fn records_for(time_ms: u64, wins: u64) -> Option<RangeInclusive<u64>> {
+    let race = Race::new(time_ms, 0);
+
+    if wins == 0 {
+        return Some(race.distance(time_ms / 2)..=u64::MAX);
+    }
+    if wins > time_ms || !(time_ms + 1 - wins).is_multiple_of(2) {
+        return None;
+    }
+
+    let first = (time_ms + 1 - wins) / 2;
+    Some(race.distance(first - 1)..=race.distance(first) - 1)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Races {
+    races: Vec<Race>,
+    /// Part 2 reads all numbers in a line as one. With many races that
+    /// doesn't fit into an integer anymore.
+    kerned: Option<Race>,
+}
+
+impl Races {
+    /// Winning moves per race. There can be hundreds of races in
+    /// generated inputs, so they are spread over all cores.
+    fn margins(&self) -> Vec<usize> {
+        maybe_par_map(&self.races, Race::winning_moves)
+    }
+}
+
+/// Part 2 reads the numbers of a line as a single one, if it fits.
+fn kerned(numbers: &str) -> Option<u64> {
+    numbers
+        .split_ascii_whitespace()
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+impl FromStr for Races {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let times = parse::labeled(lines.next().unwrap_or_default(), "Time:")?;
+        let distances = parse::labeled(lines.next().unwrap_or_default(), "Distance:")?;
+        let (time_numbers, distance_numbers) = (parse::ints(times)?, parse::ints(distances)?);
+
+        if time_numbers.len() != distance_numbers.len() {
+            return Err(Error::Unpaired {
+                times: time_numbers.len(),
+                distances: distance_numbers.len(),
+            });
+        }
+
+        let races = time_numbers
+            .into_iter()
+            .zip(distance_numbers)
+            .map(|(time, distance)| Race::new(time, distance))
+            .collect();
+
+        let kerned = kerned(times)
+            .zip(kerned(distances))
+            .map(|(time, distance)| Race::new(time, distance));
+
+        Ok(Self { races, kerned })
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = Races;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Races, Error> {
+        Races::from_str(input)
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        let mut linter = Linter::new();
+        let lines = lint::lines(&mut linter, input);
+
+        let counts = ["Time:", "Distance:"]
+            .iter()
+            .zip(&lines)
+            .map(|(label, line)| {
+                linter.line(*line, |c| {
+                    c.literal(label)?;
+
+                    let mut count = 0;
+                    while !c.is_at_end() {
+                        c.spaces()?;
+                        c.number()?;
+                        count += 1;
+                    }
+
+                    Ok(count)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if !lines.is_empty() && lines.len() != 2 {
+            linter.error(
+                lines.len().min(2) + 1,
+                1,
+                "Expected exactly two lines with times and distances",
+            );
+        }
+
+        if let [Some(times), Some(distances)] = counts[..] {
+            if times != distances {
+                linter.error(
+                    2,
+                    1,
+                    format!("Got {times} times, but {distances} distances"),
+                );
+            }
+        }
+
+        linter.finish()
+    }
+
+    /// With many races the product quickly outgrows any integer type.
+    fn part1(races: &Races) -> Result<Answer> {
+        let product = races
+            .margins()
+            .into_iter()
+            .map(BigUint::from)
+            .product::<BigUint>();
+
+        Ok(
+            u64::try_from(&product)
+                .map_or_else(|_| Answer::Text(product.to_string()), Answer::UInt),
+        )
+    }
+
+    fn part2(races: &Races) -> Result<Option<Answer>> {
+        let kerned = races
+            .kerned
+            .ok_or_else(|| anyhow!("The kerned race is too long for part 2"))?;
+
+        Ok(Some(kerned.winning_moves().into()))
+    }
+
+    fn explain(races: &Races, _query: &ExplainQuery) -> Result<Report> {
+        let mut report = Report::new("Winning moves per race");
+        report.line(format!(
+            "{:>5}  {:>10}  {:>16}  {:>10}",
+            "race", "time", "record", "margin"
+        ));
+
+        for (i, (race, margin)) in races.races.iter().zip(races.margins()).enumerate() {
+            report.line(format!(
+                "{:>5}  {:>10}  {:>16}  {:>10}",
+                i + 1,
+                race.time_ms,
+                race.record_distance_ms,
+                margin
+            ));
+        }
+
+        Ok(report)
+    }
+}
+
+/// `tool race-records`: which records make a race with a given number
+/// of winning moves, e.g. to write test races.
+pub const RACE_RECORDS: Tool = Tool {
+    year: super::YEAR,
+    day: 6,
+    name: "race-records",
+    about: "Finds the records that leave a given number of winning moves",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("time")
+                .required(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Duration of the race in milliseconds"),
+        )
+        .arg(
+            clap::Arg::new("wins")
+                .required(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("The number of winning moves"),
+        )
+    },
+    run: race_records,
+};
+
+fn race_records(invocation: &Invocation) -> Result<Report> {
+    let time = invocation.arg::<u64>("time")?;
+    let wins = invocation.arg::<u64>("wins")?;
+
+    let Some(records) = records_for(time, wins) else {
+        bail!("No record leaves {wins} winning moves in a race of {time} ms");
+    };
+
+    let mut report = Report::new(format!("Races of {time} ms with {wins} winning moves"));
+    if *records.end() == u64::MAX {
+        report.line(format!("records of {} mm or more", records.start()));
+    } else {
+        report.line(format!(
+            "records from {} to {} mm",
+            records.start(),
+            records.end()
+        ));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distnace_calculation_works() {
+        let example = Race::new(7, 9);
+
+        assert_eq!(example.distance(0), 0);
+        assert_eq!(example.distance(4), 12);
+        assert_eq!(example.distance(7), 0);
+
+        assert_eq!(example.winning_moves(), 4);
+        assert_eq!(Race::new(30, 200).winning_moves(), 9);
+        assert_eq!(Race::new(7, 12).winning_moves(), 0);
+    }
+
+    crate::example_test!(day6, part1 = 288, part2 = 71503);
+
+    #[test]
+    fn margins_work() -> Result<()> {
+        let races = Races::from_str(include_str!("day6.example"))?;
+
+        assert_eq!(races.margins(), [4, 8, 9]);
+        assert_eq!(races.kerned, Some(Race::new(71530, 940200)));
+
+        assert_eq!(
+            Races::from_str("Time: 7 15\nDistance: 9\n").err(),
+            Some(Error::Unpaired {
+                times: 2,
+                distances: 1
+            })
+        );
+        assert_eq!(
+            Races::from_str("Time: 7\n").err(),
+            Some(Error::Numbers(parse::Error::Label("Distance:".to_owned())))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn records_invert_winning_moves() {
+        assert_eq!(records_for(7, 4), Some(6..=9));
+        assert_eq!(records_for(30, 9), Some(200..=208));
+        assert_eq!(records_for(7, 3), None);
+        assert_eq!(records_for(7, 8), None);
+        assert_eq!(records_for(7, 0), Some(12..=u64::MAX));
+
+        for time in 0..40 {
+            for wins in 0..=time + 1 {
+                let Some(records) = records_for(time, wins) else {
+                    assert!(
+                        (0..200).all(|r| Race::new(time, r).winning_moves() != wins as usize),
+                        "{time} {wins}"
+                    );
+                    continue;
+                };
+
+                let (first, last) = (*records.start(), (*records.end()).min(500));
+                for record in first.saturating_sub(1)..=last + 1 {
+                    assert_eq!(
+                        Race::new(time, record).winning_moves() == wins as usize,
+                        records.contains(&record),
+                        "{time} {wins} {record}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn huge_products_become_text() -> Result<()> {
+        let races = Races::from_str(&format!(
+            "Time:{}\nDistance:{}\n",
+            " 100".repeat(20),
+            " 99".repeat(20)
+        ))?;
+
+        assert_eq!(races.kerned, None);
+        assert!(Day6::part2(&races).is_err());
+
+        // 97 winning moves each, 97^20 doesn't fit into 64 bits.
+        assert_eq!(
+            Day6::part1(&races)?,
+            Answer::Text(BigUint::from(97u32).pow(20).to_string())
+        );
+
+        Ok(())
+    }
+}
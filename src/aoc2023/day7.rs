@@ -0,0 +1,822 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+
+use rand::Rng as _;
+use thiserror::Error;
+
+use crate::{
+    error::DayError,
+    gen::{self, Rng},
+    iter::AocIteratorExt,
+    lint::{self, Diagnostic, Linter},
+    parallel::maybe_par_map,
+    solution::{Answer, ExplainQuery, Report, Solution},
+    tool::{Invocation, Tool},
+};
+
+/// The ways a list of hands and bids can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Unknown card face {0}")]
+    Face(String),
+    #[error("Expected a hand of five cards, got {0}")]
+    HandSize(String),
+    #[error("Expected a hand and a bid, got {0:?}")]
+    HandBid(String),
+    #[error("Can't parse bid {0}")]
+    Bid(String),
+    #[error("Line {line}: {error}")]
+    Line { line: usize, error: Box<Error> },
+}
+
+impl DayError for Error {}
+
+/// The faces of all cards as they appear in the input.
+const FACES: &str = "23456789TJQKA";
+
+#[repr(u8)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+enum Card {
+    Joker,
+    N2,
+    N3,
+    N4,
+    N5,
+    N6,
+    N7,
+    N8,
+    N9,
+    T,
+    J,
+    Q,
+    K,
+    A,
+}
+
+impl FromStr for Card {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Card::*;
+
+        Ok(match s {
+            "2" => N2,
+            "3" => N3,
+            "4" => N4,
+            "5" => N5,
+            "6" => N6,
+            "7" => N7,
+            "8" => N8,
+            "9" => N9,
+            "T" => T,
+            "J" => J,
+            "Q" => Q,
+            "K" => K,
+            "A" => A,
+            _ => return Err(Error::Face(s.to_owned())),
+        })
+    }
+}
+
+impl Card {
+    /// The face as in the input. Jokers are written as J.
+    fn face(self) -> char {
+        match self {
+            Card::Joker => 'J',
+            card => char::from(FACES.as_bytes()[card as usize - 1]),
+        }
+    }
+
+    fn j_to_toker(self) -> Self {
+        if self == Card::J {
+            Card::Joker
+        } else {
+            self
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Hand {
+    cards: [Card; 5],
+}
+
+impl From<[Card; 5]> for Hand {
+    fn from(value: [Card; 5]) -> Self {
+        Self { cards: value }
+    }
+}
+
+impl FromStr for Hand {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // TODO The extra string allocations could be optimized away if we iterate over slices of length 1.
+        let card_vector: Vec<Card> = s
+            .chars()
+            .map(|c| Card::from_str(&c.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let card_array: [Card; 5] = card_vector
+            .try_into()
+            .map_err(|_| Error::HandSize(s.to_owned()))?;
+
+        Ok(Self { cards: card_array })
+    }
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for card in self.cards {
+            write!(f, "{}", card.face())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl Kind {
+    /// The kind of a hand whose cards come up `counts` times, most
+    /// frequent first.
+    fn of(counts: &[usize]) -> Kind {
+        match counts {
+            [5] => Kind::FiveOfAKind,
+            [4, ..] => Kind::FourOfAKind,
+            [3, 2] => Kind::FullHouse,
+            [3, ..] => Kind::ThreeOfAKind,
+            [2, 2, ..] => Kind::TwoPair,
+            [2, ..] => Kind::OnePair,
+            _ => Kind::HighCard,
+        }
+    }
+}
+
+impl Hand {
+    fn as_joker_hand(&self) -> Hand {
+        Hand {
+            cards: self.cards.map(Card::j_to_toker),
+        }
+    }
+
+    /// The card the jokers are best played as, which is the most common
+    /// other card. Ties go to the higher card, though any of them makes
+    /// the same kind. Jokers alone become aces. `None` without jokers.
+    fn joker_assignment(&self) -> Option<Card> {
+        if !self.cards.contains(&Card::Joker) {
+            return None;
+        }
+
+        Some(
+            self.cards
+                .iter()
+                .copied()
+                .filter(|c| *c != Card::Joker)
+                .max_by_key(|c| (self.count_card(*c), *c))
+                .unwrap_or(Card::A),
+        )
+    }
+
+    /// The hand with its jokers replaced by [`Hand::joker_assignment`].
+    fn with_jokers_assigned(&self) -> Hand {
+        let Some(card) = self.joker_assignment() else {
+            return *self;
+        };
+
+        Hand {
+            cards: self.cards.map(|c| if c == Card::Joker { card } else { c }),
+        }
+    }
+
+    fn count_card(&self, card: Card) -> usize {
+        self.cards.into_iter().filter(|c| *c == card).count()
+    }
+
+    fn kind(&self) -> Kind {
+        debug_assert!(self.cards.iter().all(|c| *c != Card::Joker));
+
+        Kind::of(&self.cards.iter().counts_sorted_desc())
+    }
+
+    /// Jokers join the most common other card, which always makes the
+    /// best kind.
+    fn kind_with_jokers(&self) -> Kind {
+        let jokers = self.count_card(Card::Joker);
+        let mut counts = self
+            .cards
+            .iter()
+            .filter(|c| **c != Card::Joker)
+            .counts_sorted_desc();
+
+        match counts.first_mut() {
+            Some(most) => *most += jokers,
+            None => counts.push(jokers),
+        }
+
+        Kind::of(&counts)
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let self_kind = self.kind_with_jokers();
+        let other_kind = other.kind_with_jokers();
+
+        match self_kind.cmp(&other_kind) {
+            Ordering::Less => Ordering::Less,
+            Ordering::Greater => Ordering::Greater,
+            Ordering::Equal => self.cards.cmp(&other.cards),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HandBid {
+    hand: Hand,
+    bid: u32,
+}
+
+impl HandBid {
+    fn as_joker_hand(&self) -> Self {
+        HandBid {
+            hand: self.hand.as_joker_hand(),
+            bid: self.bid,
+        }
+    }
+}
+
+impl FromStr for HandBid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split_ascii_whitespace().collect::<Vec<_>>();
+        if parts.len() != 2 {
+            return Err(Error::HandBid(s.to_owned()));
+        }
+
+        Ok(HandBid {
+            hand: Hand::from_str(parts[0])?,
+            bid: u32::from_str(parts[1]).map_err(|_| Error::Bid(parts[1].to_owned()))?,
+        })
+    }
+}
+
+/// Hands sorted by strength, `jokers` like in part 2. The kind of each
+/// hand is computed once instead of in every comparison.
+fn ranked(input: &[HandBid], jokers: bool) -> Vec<(Kind, HandBid)> {
+    input
+        .iter()
+        .map(|hb| if jokers { hb.as_joker_hand() } else { *hb })
+        .map(|hb| (hb.hand.kind_with_jokers(), hb))
+        .sorted_by_key(|(kind, hb)| (*kind, hb.hand.cards))
+        .collect()
+}
+
+fn total_winnings(ranked: &[(Kind, HandBid)]) -> usize {
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (_, hb))| (i + 1) * usize::try_from(hb.bid).unwrap())
+        .sum()
+}
+
+/// What the hands of one kind contribute to the total winnings. Hands of
+/// the same kind have consecutive ranks.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct KindWinnings {
+    kind: Kind,
+    first_rank: usize,
+    last_rank: usize,
+    winnings: usize,
+}
+
+fn winnings_by_kind(ranked: &[(Kind, HandBid)]) -> Vec<KindWinnings> {
+    let mut buckets: Vec<KindWinnings> = vec![];
+
+    for (i, (kind, hb)) in ranked.iter().enumerate() {
+        let rank = i + 1;
+        let winnings = rank * usize::try_from(hb.bid).unwrap();
+
+        match buckets.last_mut() {
+            Some(bucket) if bucket.kind == *kind => {
+                bucket.last_rank = rank;
+                bucket.winnings += winnings;
+            }
+            _ => buckets.push(KindWinnings {
+                kind: *kind,
+                first_rank: rank,
+                last_rank: rank,
+                winnings,
+            }),
+        }
+    }
+
+    buckets
+}
+
+/// The hands of a game, ranked against each other. Inputs may hold
+/// several decks separated by empty lines, which are ranked
+/// independently.
+pub type Deck = Vec<HandBid>;
+
+/// The total winnings of each deck.
+fn winnings_per_deck(decks: &[Deck], jokers: bool) -> Vec<usize> {
+    maybe_par_map(decks, |deck| total_winnings(&ranked(deck, jokers)))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = Vec<Deck>;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Vec<Deck>, Error> {
+        let lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l))
+            .collect::<Vec<_>>();
+
+        lint::blocks(&lines)
+            .iter()
+            .map(|block| {
+                block
+                    .iter()
+                    .map(|&(line, text)| {
+                        HandBid::from_str(text).map_err(|error| Error::Line {
+                            line,
+                            error: Box::new(error),
+                        })
+                    })
+                    .collect::<Result<Deck, _>>()
+            })
+            .collect()
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        let mut linter = Linter::new();
+        let lines = lint::lines(&mut linter, input);
+
+        for line in lint::blocks(&lines).iter().flatten() {
+            linter.line(*line, |c| {
+                let start = c.clone();
+                let cards = c.many(|ch| FACES.contains(ch), "a card")?;
+                c.spaces()?;
+
+                if cards.len() != 5 {
+                    return Err(start.error(format!("Expected 5 cards, got {}", cards.len())));
+                }
+
+                c.number()?;
+                c.end()
+            });
+        }
+
+        linter.finish()
+    }
+
+    /// The winnings of all decks together.
+    fn part1(input: &Vec<Deck>) -> Result<Answer> {
+        Ok(winnings_per_deck(input, false).iter().sum::<usize>().into())
+    }
+
+    fn part2(input: &Vec<Deck>) -> Result<Option<Answer>> {
+        Ok(Some(
+            winnings_per_deck(input, true).iter().sum::<usize>().into(),
+        ))
+    }
+
+    /// Which ranks each kind of hand occupies and what it wins, with and
+    /// without jokers, in the deck selected with `deck=N`. Inputs with
+    /// several decks also get the winnings of each deck. With `hand=KTJJT`
+    /// it shows what the jokers of that hand are best played as instead.
+    fn explain(input: &Vec<Deck>, query: &ExplainQuery) -> Result<Report> {
+        if let Some(cards) = query.get("hand") {
+            return explain_jokers(cards);
+        }
+
+        let mut report = Report::new("Winnings by kind of hand");
+
+        if input.len() > 1 {
+            explain_decks(input, &mut report);
+        }
+
+        let number = query.get_or("deck", 1usize)?;
+        let deck = number
+            .checked_sub(1)
+            .and_then(|i| input.get(i))
+            .ok_or_else(|| anyhow!("There is no deck {number}"))?;
+
+        for (rules, jokers) in [("Without jokers", false), ("With jokers", true)] {
+            report.line(format!("{rules:<14}  {:>13}  {:>12}", "ranks", "winnings"));
+
+            for bucket in winnings_by_kind(&ranked(deck, jokers)) {
+                report.line(format!(
+                    "{:<14}  {:>6}..{:<5}  {:>12}",
+                    format!("{:?}", bucket.kind),
+                    bucket.first_rank,
+                    bucket.last_rank,
+                    bucket.winnings
+                ));
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// `size` hands with random bids, split into `decks=N` decks of
+    /// about the same size.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        let decks = gen::params().get_or("decks", 1usize)?.clamp(1, size.max(1));
+
+        Ok((0..size)
+            .map(|i| {
+                let hand = (0..5)
+                    .map(|_| char::from(FACES.as_bytes()[rng.gen_range(0..FACES.len())]))
+                    .collect::<String>();
+                let separator = if i > 0 && i * decks % size < decks {
+                    "\n"
+                } else {
+                    ""
+                };
+
+                format!("{separator}{hand} {}\n", rng.gen_range(1..1000))
+            })
+            .collect())
+    }
+}
+
+/// The winnings of each deck and how they are spread.
+fn explain_decks(decks: &[Deck], report: &mut Report) {
+    let part1 = winnings_per_deck(decks, false);
+    let part2 = winnings_per_deck(decks, true);
+
+    report.line(format!(
+        "{:<14}  {:>6}  {:>12}  {:>12}",
+        "Deck", "hands", "part 1", "part 2"
+    ));
+    for (i, deck) in decks.iter().enumerate() {
+        report.line(format!(
+            "{:<14}  {:>6}  {:>12}  {:>12}",
+            i + 1,
+            deck.len(),
+            part1[i],
+            part2[i]
+        ));
+    }
+
+    let spread = |w: &[usize]| {
+        let total = w.iter().sum::<usize>();
+        [
+            total,
+            total / w.len().max(1),
+            w.iter().copied().min().unwrap_or_default(),
+            w.iter().copied().max().unwrap_or_default(),
+        ]
+    };
+
+    for (statistic, (part1, part2)) in ["Total", "Mean", "Min", "Max"]
+        .into_iter()
+        .zip(spread(&part1).into_iter().zip(spread(&part2)))
+    {
+        report.line(format!(
+            "{statistic:<14}  {:>6}  {part1:>12}  {part2:>12}",
+            ""
+        ));
+    }
+}
+
+/// What the jokers of `cards` are best played as and the kind that makes.
+fn explain_jokers(cards: &str) -> Result<Report> {
+    let hand = Hand::from_str(cards)?.as_joker_hand();
+    let assigned = hand.with_jokers_assigned();
+
+    let mut report = Report::new(format!("Jokers in {cards}"));
+    match hand.joker_assignment() {
+        Some(card) => report.line(format!("best played as {}", card.face())),
+        None => report.line("no jokers"),
+    };
+    report.line(format!("{assigned}: {:?}", assigned.kind()));
+
+    Ok(report)
+}
+
+/// `tool classify-hand`: the kind of a single hand, without needing an
+/// input.
+pub const CLASSIFY_HAND: Tool = Tool {
+    year: super::YEAR,
+    day: 7,
+    name: "classify-hand",
+    about: "Shows the kind of a hand like 32T3K",
+    args: |cmd| {
+        cmd.arg(clap::Arg::new("hand").required(true)).arg(
+            clap::Arg::new("jokers")
+                .long("jokers")
+                .action(clap::ArgAction::SetTrue)
+                .help("Treats J as joker like in part 2"),
+        )
+    },
+    run: classify_hand,
+};
+
+fn classify_hand(invocation: &Invocation) -> Result<Report> {
+    let cards = invocation.arg::<String>("hand")?;
+    let hand = Hand::from_str(&cards)?;
+    let hand = if invocation.arg("jokers")? {
+        hand.as_joker_hand()
+    } else {
+        hand
+    };
+
+    let mut report = Report::new(format!("Kind of {cards}"));
+    report.line(format!("{:?}", hand.kind_with_jokers()));
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::testutil;
+
+    #[test]
+    fn jokers_are_assigned_optimally() -> Result<()> {
+        let cards = FACES
+            .chars()
+            .map(|c| Card::from_str(&c.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Every way to replace the jokers, one card at a time.
+        let best_substitution = |hand: Hand| {
+            let jokers = hand.count_card(Card::Joker);
+            (0..jokers)
+                .map(|_| cards.iter().copied())
+                .multi_cartesian_product()
+                .map(|substitutes| {
+                    let mut substitutes = substitutes.into_iter();
+                    Hand {
+                        cards: hand.cards.map(|c| match c {
+                            Card::Joker => substitutes.next().unwrap_or(c),
+                            c => c,
+                        }),
+                    }
+                    .kind()
+                })
+                .max()
+                .unwrap_or_else(|| hand.kind())
+        };
+
+        for text in [
+            "KTJJT", "JJJJJ", "J2345", "QQJQQ", "2233J", "T55J5", "32T3K",
+        ] {
+            let hand = Hand::from_str(text)?.as_joker_hand();
+            let assigned = hand.with_jokers_assigned();
+
+            assert!(assigned.cards.iter().all(|c| *c != Card::Joker));
+            assert_eq!(assigned.kind(), best_substitution(hand), "{text}");
+            assert_eq!(assigned.kind(), hand.kind_with_jokers(), "{text}");
+        }
+
+        let report = Day7::explain(&vec![], &ExplainQuery::from_str("hand=KTJJT")?)?;
+        assert_eq!(report.lines, ["best played as T", "KTTTT: FourOfAKind"]);
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn joker_assignment_is_optimal(text in testutil::hand()) {
+            let hand = Hand::from_str(&text).unwrap().as_joker_hand();
+
+            prop_assert_eq!(hand.with_jokers_assigned().kind(), hand.kind_with_jokers());
+        }
+    }
+
+    #[test]
+    fn cards_are_ordered_correctly() {
+        use Card::*;
+
+        assert!(N2 == N2);
+        assert!(N2 < N3);
+        assert!(N2 < A);
+    }
+
+    #[test]
+    fn can_parse_hand() -> Result<()> {
+        use Card::*;
+
+        assert_eq!(Hand::from_str("T55J5")?, [T, N5, N5, J, N5].into());
+        assert_eq!(Hand::from_str("T55X5"), Err(Error::Face("X".to_owned())));
+        assert_eq!(
+            Hand::from_str("T55"),
+            Err(Error::HandSize("T55".to_owned()))
+        );
+        assert_eq!(
+            Day7::parse("32T3K 765\nT55J5 x\n").unwrap_err().to_string(),
+            "Line 2: Can't parse bid x"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn recognizes_kind() -> Result<()> {
+        use Kind::*;
+
+        assert_eq!(Hand::from_str("55555")?.kind(), FiveOfAKind);
+        assert_eq!(Hand::from_str("5A555")?.kind(), FourOfAKind);
+        assert_eq!(Hand::from_str("5A5A5")?.kind(), FullHouse);
+        assert_eq!(Hand::from_str("5A5K5")?.kind(), ThreeOfAKind);
+        assert_eq!(Hand::from_str("5A5AK")?.kind(), TwoPair);
+        assert_eq!(Hand::from_str("J5Q5A")?.kind(), OnePair);
+        assert_eq!(Hand::from_str("J5Q2A")?.kind(), HighCard);
+
+        Ok(())
+    }
+
+    #[test]
+    fn recognizes_kind_with_jokers() -> Result<()> {
+        use Kind::*;
+
+        assert_eq!(
+            Hand::from_str("32T3K")?.as_joker_hand().kind_with_jokers(),
+            OnePair
+        );
+        assert_eq!(
+            Hand::from_str("KK677")?.as_joker_hand().kind_with_jokers(),
+            TwoPair
+        );
+        assert_eq!(
+            Hand::from_str("T55J5")?.as_joker_hand().kind_with_jokers(),
+            FourOfAKind
+        );
+        assert_eq!(
+            Hand::from_str("KTJJT")?.as_joker_hand().kind_with_jokers(),
+            FourOfAKind
+        );
+        assert_eq!(
+            Hand::from_str("QQQJA")?.as_joker_hand().kind_with_jokers(),
+            FourOfAKind
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn can_compare_hands() -> Result<()> {
+        assert!(Hand::from_str("55555")? > Hand::from_str("5A555")?);
+        assert!(Hand::from_str("55554")? > Hand::from_str("55553")?);
+
+        Ok(())
+    }
+
+    crate::example_test!(day7, part1 = 6440, part2 = 5905);
+
+    proptest! {
+        #[test]
+        fn hands_roundtrip(text in testutil::hand()) {
+            let hand = Hand::from_str(&text).unwrap();
+
+            prop_assert_eq!(&hand.to_string(), &text);
+            prop_assert_eq!(hand.as_joker_hand().to_string(), text);
+        }
+
+        #[test]
+        fn hands_are_totally_ordered(
+            a in testutil::hand(),
+            b in testutil::hand(),
+            c in testutil::hand(),
+            jokers: bool,
+        ) {
+            let [a, b, c] = [a, b, c].map(|h| {
+                let hand = Hand::from_str(&h).unwrap();
+                if jokers { hand.as_joker_hand() } else { hand }
+            });
+
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+            if a <= b && b <= c {
+                prop_assert!(a <= c);
+            }
+        }
+
+        #[test]
+        fn jokers_never_make_hands_worse(text in testutil::hand()) {
+            let hand = Hand::from_str(&text).unwrap();
+
+            prop_assert!(hand.as_joker_hand().kind_with_jokers() >= hand.kind());
+        }
+    }
+
+    #[test]
+    fn winnings_by_kind_add_up() -> Result<()> {
+        use Kind::*;
+
+        let decks = Day7::parse(include_str!("day7.example"))?;
+        let hand_bids = &decks[0];
+
+        let bucket = |kind, first_rank, last_rank, winnings| KindWinnings {
+            kind,
+            first_rank,
+            last_rank,
+            winnings,
+        };
+
+        assert_eq!(
+            winnings_by_kind(&ranked(hand_bids, false)),
+            [
+                bucket(OnePair, 1, 1, 765),
+                bucket(TwoPair, 2, 3, 220 * 2 + 28 * 3),
+                bucket(ThreeOfAKind, 4, 5, 684 * 4 + 483 * 5),
+            ]
+        );
+        assert_eq!(
+            winnings_by_kind(&ranked(hand_bids, true)),
+            [
+                bucket(OnePair, 1, 1, 765),
+                bucket(TwoPair, 2, 2, 28 * 2),
+                bucket(FourOfAKind, 3, 5, 684 * 3 + 483 * 4 + 220 * 5),
+            ]
+        );
+        assert_eq!(
+            winnings_by_kind(&ranked(hand_bids, true))
+                .iter()
+                .map(|b| b.winnings)
+                .sum::<usize>(),
+            5905
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn decks_are_ranked_independently() -> Result<()> {
+        let example = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+        let decks = Day7::parse(&format!("{example}\n\n{example}\n\n\n{example}\n"))?;
+
+        assert_eq!(decks.len(), 3);
+        assert_eq!(winnings_per_deck(&decks, false), [6440; 3]);
+        assert_eq!(Day7::part1(&decks)?, Answer::UInt(3 * 6440));
+        assert_eq!(Day7::part2(&decks)?, Some(Answer::UInt(3 * 5905)));
+        assert_eq!(Day7::lint(&format!("{example}\n\n{example}")), []);
+
+        let explained = Day7::explain(&decks, &ExplainQuery::from_str("deck=2")?)?.to_string();
+        assert!(explained.contains("Total"));
+        assert!(Day7::explain(&decks, &ExplainQuery::from_str("deck=4")?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn example2_works() -> Result<()> {
+        let example = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+        let hand_bids = example
+            .lines()
+            .map(|l| HandBid::from_str(l).map(|hb| hb.as_joker_hand()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let sorted_hand_bids = hand_bids
+            .into_iter()
+            .sorted_by_key(|hb| hb.hand)
+            .collect::<Vec<_>>();
+
+        eprintln!("{:?}", sorted_hand_bids);
+
+        assert_eq!(
+            sorted_hand_bids
+                .into_iter()
+                .enumerate()
+                .map(|(i, hb)| (i + 1) * usize::try_from(hb.bid).unwrap())
+                .sum::<usize>(),
+            5905
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,900 @@
+pub(crate) use std::{collections::BTreeMap, fmt::Display, fs, path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+use rand::Rng as _;
+use regex::Regex;
+use thiserror::Error;
+
+use crate::{
+    cancel::CancelToken,
+    cycle::{self, Periodic},
+    error::DayError,
+    gen::Rng,
+    geometry::Direction,
+    lint::{self, Cursor, Diagnostic, LineError, Linter},
+    math,
+    solution::{self, Answer, Report, Solution},
+    tool::{Invocation, Tool},
+};
+
+/// The ways a map of the network can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("The map is empty")]
+    Empty,
+    #[error("There are no directions")]
+    NoDirections,
+    #[error("Invalid direction {0}")]
+    Direction(char),
+    #[error("Expected a node like `AAA = (BBB, CCC)`, got {0:?}")]
+    Node(String),
+    #[error("Expected a label of three characters, got {0}")]
+    Label(String),
+    #[error("Node {0} is defined twice")]
+    Redefined(String),
+    #[error("Node {0} is never defined")]
+    Undefined(String),
+}
+
+impl DayError for Error {}
+
+/// Parses an `L` or `R` of the directions. The network only goes left
+/// and right, so those are the only directions there are.
+fn turn(c: char) -> Result<Direction, Error> {
+    match Direction::try_from(c) {
+        Ok(d @ (Direction::West | Direction::East)) => Ok(d),
+        _ => Err(Error::Direction(c)),
+    }
+}
+
+/// Which of the two successors of a node `direction` leads to.
+fn side(direction: Direction) -> usize {
+    usize::from(direction == Direction::East)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+struct Label {
+    name: [char; 3],
+}
+
+const AAA: Label = Label {
+    name: ['A', 'A', 'A'],
+};
+
+impl Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name.iter().collect::<String>())
+    }
+}
+
+impl Label {
+    fn is_start_node(&self) -> bool {
+        self.name[2] == 'A'
+    }
+
+    fn is_end_node(&self) -> bool {
+        self.name[2] == 'Z'
+    }
+}
+
+impl FromStr for Label {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Label {
+            name: s
+                .chars()
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| Error::Label(s.to_owned()))?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Input {
+    directions: Vec<Direction>,
+    map: BTreeMap<Label, (Label, Label)>,
+}
+
+impl Input {
+    fn next_label(&self, current: Label, direction: Direction) -> Result<Label> {
+        let (left, right) = self
+            .map
+            .get(&current)
+            .copied()
+            .ok_or_else(|| anyhow!("Invalid label: {current}"))?;
+
+        Ok([left, right][side(direction)])
+    }
+
+    /// The nodes visited when following the directions from `start`,
+    /// with the direction taken to get to each. Ends after the first
+    /// node that isn't in the map.
+    fn path(&self, start: Label) -> impl Iterator<Item = Result<(Direction, Label)>> + '_ {
+        self.directions
+            .iter()
+            .copied()
+            .cycle()
+            .scan(Some(start), move |location, direction| {
+                let next = self.next_label((*location)?, direction);
+                *location = next.as_ref().ok().copied();
+                Some(next.map(|next| (direction, next)))
+            })
+    }
+
+    /// Never ends if no end node can be reached, so it gives up once
+    /// the solution is cancelled.
+    fn solve_one(&self, start: Label) -> Result<usize> {
+        let cancel = CancelToken::current();
+        let mut location = start;
+
+        for (steps, direction) in self.directions.iter().copied().cycle().enumerate() {
+            if location.is_end_node() {
+                return Ok(steps);
+            }
+            if steps % self.directions.len() == 0 {
+                cancel.check()?;
+            }
+
+            location = self.next_label(location, direction)?;
+        }
+
+        // The for loop never finishes.
+        unreachable!();
+    }
+
+    fn solve_part1(&self) -> Result<usize> {
+        self.solve_one(AAA)
+    }
+
+    fn start_nodes(&self) -> Vec<Label> {
+        self.map
+            .keys()
+            .copied()
+            .filter(|l| l.is_start_node())
+            .collect()
+    }
+
+    /// Assumes that each ghost reaches its only end node after exactly
+    /// one cycle length, like in the puzzle inputs, but not in general.
+    fn solve_part2_lcm(&self) -> Result<usize> {
+        let solutions = self
+            .start_nodes()
+            .into_iter()
+            .map(|l| self.solve_one(l))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(solutions.into_iter().fold(1, math::lcm))
+    }
+
+    /// The step from a node at a position in the directions.
+    fn step(&self, (location, index): (Label, usize)) -> Result<(Label, usize)> {
+        let next = self.next_label(location, self.directions[index])?;
+        Ok((next, (index + 1) % self.directions.len()))
+    }
+
+    /// Follows a ghost until it is at the same node at the same position
+    /// in the directions again, noting when it is at end nodes.
+    fn ghost(&self, start: Label) -> Result<Periodic> {
+        let cycle = cycle::try_find_cycle((start, 0), |state| self.step(*state))?;
+        Ok(Periodic::from_cycle(&cycle, |(location, _)| {
+            location.is_end_node()
+        }))
+    }
+
+    /// Works for any map: finds the cycle of each ghost and when they
+    /// are all at end nodes at once.
+    fn solve_part2(&self) -> Result<usize> {
+        let ghosts = self
+            .start_nodes()
+            .into_iter()
+            .map(|l| self.ghost(l))
+            .collect::<Result<Vec<_>>>()?;
+
+        cycle::first_common_hit(&ghosts)
+            .ok_or_else(|| anyhow!("The ghosts never all reach end nodes at once"))
+    }
+
+    /// Moves all ghosts in lockstep for up to `limit` steps.
+    fn walk_all(&self, limit: usize) -> Result<Option<usize>> {
+        let cancel = CancelToken::current();
+        let mut locations = self.start_nodes();
+
+        for (steps, direction) in self.directions.iter().copied().cycle().enumerate() {
+            if locations.iter().all(Label::is_end_node) {
+                return Ok(Some(steps));
+            }
+            if steps == limit {
+                return Ok(None);
+            }
+            if steps % self.directions.len() == 0 {
+                cancel.check()?;
+            }
+
+            for location in &mut locations {
+                *location = self.next_label(*location, direction)?;
+            }
+        }
+
+        unreachable!("directions are cycled forever");
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Algo {
+    Crt,
+    Lcm,
+}
+
+impl Algo {
+    fn selected() -> Self {
+        match solution::algo() {
+            Some("lcm") => Algo::Lcm,
+            _ => Algo::Crt,
+        }
+    }
+}
+
+impl FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        let directions = lines
+            .next()
+            .ok_or(Error::Empty)?
+            .chars()
+            .map(turn)
+            .collect::<Result<Vec<_>, _>>()?;
+        if directions.is_empty() {
+            return Err(Error::NoDirections);
+        }
+
+        let re = Regex::new(r"^(...) = \((...), (...)\)$").expect("the node pattern is valid");
+        let mut map: BTreeMap<Label, (Label, Label)> = BTreeMap::default();
+
+        for l in lines {
+            if l.is_empty() {
+                continue;
+            }
+
+            let captures = re.captures(l).ok_or_else(|| Error::Node(l.to_owned()))?;
+
+            let label_from = |group| Label::from_str(captures.get(group).unwrap().as_str());
+
+            let from = label_from(1)?;
+            if map.insert(from, (label_from(2)?, label_from(3)?)).is_some() {
+                return Err(Error::Redefined(from.to_string()));
+            }
+        }
+
+        if let Some(undefined) = map
+            .values()
+            .flat_map(|(left, right)| [left, right])
+            .find(|label| !map.contains_key(label))
+        {
+            return Err(Error::Undefined(undefined.to_string()));
+        }
+
+        Ok(Self { directions, map })
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = Input;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Input, Error> {
+        Input::from_str(input)
+    }
+
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        fn node(c: &mut Cursor) -> Result<(), LineError> {
+            let start = c.clone();
+            if c.many(|ch| ch.is_ascii_alphanumeric(), "a node")?.len() != 3 {
+                return Err(start.error("Nodes have three characters"));
+            }
+
+            Ok(())
+        }
+
+        let mut linter = Linter::new();
+        let lines = lint::lines(&mut linter, input);
+        let blocks = lint::blocks(&lines);
+
+        let Some((directions, network)) = blocks.split_first() else {
+            return linter.finish();
+        };
+
+        linter.line(directions[0], |c| c.only(|ch| ch == 'L' || ch == 'R'));
+        for (number, _) in &directions[1..] {
+            linter.error(*number, 1, "Expected an empty line after the directions");
+        }
+
+        if network.is_empty() {
+            linter.error(lines.len() + 1, 1, "Expected the network of nodes");
+        }
+
+        for line in network.iter().flatten() {
+            linter.line(*line, |c| {
+                node(c)?;
+                c.literal(" = (")?;
+                node(c)?;
+                c.literal(", ")?;
+                node(c)?;
+                c.literal(")")?;
+                c.end()
+            });
+        }
+
+        linter.finish()
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        Ok(input.solve_part1()?.into())
+    }
+
+    fn part2(input: &Input) -> Result<Option<Answer>> {
+        Ok(Some(
+            match Algo::selected() {
+                Algo::Crt => input.solve_part2()?,
+                Algo::Lcm => input.solve_part2_lcm()?,
+            }
+            .into(),
+        ))
+    }
+
+    /// Walks all ghosts at once. That takes far too long for the puzzle
+    /// inputs, so part 2 only has a reference on small generated maps.
+    fn reference(input: &Input) -> Result<[Option<Answer>; 2]> {
+        Ok([None, input.walk_all(100_000)?.map(Answer::from)])
+    }
+
+    /// About `size` nodes in two to four ghost paths that take a while
+    /// to get into their cycles and pass one or more end nodes in each.
+    /// Where the ghosts first meet at end nodes is unrelated to the cycle
+    /// lengths, so `--algo lcm` gets part 2 wrong.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        // The first two characters of a label number the nodes, the last
+        // one tells start and end nodes apart from the others.
+        const CHARS: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY0123456789";
+        let label = |node: usize, last: char| {
+            format!(
+                "{}{}{last}",
+                char::from(CHARS[node / CHARS.len()]),
+                char::from(CHARS[node % CHARS.len()])
+            )
+        };
+
+        let ghosts = rng.gen_range(2..=4);
+        let nodes = (size / ghosts).clamp(4, CHARS.len() * CHARS.len() / ghosts);
+
+        let paths = (0..ghosts)
+            .map(|_| {
+                let cycle = rng.gen_range(2..=nodes / 2);
+                (nodes - cycle, cycle)
+            })
+            .collect::<Vec<_>>();
+
+        // All ghosts are at end nodes after `meet` steps. Anything else in
+        // their cycles may be an end node, too.
+        let longest = paths.iter().map(|(lead, _)| *lead).max().unwrap_or(0);
+        let meet = rng.gen_range(longest..longest + 10 * nodes);
+
+        let mut map = (0..rng.gen_range(2..=20))
+            .map(|_| if rng.gen_ratio(1, 2) { 'L' } else { 'R' })
+            .collect::<String>();
+        map.push_str("\n\n");
+
+        for (ghost, (lead, cycle)) in paths.into_iter().enumerate() {
+            let labels = (0..lead + cycle)
+                .map(|i| match i {
+                    0 if ghost == 0 => "AAA".to_owned(),
+                    0 => label(ghost * nodes, 'A'),
+                    i if i >= lead
+                        && ((i - lead) == (meet - lead) % cycle || rng.gen_ratio(1, 8)) =>
+                    {
+                        label(ghost * nodes + i, 'Z')
+                    }
+                    i => label(ghost * nodes + i, 'X'),
+                })
+                .collect::<Vec<_>>();
+
+            for (i, from) in labels.iter().enumerate() {
+                let to = &labels[if i + 1 == labels.len() { lead } else { i + 1 }];
+                map.push_str(&format!("{from} = ({to}, {to})\n"));
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+/// `tool walk`: the first steps through the network from one node.
+pub const WALK: Tool = Tool {
+    year: super::YEAR,
+    day: 8,
+    name: "walk",
+    about: "Follows the directions for a few steps",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("from")
+                .long("from")
+                .default_value("AAA")
+                .help("The node to start at"),
+        )
+        .arg(
+            clap::Arg::new("steps")
+                .long("steps")
+                .default_value("10")
+                .value_parser(clap::value_parser!(usize))
+                .help("How many steps to take"),
+        )
+    },
+    run: walk,
+};
+
+fn walk(invocation: &Invocation) -> Result<Report> {
+    let input = invocation.parsed::<Day8>()?;
+    let mut location = Label::from_str(&invocation.arg::<String>("from")?)?;
+
+    let mut report = Report::new(format!("Walking from {location}"));
+    for (step, next) in input
+        .path(location)
+        .take(invocation.arg("steps")?)
+        .enumerate()
+    {
+        let (direction, next) = next?;
+        let end = if next.is_end_node() { " (end)" } else { "" };
+
+        report.line(format!(
+            "{:>5} {location} {direction} {next}{end}",
+            step + 1
+        ));
+        location = next;
+    }
+
+    Ok(report)
+}
+
+/// `tool compare-maps`: what changed between two maps, and where that
+/// makes the walk from AAA go elsewhere.
+pub const COMPARE_MAPS: Tool = Tool {
+    year: super::YEAR,
+    day: 8,
+    name: "compare-maps",
+    about: "Compares two maps and where walking them from AAA diverges",
+    args: |cmd| {
+        cmd.arg(
+            clap::Arg::new("a")
+                .required(true)
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("The first map"),
+        )
+        .arg(
+            clap::Arg::new("b")
+                .required(true)
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("The second map"),
+        )
+        .arg(
+            clap::Arg::new("steps")
+                .long("steps")
+                .default_value("1000000")
+                .value_parser(clap::value_parser!(usize))
+                .help("Gives up looking for a divergence after this many steps"),
+        )
+    },
+    run: compare_maps,
+};
+
+fn compare_maps(invocation: &Invocation) -> Result<Report> {
+    let [a, b] = ["a", "b"].map(|id| {
+        let path = invocation.arg::<PathBuf>(id)?;
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Input::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))
+    });
+    let (a, b) = (a?, b?);
+
+    let mut report = Report::new("Differences between the maps");
+    for line in compare(&a, &b, invocation.arg("steps")?) {
+        report.line(line);
+    }
+
+    Ok(report)
+}
+
+/// Lists the differences of the directions and nodes of `a` and `b`,
+/// and where walking both from AAA first leads to different nodes.
+fn compare(a: &Input, b: &Input, limit: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let letters = |input: &Input| {
+        input
+            .directions
+            .iter()
+            .map(ToString::to_string)
+            .collect::<String>()
+    };
+    let (directions_a, directions_b) = (letters(a), letters(b));
+
+    match directions_a
+        .chars()
+        .zip(directions_b.chars())
+        .position(|(a, b)| a != b)
+    {
+        Some(i) => lines.push(format!(
+            "Directions differ first at {}: {} vs {}",
+            i + 1,
+            &directions_a[i..=i],
+            &directions_b[i..=i]
+        )),
+        None if directions_a.len() != directions_b.len() => lines.push(format!(
+            "Directions have {} vs {} steps",
+            directions_a.len(),
+            directions_b.len()
+        )),
+        None => lines.push("Directions are the same".to_owned()),
+    }
+
+    for (label, next) in &a.map {
+        match b.map.get(label) {
+            None => lines.push(format!("- {label} = ({}, {})", next.0, next.1)),
+            Some(other) if other != next => lines.push(format!(
+                "~ {label} = ({}, {}) -> ({}, {})",
+                next.0, next.1, other.0, other.1
+            )),
+            Some(_) => {}
+        }
+    }
+    for (label, next) in b.map.iter().filter(|(l, _)| !a.map.contains_key(l)) {
+        lines.push(format!("+ {label} = ({}, {})", next.0, next.1));
+    }
+
+    let walks = a.path(AAA).zip(b.path(AAA)).take(limit);
+    let mut divergence = None;
+    for (step, (next_a, next_b)) in (1..).zip(walks) {
+        match (next_a, next_b) {
+            (Ok((_, a)), Ok((_, b))) if a == b => {
+                if a.is_end_node() {
+                    divergence = Some(format!("Both walks reach {a} after {step} steps"));
+                    break;
+                }
+            }
+            (Ok((_, a)), Ok((_, b))) => {
+                divergence = Some(format!("Walks diverge at step {step}: {a} vs {b}"));
+                break;
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                divergence = Some(format!("Walks stop at step {step}: {e}"));
+                break;
+            }
+        }
+    }
+    lines.push(divergence.unwrap_or_else(|| format!("Walks agree for {limit} steps")));
+
+    lines
+}
+
+/// A byte-level parser for generated maps with millions of nodes, where
+/// going through lines, regexes and a `BTreeMap` dominates the runtime.
+/// Labels can have any length here and are interned to dense indices.
+#[cfg(feature = "large-input")]
+pub mod large {
+    use std::{collections::HashMap, fs::File, path::Path};
+
+    use anyhow::{anyhow, Context, Result};
+    use itertools::Itertools;
+    use memmap2::Mmap;
+
+    use super::{side, turn, Direction, Error};
+    use crate::{
+        cancel::CancelToken,
+        cycle::{self, Periodic},
+    };
+
+    /// Hands out consecutive ids for labels.
+    #[derive(Debug, Default)]
+    struct Interner<'a> {
+        ids: HashMap<&'a [u8], u32>,
+        names: Vec<&'a [u8]>,
+    }
+
+    impl<'a> Interner<'a> {
+        fn intern(&mut self, name: &'a [u8]) -> u32 {
+            *self.ids.entry(name).or_insert_with(|| {
+                self.names.push(name);
+                (self.names.len() - 1) as u32
+            })
+        }
+    }
+
+    /// Marks nodes that are referenced but never defined.
+    const UNDEFINED: [u32; 2] = [u32::MAX; 2];
+
+    #[derive(Debug)]
+    pub struct Graph<'a> {
+        directions: Vec<Direction>,
+        interner: Interner<'a>,
+        /// Left and right successor of each node, indexed by id.
+        next: Vec<[u32; 2]>,
+    }
+
+    fn split_once<'a>(s: &'a [u8], sep: &[u8]) -> Option<(&'a [u8], &'a [u8])> {
+        let pos = s.windows(sep.len()).position(|w| w == sep)?;
+        Some((&s[..pos], &s[pos + sep.len()..]))
+    }
+
+    impl<'a> Graph<'a> {
+        pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+            let mut lines = bytes.split(|&b| b == b'\n').map(<[u8]>::trim_ascii_end);
+
+            let directions = lines
+                .next()
+                .ok_or(Error::Empty)?
+                .iter()
+                .map(|b| turn(char::from(*b)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if directions.is_empty() {
+                return Err(Error::NoDirections);
+            }
+
+            let mut interner = Interner::default();
+            let mut next = vec![];
+
+            for line in lines.filter(|l| !l.is_empty()) {
+                let invalid = || Error::Node(String::from_utf8_lossy(line).into_owned());
+
+                let (from, rest) = split_once(line, b" = (").ok_or_else(invalid)?;
+                let (left, right) = rest
+                    .strip_suffix(b")")
+                    .and_then(|rest| split_once(rest, b", "))
+                    .ok_or_else(invalid)?;
+
+                let ids = [from, left, right].map(|l| interner.intern(l) as usize);
+                next.resize(interner.names.len(), UNDEFINED);
+
+                if next[ids[0]] != UNDEFINED {
+                    return Err(Error::Redefined(String::from_utf8_lossy(from).into_owned()));
+                }
+                next[ids[0]] = [ids[1] as u32, ids[2] as u32];
+            }
+
+            if let Some(id) = next.iter().position(|n| *n == UNDEFINED) {
+                return Err(Error::Undefined(
+                    String::from_utf8_lossy(interner.names[id]).into_owned(),
+                ));
+            }
+
+            Ok(Self {
+                directions,
+                interner,
+                next,
+            })
+        }
+
+        fn ends_with(&self, id: u32, suffix: u8) -> bool {
+            self.interner.names[id as usize].last() == Some(&suffix)
+        }
+
+        fn solve_one(&self, start: u32) -> Result<usize> {
+            let cancel = CancelToken::current();
+            let mut location = start;
+
+            for (steps, direction) in self.directions.iter().cycle().enumerate() {
+                if self.ends_with(location, b'Z') {
+                    return Ok(steps);
+                }
+                if steps % self.directions.len() == 0 {
+                    cancel.check()?;
+                }
+
+                location = self.next[location as usize][side(*direction)];
+            }
+
+            unreachable!("directions are cycled forever");
+        }
+
+        pub fn solve_part1(&self) -> Result<usize> {
+            let start = self
+                .interner
+                .ids
+                .get(&b"AAA"[..])
+                .ok_or_else(|| anyhow!("There is no node AAA"))?;
+
+            self.solve_one(*start)
+        }
+
+        /// Like the default parser, this finds the cycle of each ghost
+        /// instead of assuming they only meet after whole cycles.
+        pub fn solve_part2(&self) -> Result<usize> {
+            let ghosts = (0..self.next.len() as u32)
+                .filter(|id| self.ends_with(*id, b'A'))
+                .map(|id| {
+                    let cycle = cycle::find_cycle((id, 0), |&(id, index)| {
+                        let direction = self.directions[index];
+                        let next = self.next[id as usize][side(direction)];
+                        (next, (index + 1) % self.directions.len())
+                    });
+                    Periodic::from_cycle(&cycle, |(id, _)| self.ends_with(*id, b'Z'))
+                })
+                .collect::<Vec<_>>();
+
+            cycle::first_common_hit(&ghosts)
+                .ok_or_else(|| anyhow!("The ghosts never all reach end nodes at once"))
+        }
+    }
+
+    /// Solves both parts for the map in `path` without reading it into
+    /// memory first.
+    pub fn solve_file(path: &Path) -> Result<(usize, usize)> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        // SAFETY: The file must not be modified while it is mapped.
+        let map = unsafe { Mmap::map(&file)? };
+        let graph = Graph::parse(&map)?;
+
+        Ok((graph.solve_part1()?, graph.solve_part2()?))
+    }
+
+    /// A map with every three character label except those with an A or Z,
+    /// chained into one long path from `AAA` to `ZZZ`, for benchmarks.
+    #[must_use]
+    pub fn generate_map() -> String {
+        let alphabet = b"0123456789BCDEFGHIJKLMNOPQRSTUVWXY";
+        let labels = alphabet
+            .iter()
+            .cartesian_product(alphabet)
+            .cartesian_product(alphabet)
+            .map(|((&a, &b), &c)| [a, b, c].map(char::from).iter().collect::<String>())
+            .collect::<Vec<_>>();
+
+        let mut map = String::from("LR\n\nAAA = (000, 000)\nZZZ = (AAA, AAA)\n");
+        for (i, label) in labels.iter().enumerate() {
+            let next = labels.get(i + 1).map_or("ZZZ", String::as_str);
+            map.push_str(&format!("{label} = ({next}, {next})\n"));
+        }
+
+        map
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::str::FromStr;
+
+        use super::*;
+        use crate::{aoc2023::day8::Input, input::load_for_test};
+
+        #[test]
+        fn matches_default_parser() -> Result<()> {
+            let text = load_for_test(2023, 8);
+            let graph = Graph::parse(text.as_bytes())?;
+            let input = Input::from_str(&text)?;
+
+            assert_eq!(graph.solve_part1()?, input.solve_part1()?);
+            assert_eq!(graph.solve_part2()?, input.solve_part2()?);
+
+            Ok(())
+        }
+
+        #[test]
+        fn rejects_what_the_default_parser_rejects() {
+            for (map, error) in [
+                (
+                    "L\n\nAAA = (BBB, AAA)\n",
+                    Error::Undefined("BBB".to_owned()),
+                ),
+                (
+                    "L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)\n",
+                    Error::Redefined("AAA".to_owned()),
+                ),
+                ("LUR\n", Error::Direction('U')),
+                ("\n\nAAA = (AAA, AAA)\n", Error::NoDirections),
+            ] {
+                assert_eq!(Graph::parse(map.as_bytes()).err(), Some(error.clone()));
+                assert_eq!(Input::from_str(map).err(), Some(error));
+            }
+        }
+
+        #[test]
+        fn matches_default_parser_on_long_paths() -> Result<()> {
+            let map = generate_map();
+            let graph = Graph::parse(map.as_bytes())?;
+
+            assert_eq!(graph.solve_part1()?, Input::from_str(&map)?.solve_part1()?);
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, time::Duration};
+
+    use super::*;
+    use crate::cancel;
+
+    crate::example_test!(day8, part1 = 6);
+    crate::example_test!(day8_part2, part2 = 6);
+
+    #[test]
+    fn endless_walks_stop_when_cancelled() -> Result<()> {
+        let input = Input::from_str("L\n\nAAA = (AAA, AAA)\n")?;
+        let (tx, rx) = mpsc::channel();
+
+        let result = cancel::run_with_timeout(Duration::from_millis(10), move || {
+            let steps = input.solve_part1();
+            tx.send(steps.is_err())?;
+            steps
+        });
+
+        assert!(result.is_err());
+        assert!(rx.recv_timeout(Duration::from_secs(10))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_finds_divergence() -> Result<()> {
+        let a = Input::from_str(include_str!("day8.example"))?;
+        let b = Input::from_str("LRR\n\nAAA = (BBB, BBB)\nBBB = (CCC, CCC)\nCCC = (AAA, AAA)\n")?;
+
+        assert_eq!(
+            compare(&a, &b, 100),
+            [
+                "Directions differ first at 2: L vs R",
+                "~ BBB = (AAA, ZZZ) -> (CCC, CCC)",
+                "- ZZZ = (ZZZ, ZZZ)",
+                "+ CCC = (AAA, AAA)",
+                "Walks diverge at step 2: AAA vs CCC",
+            ]
+        );
+        assert_eq!(
+            compare(&a, &a, 100).last().map(String::as_str),
+            Some("Both walks reach ZZZ after 6 steps")
+        );
+        assert_eq!(
+            compare(&a, &a, 3).last().map(String::as_str),
+            Some("Walks agree for 3 steps")
+        );
+        let c = Input::from_str("L\n\nBBB = (BBB, BBB)\n")?;
+        assert_eq!(
+            compare(&c, &c, 100).last().map(String::as_str),
+            Some("Walks stop at step 1: Invalid label: AAA")
+        );
+        assert_eq!(
+            Input::from_str("L\n\nAAA = (BBB, BBB)\n").err(),
+            Some(Error::Undefined("BBB".to_owned()))
+        );
+        assert_eq!(
+            Input::from_str("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)\n").err(),
+            Some(Error::Redefined("AAA".to_owned()))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn crt_handles_what_lcm_gets_wrong() -> Result<()> {
+        let mut lcm_wrong = 0;
+
+        for seed in 0..20 {
+            let map = Day8::generate(&mut crate::gen::rng(seed), 100)?;
+            let input = Input::from_str(&map)?;
+
+            assert_eq!(Some(input.solve_part2()?), input.walk_all(100_000)?);
+            if input.solve_part2_lcm()? != input.solve_part2()? {
+                lcm_wrong += 1;
+            }
+        }
+
+        assert!(lcm_wrong > 10, "{lcm_wrong}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,398 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use itertools::Itertools;
+use rand::Rng as _;
+use thiserror::Error;
+
+use crate::{
+    error::DayError,
+    gen::Rng,
+    lint::{self, Diagnostic},
+    parse,
+    solution::{Answer, ExplainQuery, Report, Solution},
+};
+
+/// The ways a report of sequences can be broken.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Can't parse line {line}: {error}")]
+    Values { line: usize, error: parse::Error },
+    #[error("Line {0} has no values")]
+    NoValues(usize),
+}
+
+impl DayError for Error {}
+
+/// The sequences of values of the input, one per line.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Sequences {
+    sequences: Vec<Vec<i64>>,
+}
+
+impl Sequences {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// The sequence on `line`, counting from 1.
+    #[must_use]
+    pub fn line(&self, line: usize) -> Option<&[i64]> {
+        self.sequences.get(line.checked_sub(1)?).map(Vec::as_slice)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[i64]> {
+        self.sequences.iter().map(Vec::as_slice)
+    }
+}
+
+impl FromStr for Sequences {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sequences = s
+            .lines()
+            .zip(1..)
+            .map(|(l, line)| {
+                let values =
+                    parse::ints::<i64>(l).map_err(|error| Error::Values { line, error })?;
+
+                if values.is_empty() {
+                    return Err(Error::NoValues(line));
+                }
+                Ok(values)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { sequences })
+    }
+}
+
+fn extrapolation_vectors(input: &[i64]) -> Vec<Vec<i64>> {
+    let mut derivations: Vec<Vec<i64>> = vec![input.to_owned()];
+
+    loop {
+        let next = derivations
+            .last()
+            .unwrap()
+            .iter()
+            .copied()
+            .tuple_windows()
+            .map(|(a, b)| b - a)
+            .collect::<Vec<_>>();
+
+        if next.iter().copied().all(|v| v == 0) {
+            break;
+        }
+
+        derivations.push(next);
+    }
+
+    derivations
+}
+
+fn extrapolate_fwd(input: &[i64]) -> i64 {
+    let derivations = extrapolation_vectors(input);
+
+    derivations
+        .into_iter()
+        .map(|v| *v.last().unwrap())
+        .rev()
+        .sum::<i64>()
+}
+
+fn extrapolate_bwd(input: &[i64]) -> i64 {
+    let derivations = extrapolation_vectors(input);
+
+    derivations
+        .into_iter()
+        .map(|v| *v.first().unwrap())
+        .rev()
+        .fold(0, |last, val| val - last)
+}
+
+/// The difference triangle of `input` with the extrapolated values on
+/// both ends of each row, down to the row of zeros.
+fn triangle(input: &[i64]) -> Vec<(i64, Vec<i64>, i64)> {
+    let mut rows = extrapolation_vectors(input);
+    rows.push(vec![
+        0;
+        rows.last().map_or(0, |r| r.len().saturating_sub(1))
+    ]);
+
+    let mut next = 0;
+    let mut previous = 0;
+    let mut triangle = rows
+        .into_iter()
+        .rev()
+        .map(|row| {
+            next += row.last().copied().unwrap_or_default();
+            previous = row.first().copied().unwrap_or_default() - previous;
+            (previous, row, next)
+        })
+        .collect::<Vec<_>>();
+
+    triangle.reverse();
+    triangle
+}
+
+/// What the values of a line look like, going by their differences.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Fit {
+    /// The values of a polynomial of this degree: the differences become
+    /// all zeros after `degree + 1` levels.
+    Polynomial(usize),
+    /// The differences don't become all zeros within the allowed levels,
+    /// or only because they ran out of values.
+    Diverges,
+}
+
+/// Classifies `sequence`, allowing up to `max_levels` levels of
+/// differences before giving up.
+fn classify(sequence: &[i64], max_levels: usize) -> Fit {
+    let rows = extrapolation_vectors(sequence);
+    let degree = rows.len() - 1;
+
+    // With one value left the zeros below it are made up.
+    if rows.last().is_some_and(|r| r.len() < 2) || degree >= max_levels {
+        Fit::Diverges
+    } else {
+        Fit::Polynomial(degree)
+    }
+}
+
+impl Display for Fit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fit::Polynomial(0) => f.write_str("constant"),
+            Fit::Polynomial(1) => f.write_str("arithmetic"),
+            Fit::Polynomial(degree) => write!(f, "polynomial of degree {degree}"),
+            Fit::Diverges => f.write_str("does not converge"),
+        }
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed = Sequences;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Sequences, Error> {
+        Sequences::from_str(input)
+    }
+
+    /// Besides the format, the values have to come from a polynomial,
+    /// so garbage lines are caught before they give garbage answers.
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        lint::each_line(input, |c| {
+            let start = c.clone();
+            let mut values = vec![];
+
+            loop {
+                values.push(c.integer()?);
+                if c.is_at_end() {
+                    break;
+                }
+                c.literal(" ")?;
+            }
+
+            match classify(&values, usize::MAX) {
+                Fit::Diverges => {
+                    Err(start.error("The differences of these values never become all zeros"))
+                }
+                Fit::Polynomial(_) => Ok(()),
+            }
+        })
+    }
+
+    fn part1(input: &Sequences) -> Result<Answer> {
+        Ok(input.iter().map(extrapolate_fwd).sum::<i64>().into())
+    }
+
+    fn part2(input: &Sequences) -> Result<Option<Answer>> {
+        Ok(Some(input.iter().map(extrapolate_bwd).sum::<i64>().into()))
+    }
+
+    /// `size` lines of 21 values of random polynomials, which always end
+    /// in a row of zeros.
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok((0..size)
+            .map(|_| {
+                let coefficients = (0..=rng.gen_range(0..6))
+                    .map(|_| rng.gen_range(-5i64..=5))
+                    .collect::<Vec<_>>();
+
+                (0..21i64)
+                    .map(|x| {
+                        coefficients
+                            .iter()
+                            .rev()
+                            .fold(0, |value, c| value * x + c)
+                            .to_string()
+                    })
+                    .join(" ")
+                    + "\n"
+            })
+            .collect())
+    }
+
+    /// Draws the difference triangle of input line `line` (default 1)
+    /// with the extrapolated values highlighted. `line=all` classifies
+    /// every line instead, allowing `levels` levels of differences if
+    /// given.
+    fn explain(input: &Sequences, query: &ExplainQuery) -> Result<Report> {
+        if query.get("line") == Some("all") {
+            let levels = query.get_or("levels", usize::MAX)?;
+            let mut report = Report::new("Lines by fit");
+
+            for (i, sequence) in input.iter().enumerate() {
+                report.line(format!("{:>4}: {}", i + 1, classify(sequence, levels)));
+            }
+
+            return Ok(report);
+        }
+
+        let line = query.get_or("line", 1usize)?;
+        let sequence = input
+            .line(line)
+            .ok_or_else(|| anyhow!("There is no line {line}"))?;
+
+        let triangle = triangle(sequence);
+        let width = triangle
+            .iter()
+            .flat_map(|(previous, row, next)| row.iter().chain([previous, next]))
+            .map(|v| v.to_string().len())
+            .max()
+            .unwrap_or_default()
+            + 2;
+
+        let mut report = Report::new(format!("Difference triangle of line {line}"));
+        for (depth, (previous, row, next)) in triangle.iter().enumerate() {
+            let values = row
+                .iter()
+                .map(|v| format!("{v:>width$}"))
+                .collect::<String>();
+
+            report.line(format!(
+                "{:indent$}{}{values}{}",
+                "",
+                format!("{previous:>width$}").green().bold(),
+                format!("{next:>width$}").green().bold(),
+                indent = depth * width / 2
+            ));
+        }
+
+        // A single value has no differences left, so the zeros at the
+        // bottom are made up rather than computed.
+        if triangle.len() > 2 && triangle[triangle.len() - 2].1.len() == 1 {
+            report.line("The differences never became all zeros, so the extrapolation is a guess.");
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    crate::example_test!(day9, part1 = 114, part2 = 2);
+
+    #[test]
+    fn parse_errors_name_the_line() -> Result<()> {
+        let sequences = Sequences::from_str(include_str!("day9.example"))?;
+        assert_eq!(sequences.len(), 3);
+        assert_eq!(sequences.line(3), Some(&[10, 13, 16, 21, 30, 45][..]));
+        assert_eq!(sequences.line(0), None);
+        assert_eq!(sequences.iter().map(<[i64]>::len).sum::<usize>(), 18);
+
+        let error = Sequences::from_str("1 2\n3 x 5\n").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Can't parse line 2: Can't parse number x: invalid digit found in string"
+        );
+        assert_eq!(Sequences::from_str("1 2\n\n"), Err(Error::NoValues(2)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn example_works() -> Result<()> {
+        assert_eq!(extrapolate_fwd(&[0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(extrapolate_fwd(&[1, 3, 6, 10, 15, 21]), 28);
+
+        Ok(())
+    }
+
+    #[test]
+    fn example2_works() -> Result<()> {
+        assert_eq!(extrapolate_bwd(&[10, 13, 16, 21, 30, 45]), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn classifies_lines() -> Result<()> {
+        assert_eq!(classify(&[7, 7, 7], usize::MAX), Fit::Polynomial(0));
+        assert_eq!(classify(&[0, 3, 6, 9], usize::MAX), Fit::Polynomial(1));
+        assert_eq!(
+            classify(&[1, 3, 6, 10, 15, 21], usize::MAX),
+            Fit::Polynomial(2)
+        );
+        assert_eq!(classify(&[1, 3, 6, 10, 15, 21], 2), Fit::Diverges);
+        assert_eq!(classify(&[1, 5, 2, 8], usize::MAX), Fit::Diverges);
+        assert_eq!(classify(&[4], usize::MAX), Fit::Diverges);
+
+        assert_eq!(Day9::lint("0 3 6 9\n1 3 6 10 15 21\n"), []);
+        let diagnostics = Day9::lint("0 3 6 9\n1 5 2 8\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, 2);
+
+        let report = Day9::explain(
+            &Sequences::from_str("0 3 6 9\n1 5 2 8\n")?,
+            &ExplainQuery::from_str("line=all")?,
+        )?;
+        assert_eq!(
+            report.lines,
+            ["   1: arithmetic", "   2: does not converge"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_draws_triangle() -> Result<()> {
+        colored::control::set_override(false);
+
+        let report = Day9::explain(
+            &Sequences::from_str("0 3 6\n10 13 16 21 30 45\n")?,
+            &ExplainQuery::from_str("line=2")?,
+        )?;
+
+        assert_eq!(report.title, "Difference triangle of line 2");
+        assert_eq!(
+            report.lines,
+            [
+                "   5  10  13  16  21  30  45  68",
+                "     5   3   3   5   9  15  23",
+                "      -2   0   2   4   6   8",
+                "         2   2   2   2   2",
+                "           0   0   0   0",
+            ]
+        );
+
+        assert!(Day9::explain(&Sequences::default(), &ExplainQuery::default()).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! Solutions for Advent of Code 2023. Each day is behind a feature of
+//! the same name, so working on one day doesn't mean compiling all.
+
+use crate::{registry::Puzzle, tool::Tool};
+
+#[cfg(feature = "day1")]
+pub mod day1;
+#[cfg(feature = "day10")]
+pub mod day10;
+#[cfg(feature = "day11")]
+pub mod day11;
+#[cfg(feature = "day12")]
+pub mod day12;
+#[cfg(feature = "day13")]
+pub mod day13;
+#[cfg(feature = "day2")]
+pub mod day2;
+#[cfg(feature = "day3")]
+pub mod day3;
+#[cfg(feature = "day4")]
+pub mod day4;
+#[cfg(feature = "day5")]
+pub mod day5;
+#[cfg(feature = "day6")]
+pub mod day6;
+#[cfg(feature = "day7")]
+pub mod day7;
+#[cfg(feature = "day8")]
+pub mod day8;
+#[cfg(feature = "day9")]
+pub mod day9;
+
+pub const YEAR: u16 = 2023;
+
+#[must_use]
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        #[cfg(feature = "day1")]
+        Puzzle::new::<day1::Day1>(YEAR, 1),
+        #[cfg(feature = "day2")]
+        Puzzle::new::<day2::Day2>(YEAR, 2),
+        #[cfg(feature = "day3")]
+        Puzzle::new::<day3::Day3>(YEAR, 3),
+        #[cfg(feature = "day4")]
+        Puzzle::new::<day4::Day4>(YEAR, 4),
+        #[cfg(feature = "day5")]
+        Puzzle::new::<day5::Day5>(YEAR, 5),
+        #[cfg(feature = "day6")]
+        Puzzle::new::<day6::Day6>(YEAR, 6),
+        #[cfg(feature = "day7")]
+        Puzzle::new::<day7::Day7>(YEAR, 7),
+        #[cfg(feature = "day8")]
+        Puzzle::new::<day8::Day8>(YEAR, 8),
+        #[cfg(feature = "day9")]
+        Puzzle::new::<day9::Day9>(YEAR, 9),
+        #[cfg(feature = "day10")]
+        Puzzle::new::<day10::Day10>(YEAR, 10),
+        #[cfg(feature = "day11")]
+        Puzzle::new::<day11::Day11>(YEAR, 11),
+        #[cfg(feature = "day12")]
+        Puzzle::new::<day12::Day12>(YEAR, 12),
+        #[cfg(feature = "day13")]
+        Puzzle::new::<day13::Day13>(YEAR, 13),
+    ]
+}
+
+#[must_use]
+pub fn tools() -> Vec<Tool> {
+    vec![
+        #[cfg(feature = "day3")]
+        day3::PARTS_GRAPH,
+        #[cfg(feature = "day3")]
+        day3::STREAM_SCHEMATIC,
+        #[cfg(feature = "day4")]
+        day4::CONVERT_CARDS,
+        #[cfg(feature = "day5")]
+        day5::MAP_SEED,
+        #[cfg(feature = "day6")]
+        day6::RACE_RECORDS,
+        #[cfg(feature = "day7")]
+        day7::CLASSIFY_HAND,
+        #[cfg(feature = "day8")]
+        day8::WALK,
+        #[cfg(feature = "day8")]
+        day8::COMPARE_MAPS,
+        #[cfg(feature = "day11")]
+        day11::LOCATE,
+        #[cfg(feature = "day12")]
+        day12::STREAM,
+        #[cfg(feature = "day13")]
+        day13::AXIS_STABILITY,
+    ]
+}
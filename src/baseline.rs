@@ -0,0 +1,165 @@
+//! Named snapshots of benchmark results per part, to check that changing
+//! shared code didn't slow any part down. Stored as TOML next to the
+//! history:
+//!
+//! ```toml
+//! [[main]]
+//! year = 2023
+//! day = 7
+//! part = 1
+//! median_ns = 1234567
+//! ```
+
+use std::{collections::BTreeMap, fs, io, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// The median time of one part.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct PartTime {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub median_ns: u64,
+}
+
+impl PartTime {
+    #[must_use]
+    pub fn new(year: u16, day: u8, part: u8, median: Duration) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            median_ns: u64::try_from(median.as_nanos()).unwrap_or(u64::MAX),
+        }
+    }
+
+    fn key(&self) -> (u16, u8, u8) {
+        (self.year, self.day, self.part)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baselines(BTreeMap<String, Vec<PartTime>>);
+
+impl Baselines {
+    /// Loads the baselines from `path`. A missing file has none.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => {
+                toml::from_str(&s).with_context(|| format!("Failed to parse {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&[PartTime]> {
+        self.0.get(name).map(Vec::as_slice)
+    }
+
+    /// Stores `times` in baseline `name`. Parts that weren't measured
+    /// this time keep their earlier times.
+    pub fn update(&mut self, name: &str, times: &[PartTime]) {
+        let baseline = self.0.entry(name.to_owned()).or_default();
+
+        for time in times {
+            match baseline.iter_mut().find(|t| t.key() == time.key()) {
+                Some(old) => *old = *time,
+                None => baseline.push(*time),
+            }
+        }
+
+        baseline.sort_by_key(PartTime::key);
+    }
+}
+
+/// A part that got slower than in the baseline.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Slowdown {
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub time: Duration,
+    /// How much slower, in percent of the baseline.
+    pub percent: u64,
+}
+
+/// The parts of `times` that are more than `threshold` percent slower
+/// than in `baseline`. Parts missing from the baseline are new and
+/// can't be slower.
+#[must_use]
+pub fn compare(baseline: &[PartTime], times: &[PartTime], threshold: u64) -> Vec<Slowdown> {
+    times
+        .iter()
+        .filter_map(|time| {
+            let base = baseline.iter().find(|b| b.key() == time.key())?;
+            let percent = time
+                .median_ns
+                .saturating_sub(base.median_ns)
+                .saturating_mul(100)
+                / base.median_ns.max(1);
+
+            (percent > threshold).then_some(Slowdown {
+                day: time.day,
+                part: time.part,
+                baseline: Duration::from_nanos(base.median_ns),
+                time: Duration::from_nanos(time.median_ns),
+                percent,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(day: u8, part: u8, millis: u64) -> PartTime {
+        PartTime::new(2023, day, part, Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn finds_slower_parts() -> Result<()> {
+        let mut baselines = Baselines::default();
+        baselines.update("main", &[time(1, 1, 100), time(1, 2, 100)]);
+        baselines.update("main", &[time(2, 1, 10), time(1, 2, 50)]);
+
+        assert_eq!(
+            baselines.get("main"),
+            Some(&[time(1, 1, 100), time(1, 2, 50), time(2, 1, 10)][..])
+        );
+        assert_eq!(baselines.get("other"), None);
+        assert_eq!(
+            toml::from_str::<Baselines>(&toml::to_string(&baselines)?)?,
+            baselines
+        );
+
+        let slowdowns = compare(
+            baselines.get("main").unwrap_or_default(),
+            &[time(1, 1, 109), time(1, 2, 60), time(3, 1, 1000)],
+            10,
+        );
+
+        assert_eq!(
+            slowdowns,
+            [Slowdown {
+                day: 1,
+                part: 2,
+                baseline: Duration::from_millis(50),
+                time: Duration::from_millis(60),
+                percent: 20,
+            }]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+//! Benchmarking support for the runner.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{ensure, Result};
+use serde_json::{json, Value};
+
+/// Summary of a series of timing samples after outlier rejection.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    /// Samples that went into the statistics.
+    pub samples: usize,
+    /// Samples that were discarded as outliers.
+    pub rejected: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+    /// Median absolute deviation from the median.
+    pub mad: Duration,
+    /// Standard deviation from the mean.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// The standard deviation in percent of the mean.
+    #[must_use]
+    pub fn variation_percent(&self) -> u128 {
+        self.stddev.as_nanos() * 100 / self.mean.as_nanos().max(1)
+    }
+
+    /// Whether the runs differ so much that the numbers are unreliable,
+    /// e.g. because threads got scheduled differently.
+    #[must_use]
+    pub fn is_noisy(&self) -> bool {
+        self.variation_percent() > NOISY_PERCENT
+    }
+}
+
+/// Standard deviations above this percentage of the mean are noisy.
+pub const NOISY_PERCENT: u128 = 10;
+
+/// Runs `f` `warmup` times without measuring and then `iterations`
+/// times while recording how long each run took.
+pub fn measure(
+    mut f: impl FnMut() -> Result<()>,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Vec<Duration>> {
+    ensure!(iterations > 0, "Need at least one iteration");
+
+    for _ in 0..warmup {
+        f()?;
+    }
+
+    (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            f()?;
+            Ok(start.elapsed())
+        })
+        .collect()
+}
+
+/// Summarizes timing samples. Samples outside of Tukey's fences
+/// (1.5 times the interquartile range beyond the quartiles) are
+/// rejected as outliers.
+///
+/// Panics if there are no samples.
+#[must_use]
+pub fn summarize(samples: &[Duration]) -> Stats {
+    assert!(!samples.is_empty());
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+
+    let q1 = sorted[sorted.len() / 4];
+    let q3 = sorted[sorted.len() * 3 / 4];
+    let fence = (q3 - q1) * 3 / 2;
+    let (low, high) = (q1.saturating_sub(fence), q3 + fence);
+
+    let kept = sorted
+        .iter()
+        .copied()
+        .filter(|s| (low..=high).contains(s))
+        .collect::<Vec<_>>();
+
+    // The quartiles themselves are always within the fences.
+    debug_assert!(!kept.is_empty());
+
+    let total = kept.iter().sum::<Duration>();
+    let median = kept[kept.len() / 2];
+    let mean = total / u32::try_from(kept.len()).unwrap_or(u32::MAX);
+
+    let mut deviations = kept.iter().map(|s| s.abs_diff(median)).collect::<Vec<_>>();
+    deviations.sort_unstable();
+
+    let variance = kept
+        .iter()
+        .map(|s| s.abs_diff(mean).as_nanos().pow(2))
+        .sum::<u128>()
+        / kept.len() as u128;
+
+    Stats {
+        samples: kept.len(),
+        rejected: sorted.len() - kept.len(),
+        min: kept[0],
+        median,
+        mean,
+        max: kept[kept.len() - 1],
+        mad: deviations[deviations.len() / 2],
+        stddev: Duration::from_nanos(u64::try_from(variance.isqrt()).unwrap_or(u64::MAX)),
+    }
+}
+
+/// The statistics as JSON object, with times in nanoseconds.
+#[must_use]
+pub fn stats_json(s: &Stats) -> Value {
+    json!({
+        "samples": s.samples,
+        "rejected": s.rejected,
+        "min_ns": s.min.as_nanos(),
+        "median_ns": s.median.as_nanos(),
+        "mean_ns": s.mean.as_nanos(),
+        "max_ns": s.max.as_nanos(),
+        "mad_ns": s.mad.as_nanos(),
+        "stddev_ns": s.stddev.as_nanos(),
+        "noisy": s.is_noisy(),
+    })
+}
+
+/// The statistics of each day as JSON, with times in nanoseconds.
+pub fn to_json(days: &[(u8, Stats)]) -> Result<String> {
+    let days = days
+        .iter()
+        .map(|(day, s)| {
+            let mut json = stats_json(s);
+            json["day"] = (*day).into();
+            json
+        })
+        .collect::<Vec<_>>();
+
+    Ok(serde_json::to_string_pretty(&days)? + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(v: &[u64]) -> Vec<Duration> {
+        v.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn summarize_without_outliers() {
+        let stats = summarize(&ms(&[3, 1, 2]));
+
+        assert_eq!(
+            stats,
+            Stats {
+                samples: 3,
+                rejected: 0,
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(2),
+                mean: Duration::from_millis(2),
+                max: Duration::from_millis(3),
+                mad: Duration::from_millis(1),
+                stddev: Duration::from_nanos(816_496),
+            }
+        );
+        assert!(stats.is_noisy());
+    }
+
+    #[test]
+    fn summarize_rejects_outliers() {
+        let stats = summarize(&ms(&[10, 11, 10, 12, 11, 10, 250, 11]));
+
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.rejected, 1);
+        assert_eq!(stats.max, Duration::from_millis(12));
+        assert!(!stats.is_noisy());
+    }
+
+    #[test]
+    fn stats_as_json() -> Result<()> {
+        let json = serde_json::from_str::<serde_json::Value>(&to_json(&[(
+            3,
+            summarize(&ms(&[3, 1, 2])),
+        )])?)?;
+
+        assert_eq!(json[0]["day"], 3);
+        assert_eq!(json[0]["median_ns"], 2_000_000);
+        assert_eq!(json[0]["noisy"], true);
+
+        Ok(())
+    }
+
+    #[test]
+    fn measure_runs_warmup_and_iterations() -> Result<()> {
+        let mut calls = 0;
+
+        let samples = measure(
+            || {
+                calls += 1;
+                Ok(())
+            },
+            2,
+            5,
+        )?;
+
+        assert_eq!(samples.len(), 5);
+        assert_eq!(calls, 7);
+        assert!(measure(|| Ok(()), 0, 0).is_err());
+
+        Ok(())
+    }
+}
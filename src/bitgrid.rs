@@ -0,0 +1,245 @@
+//! Grids of booleans with a bit per cell, for inputs that only tell
+//! whether something is at a position. Whole rows are compared and
+//! counted a word at a time.
+
+use std::{
+    fmt::{self, Display},
+    ops::Index,
+};
+
+use anyhow::Result;
+
+use crate::{geometry::Point2, grid::Grid};
+
+/// Each row starts a new `u64` word, with the first column in the lowest
+/// bit. Bits beyond the last column are always clear.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    rows: usize,
+    cols: usize,
+}
+
+impl BitGrid {
+    /// A grid without any set cells.
+    #[must_use]
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            words: vec![0; rows * cols.div_ceil(64)],
+            rows,
+            cols,
+        }
+    }
+
+    /// Parses a grid with a line per row, setting the cells for which
+    /// `cell` says so.
+    pub fn parse(s: &str, cell: impl FnMut(char) -> Result<bool>) -> Result<Self> {
+        Ok(Self::from(&Grid::parse(s, cell)?))
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point2) -> bool {
+        p.row < self.rows && p.col < self.cols
+    }
+
+    fn stride(&self) -> usize {
+        self.cols.div_ceil(64)
+    }
+
+    /// The word and bit of the cell at `p`.
+    fn bit(&self, p: Point2) -> (usize, u64) {
+        (p.row * self.stride() + p.col / 64, 1 << (p.col % 64))
+    }
+
+    /// Whether the cell at `p` is set. Cells outside of the grid aren't.
+    #[must_use]
+    pub fn get(&self, p: Point2) -> bool {
+        let (word, bit) = self.bit(p);
+        self.contains(p) && self.words[word] & bit != 0
+    }
+
+    /// Panics outside of the grid.
+    pub fn set(&mut self, p: Point2, value: bool) {
+        assert!(self.contains(p), "{p} is outside of the grid");
+
+        let (word, bit) = self.bit(p);
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    /// The words of a row. Equal rows have equal words.
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.stride()..(row + 1) * self.stride()]
+    }
+
+    /// The number of set cells in a row.
+    #[must_use]
+    pub fn row_count(&self, row: usize) -> usize {
+        self.row(row).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The number of set cells in a column. Panics outside of the grid.
+    #[must_use]
+    pub fn col_count(&self, col: usize) -> usize {
+        assert!(col < self.cols, "column {col} is outside of the grid");
+
+        (0..self.rows)
+            .filter(|&row| self.get(Point2::new(row, col)))
+            .count()
+    }
+
+    #[must_use]
+    pub fn row_is_empty(&self, row: usize) -> bool {
+        self.row(row).iter().all(|w| *w == 0)
+    }
+
+    /// Panics outside of the grid.
+    #[must_use]
+    pub fn col_is_empty(&self, col: usize) -> bool {
+        assert!(col < self.cols, "column {col} is outside of the grid");
+
+        (0..self.rows).all(|row| {
+            let (word, bit) = self.bit(Point2::new(row, col));
+            self.words[word] & bit == 0
+        })
+    }
+
+    /// The number of set cells.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The positions of the set cells, row by row.
+    pub fn ones(&self) -> impl Iterator<Item = Point2> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (row, first_col) = (i / self.stride(), i % self.stride() * 64);
+            let mut rest = word;
+
+            std::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    Point2::new(row, first_col + bit)
+                })
+            })
+        })
+    }
+
+    /// The grid mirrored along its diagonal, so rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Self::new(self.cols, self.rows);
+        for p in self.ones() {
+            transposed.set(Point2::new(p.col, p.row), true);
+        }
+        transposed
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = Self::new(grid.rows(), grid.cols());
+        for (p, _) in grid.enumerate().filter(|(_, set)| **set) {
+            bits.set(p, true);
+        }
+        bits
+    }
+}
+
+/// Cells can't be borrowed as bits, so this hands out constants.
+impl Index<Point2> for BitGrid {
+    type Output = bool;
+
+    /// Panics outside of the grid.
+    fn index(&self, p: Point2) -> &bool {
+        assert!(self.contains(p), "{p} is outside of the grid");
+
+        if self.get(p) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+/// Set cells as `#` and others as `.`, like most puzzle inputs.
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let set = self.get(Point2::new(row, col));
+                write!(f, "{}", if set { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::bail;
+
+    use super::*;
+
+    #[test]
+    fn bit_grids_work() -> Result<()> {
+        let text = "#..\n...\n.##\n";
+        let grid = BitGrid::parse(text, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => bail!("Invalid cell {c}"),
+        })?;
+
+        assert_eq!(grid.to_string(), text);
+        assert!(grid[Point2::new(2, 1)]);
+        assert!(!grid.get(Point2::new(3, 0)));
+        assert_eq!(
+            (grid.row_count(2), grid.col_count(0), grid.count()),
+            (2, 1, 3)
+        );
+        assert!(grid.row_is_empty(1) && !grid.row_is_empty(2));
+        assert!(!grid.col_is_empty(1));
+        assert_eq!(
+            grid.ones().collect::<Vec<_>>(),
+            [Point2::new(0, 0), Point2::new(2, 1), Point2::new(2, 2)]
+        );
+        assert_eq!(grid.transpose().to_string(), "#..\n..#\n..#\n");
+        assert_eq!(grid.transpose().transpose(), grid);
+
+        // Rows longer than a word.
+        let mut wide = BitGrid::new(2, 130);
+        wide.set(Point2::new(1, 129), true);
+        wide.set(Point2::new(1, 64), true);
+        assert_eq!(wide.row(1).len(), 3);
+        assert!(wide.col_is_empty(128) && !wide.col_is_empty(129));
+        assert_eq!(wide.ones().last(), Some(Point2::new(1, 129)));
+        wide.set(Point2::new(1, 129), false);
+        assert_eq!(wide.count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside of the grid")]
+    fn columns_outside_of_the_grid_panic() {
+        // Column 3 would read the unused bits at the end of each row.
+        let _ = BitGrid::new(2, 3).col_is_empty(3);
+    }
+}
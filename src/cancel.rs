@@ -0,0 +1,131 @@
+//! Running solutions with a time limit.
+//!
+//! Threads can't be killed, so a solution that runs out of time is only
+//! asked to stop. Long running solvers poll their [`CancelToken`] and
+//! give up once it is cancelled; everything else simply finishes in
+//! the background while its result is ignored.
+
+use std::{
+    cell::RefCell,
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::{error::Cancelled, output::Message};
+
+thread_local! {
+    static CURRENT: RefCell<CancelToken> = RefCell::default();
+}
+
+/// Tells a solver that its result is no longer needed.
+#[derive(Debug, Default, Clone)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// The token of the solution running on this thread. Outside of
+    /// [`run_with_timeout`] this token is never cancelled.
+    ///
+    /// Worker threads (e.g. rayon's) don't inherit it, so grab it before
+    /// fanning out.
+    #[must_use]
+    pub fn current() -> Self {
+        CURRENT.with(|t| t.borrow().clone())
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(Cancelled.into());
+        }
+
+        Ok(())
+    }
+
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Runs `f` on a worker thread and gives up waiting for it after `limit`.
+/// A solver that doesn't poll its token keeps the thread busy until it is
+/// done, so solvers that can run without end, like walks that never reach
+/// their goal, have to poll.
+pub fn run_with_timeout<T, F>(limit: Duration, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let token = CancelToken::default();
+    let worker_token = token.clone();
+    let (tx, rx) = mpsc::channel();
+
+    thread::Builder::new()
+        .name("solver".to_owned())
+        .spawn(move || {
+            CURRENT.with(|t| *t.borrow_mut() = worker_token);
+
+            // The receiver is gone if we took too long. Nobody cares
+            // about the result anymore then.
+            let _ = tx.send(panic::catch_unwind(panic::AssertUnwindSafe(f)));
+        })?;
+
+    match rx.recv_timeout(limit) {
+        Ok(Ok(result)) => result,
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Err(anyhow!("{}", Message::TimedOut(limit)))
+        }
+        Err(RecvTimeoutError::Disconnected) => bail!("Solver thread vanished"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finishes_in_time() {
+        assert_eq!(
+            run_with_timeout(Duration::from_secs(10), || Ok(42)).unwrap(),
+            42
+        );
+    }
+
+    #[test]
+    fn runaway_solvers_get_cancelled() {
+        let (tx, rx) = mpsc::channel();
+
+        let result = run_with_timeout(Duration::from_millis(10), move || {
+            let token = CancelToken::current();
+
+            while !token.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            tx.send(())?;
+            token.check()
+        });
+
+        assert!(result.is_err());
+        rx.recv_timeout(Duration::from_secs(10))
+            .expect("solver should notice the cancellation");
+    }
+
+    #[test]
+    fn tokens_outside_of_solvers_never_cancel() {
+        assert!(!CancelToken::current().is_cancelled());
+    }
+}
@@ -0,0 +1,54 @@
+//! Horizontal bar charts for the terminal, for explanations that are
+//! easier to see than to read.
+
+/// Partially filled cells, in eighths.
+const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// One bar per row, scaled so the largest value is `width` cells long.
+/// Labels are aligned on the left, values follow the bars.
+#[must_use]
+pub fn bars(rows: &[(String, u128)], width: usize) -> Vec<String> {
+    let largest = rows
+        .iter()
+        .map(|(_, v)| *v)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let label_width = rows.iter().map(|(l, _)| l.chars().count()).max();
+    let width = u128::try_from(width).unwrap_or(u128::MAX);
+
+    rows.iter()
+        .map(|(label, value)| {
+            let eighths = value.saturating_mul(width).saturating_mul(8) / largest;
+            let full = usize::try_from(eighths / 8).unwrap_or_default();
+            let partial = EIGHTHS[usize::try_from(eighths % 8).unwrap_or_default()];
+
+            let bar = "█".repeat(full) + partial.to_string().trim_end();
+            format!(
+                "{label:>label_width$} │{bar} {value}",
+                label_width = label_width.unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scales_to_the_largest_value() {
+        let rows = [
+            ("a".to_owned(), 4),
+            ("bb".to_owned(), 1),
+            ("c".to_owned(), 0),
+        ];
+
+        assert_eq!(bars(&rows, 4), [" a │████ 4", "bb │█ 1", " c │ 0"]);
+        assert_eq!(bars(&rows[1..2], 2), ["bb │██ 1"]);
+        assert_eq!(
+            bars(&[("x".to_owned(), 3), ("y".to_owned(), 16)], 2)[0],
+            "x │▍ 3"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Finding where iterating a state transition starts repeating itself,
+//! to jump ahead to step counts that are too large to simulate, and when
+//! several such sequences line up.
+
+use std::{collections::HashMap, convert::Infallible, hash::Hash};
+
+use crate::math;
+
+/// The states of iterating a function until it repeats: `states[start..]`
+/// go around in circles, and the state after them is `states[start]`
+/// again.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cycle<S> {
+    pub states: Vec<S>,
+    pub start: usize,
+    pub len: usize,
+}
+
+impl<S> Cycle<S> {
+    /// The state after `n` steps.
+    #[must_use]
+    pub fn nth(&self, n: usize) -> &S {
+        if n < self.states.len() {
+            &self.states[n]
+        } else {
+            &self.states[self.start + (n - self.start) % self.len]
+        }
+    }
+}
+
+/// Iterates `step` from `initial` until a state comes up again,
+/// remembering all states by their hash.
+pub fn find_cycle<S>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let Ok(cycle) = try_find_cycle(initial, |s| Ok::<_, Infallible>(step(s)));
+    cycle
+}
+
+/// Like [`find_cycle`], but for steps that can fail.
+pub fn try_find_cycle<S, E>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Result<S, E>,
+) -> Result<Cycle<S>, E>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let len = states.len() - start;
+            return Ok(Cycle { states, start, len });
+        }
+
+        let next = step(&state)?;
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// The start and length of the cycle that iterating `step` from
+/// `initial` runs into, by Brent's algorithm. It only keeps two states
+/// around instead of all of them, but calls `step` about three times as
+/// often as [`find_cycle`].
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    // Find the length by moving the hare ahead in powers of two until it
+    // meets the tortoise, which waits at the last power of two.
+    let (mut power, mut len) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // With the hare `len` steps ahead, both meet where the cycle starts.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    (start, len)
+}
+
+/// The steps at which a sequence that starts repeating itself hits
+/// something: `early` ones before it repeats, and `late` ones from `start`
+/// on, which come back every `period` steps.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Periodic {
+    early: Vec<usize>,
+    late: Vec<usize>,
+    start: usize,
+    period: usize,
+}
+
+impl Periodic {
+    /// `hits` are all steps with a hit before `start + period`.
+    #[must_use]
+    pub fn new(hits: impl IntoIterator<Item = usize>, start: usize, period: usize) -> Self {
+        assert!(period > 0, "periods can't be empty");
+
+        let (early, late) = hits.into_iter().partition(|&t| t < start);
+        Self {
+            early,
+            late,
+            start,
+            period,
+        }
+    }
+
+    /// The steps at which the states of `cycle` satisfy `hit`.
+    #[must_use]
+    pub fn from_cycle<S>(cycle: &Cycle<S>, mut hit: impl FnMut(&S) -> bool) -> Self {
+        let hits = (0..cycle.states.len()).filter(|&t| hit(&cycle.states[t]));
+        Self::new(hits, cycle.start, cycle.len)
+    }
+
+    #[must_use]
+    pub fn hits(&self, steps: usize) -> bool {
+        if steps < self.start {
+            self.early.contains(&steps)
+        } else {
+            self.late
+                .contains(&(self.start + (steps - self.start) % self.period))
+        }
+    }
+}
+
+/// The first step at which all `sequences` hit at once, if there is one.
+///
+/// Before all of them repeat, only early hits can line up, so those are
+/// checked one by one. Afterwards, each combination of late hits is a
+/// system of congruences for the Chinese remainder theorem. The periods
+/// don't need to be coprime: congruences whose periods share a factor
+/// either agree on it or have no common step.
+#[must_use]
+pub fn first_common_hit(sequences: &[Periodic]) -> Option<usize> {
+    let early = sequences
+        .iter()
+        .flat_map(|s| s.early.iter().copied())
+        .filter(|&t| sequences.iter().all(|s| s.hits(t)));
+
+    let start = sequences.iter().map(|s| s.start).max().unwrap_or(0);
+    let late = sequences
+        .iter()
+        .fold(vec![(0, 1)], |congruences, s| {
+            congruences
+                .iter()
+                .flat_map(|&c| {
+                    s.late
+                        .iter()
+                        .filter_map(move |&t| math::crt(c, (t % s.period, s.period)))
+                })
+                .collect()
+        })
+        .into_iter()
+        .map(|(residue, modulus)| {
+            residue + start.saturating_sub(residue).div_ceil(modulus) * modulus
+        });
+
+    early.chain(late).min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_are_found() {
+        for (initial, modulus) in [(3u64, 255), (2, 1000), (0, 7), (5, 1)] {
+            let step = |x: &u64| (x * x + 1) % modulus;
+            let cycle = find_cycle(initial, step);
+
+            assert_eq!(brent(initial, step), (cycle.start, cycle.len));
+
+            let mut state = initial;
+            for n in 0..500 {
+                assert_eq!(*cycle.nth(n), state, "{initial} {modulus} {n}");
+                state = step(&state);
+            }
+        }
+
+        let cycle = find_cycle(0, |x| (x + 1) % 4);
+        assert_eq!((cycle.start, cycle.len), (0, 4));
+        assert_eq!(*cycle.nth(1_000_000_001), 1);
+
+        assert_eq!(
+            try_find_cycle(0, |x| if *x < 3 { Ok(x + 1) } else { Err(*x) }),
+            Err(3)
+        );
+    }
+
+    #[test]
+    fn common_hits_are_found() {
+        let brute_force =
+            |sequences: &[Periodic]| (0..10_000).find(|&t| sequences.iter().all(|s| s.hits(t)));
+
+        // Periods with common factors and phases that don't start at 0.
+        let sequences = [
+            Periodic::new([5, 7], 2, 6),
+            Periodic::new([1, 9, 12], 3, 10),
+            Periodic::new([3], 3, 4),
+        ];
+        assert_eq!(first_common_hit(&sequences), brute_force(&sequences));
+        assert_eq!(first_common_hit(&sequences), Some(19));
+
+        // An early hit that the others share.
+        let sequences = [Periodic::new([1, 3], 2, 2), Periodic::new([1, 6], 0, 7)];
+        assert_eq!(first_common_hit(&sequences), Some(1));
+
+        // Even and odd steps never meet.
+        let sequences = [Periodic::new([0], 0, 2), Periodic::new([1], 0, 2)];
+        assert_eq!(first_common_hit(&sequences), None);
+
+        let cycle = find_cycle(0, |x| (x + 3) % 10);
+        let multiples = Periodic::from_cycle(&cycle, |x| x % 5 == 0);
+        assert!(multiples.hits(15) && !multiples.hits(16));
+    }
+}
@@ -0,0 +1,157 @@
+//! Structured exports of the intermediate state of a day, e.g. to check
+//! a sum independently in a spreadsheet.
+
+use std::{path::Path, str::FromStr};
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::{Map, Value};
+
+use crate::solution::Answer;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    /// Picks the format by the extension of `path`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Can't tell the format of {} without extension",
+                    path.display()
+                )
+            })?
+            .parse()
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => bail!("Unsupported format: {s}, use csv or json"),
+        }
+    }
+}
+
+/// Rows of values with named columns.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Table {
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<Answer>>,
+}
+
+impl Table {
+    #[must_use]
+    pub fn new(columns: &[&'static str]) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            rows: vec![],
+        }
+    }
+
+    pub fn row(&mut self, row: Vec<Answer>) -> &mut Self {
+        debug_assert_eq!(row.len(), self.columns.len());
+
+        self.rows.push(row);
+        self
+    }
+
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let line = |cells: Vec<String>| {
+            cells
+                .iter()
+                .map(|c| csv_field(c))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        std::iter::once(line(self.columns.iter().map(|c| c.to_string()).collect()))
+            .chain(
+                self.rows
+                    .iter()
+                    .map(|row| line(row.iter().map(ToString::to_string).collect())),
+            )
+            .map(|l| l + "\n")
+            .collect()
+    }
+
+    /// An array with an object per row.
+    pub fn to_json(&self) -> Result<String> {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                Value::Object(
+                    self.columns
+                        .iter()
+                        .zip(row)
+                        .map(|(c, v)| (c.to_string(), json_value(v)))
+                        .collect::<Map<_, _>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Ok(serde_json::to_string_pretty(&rows)? + "\n")
+    }
+
+    pub fn render(&self, format: Format) -> Result<String> {
+        match format {
+            Format::Csv => Ok(self.to_csv()),
+            Format::Json => self.to_json(),
+        }
+    }
+}
+
+/// Quotes fields that would otherwise break the line apart.
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// An answer as JSON. Every machine-readable output uses this, so they
+/// all agree on how answers look.
+#[must_use]
+pub fn json_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Int(v) => (*v).into(),
+        Answer::UInt(v) => (*v).into(),
+        Answer::Text(v) => v.clone().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_tables() -> Result<()> {
+        let mut table = Table::new(&["name", "value"]);
+        table
+            .row(vec![Answer::Text("a,b".to_owned()), Answer::Int(-1)])
+            .row(vec![Answer::Text("c".to_owned()), Answer::UInt(2)]);
+
+        assert_eq!(table.to_csv(), "name,value\n\"a,b\",-1\nc,2\n");
+        assert_eq!(
+            serde_json::from_str::<Value>(&table.to_json()?)?,
+            serde_json::json!([{"name": "a,b", "value": -1}, {"name": "c", "value": 2}])
+        );
+
+        assert_eq!(Format::from_path(Path::new("out.JSON"))?, Format::Json);
+        assert!(Format::from_path(Path::new("out")).is_err());
+        assert!(Format::from_path(Path::new("out.xls")).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+//! Errors of the library API. Each day names the ways its input can be
+//! broken in an error type of its own, but solvers use `anyhow` inside.
+//! Callers like [`serve`](crate::serve) need to tell inputs that are
+//! broken from solvers that are, so [`registry`](crate::registry) sorts
+//! failures into these types.
+
+use std::{convert::Infallible, error::Error as StdError, fmt::Display, iter};
+
+use thiserror::Error;
+
+use crate::lint::Diagnostic;
+
+/// The input doesn't have the shape the day expects.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub struct ParseError {
+    /// Where the input goes wrong, 1-based, as far as the lint of the day
+    /// can tell.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    /// The error of a parser that rejected an input, placed at the first
+    /// problem `diagnostics` point out. The message is the parser's, with
+    /// the errors that caused it.
+    #[must_use]
+    pub fn new(error: &impl DayError, diagnostics: &[Diagnostic]) -> Self {
+        let first = diagnostics.first();
+        let causes = iter::successors(Some(error as &dyn StdError), |e| (*e).source());
+
+        Self {
+            line: first.map(|d| d.line),
+            column: first.map(|d| d.column),
+            message: causes
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(": "),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{line}:{column}: {}", self.message),
+            (Some(line), None) => write!(f, "{line}: {}", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// The error type of a day's parser, see
+/// [`Solution::Error`](crate::solution::Solution::Error). The parsers
+/// don't know where in the input they are, so [`ParseError::new`] leaves
+/// that to the lint of the day.
+pub trait DayError: StdError + Send + Sync + 'static {}
+
+/// For days whose parser takes any input.
+impl DayError for Infallible {}
+
+/// A solver gave up because its [`CancelToken`](crate::cancel::CancelToken)
+/// was cancelled.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Error)]
+#[error("cancelled")]
+pub struct Cancelled;
+
+#[derive(Debug, Error)]
+pub enum SolveError {
+    #[error("Invalid input: {0}")]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Cancelled(#[from] Cancelled),
+    /// Anything else, which points at a bug in the solution.
+    #[error(transparent)]
+    Internal(anyhow::Error),
+}
+
+impl From<anyhow::Error> for SolveError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<Cancelled>() {
+            Ok(cancelled) => SolveError::Cancelled(cancelled),
+            Err(error) => SolveError::Internal(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::ParseIntError;
+
+    use anyhow::{anyhow, Context};
+
+    use super::*;
+
+    #[derive(Debug, Error)]
+    #[error("Broken count")]
+    struct Broken(#[source] ParseIntError);
+
+    impl DayError for Broken {}
+
+    #[test]
+    fn errors_are_sorted() {
+        let diagnostics = [Diagnostic {
+            line: 3,
+            column: 7,
+            message: "Expected a digit".to_owned(),
+        }];
+        let broken = Broken("x".parse::<u32>().unwrap_err());
+        let error = ParseError::new(&broken, &diagnostics);
+        assert_eq!(
+            error.to_string(),
+            "3:7: Broken count: invalid digit found in string"
+        );
+        assert_eq!(
+            ParseError::new(&broken, &[]).to_string(),
+            "Broken count: invalid digit found in string"
+        );
+
+        let cancelled = Err::<(), _>(anyhow::Error::new(Cancelled)).context("Line 5");
+        assert!(matches!(
+            cancelled.map_err(SolveError::from),
+            Err(SolveError::Cancelled(Cancelled))
+        ));
+        assert!(matches!(
+            SolveError::from(anyhow!("overflow")),
+            SolveError::Internal(_)
+        ));
+    }
+}
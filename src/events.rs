@@ -0,0 +1,134 @@
+//! Progress of a run as JSON lines on stderr, for editors and dashboards
+//! that want to follow long runs without parsing the regular output:
+//!
+//! ```text
+//! {"event":"part_started","day":12,"part":2}
+//! ```
+
+use std::{
+    cell::Cell,
+    io::{self, Write},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{dump::json_value, results::PartResult};
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    RunStarted {
+        days: Vec<u8>,
+    },
+    DayStarted {
+        day: u8,
+    },
+    PartStarted {
+        day: u8,
+        part: u8,
+    },
+    PartFinished {
+        day: u8,
+        part: u8,
+        answer: Value,
+        time_ns: u128,
+    },
+    /// The day is done, either with all its parts or with `error`, e.g.
+    /// because it timed out and was cancelled.
+    DayFinished {
+        day: u8,
+        error: Option<String>,
+    },
+    /// The day was left out, because of `--skip` or because it would
+    /// have taken `expected_ns`, longer than the rest of the `--budget`.
+    DaySkipped {
+        day: u8,
+        expected_ns: Option<u128>,
+    },
+    RunFinished {
+        failed: Vec<u8>,
+        wall_time_ns: u128,
+    },
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Starts writing events. Without this they are dropped.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Writes `event` as a line of JSON to stderr.
+pub fn emit(event: &Event) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    if let Ok(line) = serde_json::to_string(event) {
+        // A single write, so lines of different threads don't mix.
+        let _ = io::stderr().write_all(format!("{line}\n").as_bytes());
+    }
+}
+
+thread_local! {
+    static DAY: Cell<Option<u8>> = const { Cell::new(None) };
+}
+
+/// Marks the current thread as solving `day`, so the events of its
+/// parts can say which day they belong to.
+pub fn attach(day: u8) {
+    DAY.with(|d| d.set(Some(day)));
+}
+
+/// Announces that `part` of the day attached to this thread starts.
+pub fn part_started(part: u8) {
+    if let Some(day) = DAY.with(Cell::get) {
+        emit(&Event::PartStarted { day, part });
+    }
+}
+
+/// Announces the result of a part of the day attached to this thread.
+pub fn part_finished(result: &PartResult) {
+    if let Some(day) = DAY.with(Cell::get) {
+        emit(&Event::PartFinished {
+            day,
+            part: result.part,
+            answer: json_value(&result.answer),
+            time_ns: result.time.as_nanos(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Answer;
+
+    #[test]
+    fn events_are_tagged() -> serde_json::Result<()> {
+        assert_eq!(
+            serde_json::to_string(&Event::PartStarted { day: 12, part: 2 })?,
+            r#"{"event":"part_started","day":12,"part":2}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Event::PartFinished {
+                day: 1,
+                part: 1,
+                answer: json_value(&Answer::UInt(142)),
+                time_ns: 5,
+            })?,
+            r#"{"event":"part_finished","day":1,"part":1,"answer":142,"time_ns":5}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Event::RunFinished {
+                failed: vec![3],
+                wall_time_ns: 7
+            })?,
+            r#"{"event":"run_finished","failed":[3],"wall_time_ns":7}"#
+        );
+
+        Ok(())
+    }
+}
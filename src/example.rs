@@ -0,0 +1,82 @@
+//! Tests of the examples from the puzzle descriptions. Each example is a
+//! file next to its day, e.g. `day7.example`, and
+//!
+//! ```ignore
+//! example_test!(day7, part1 = 6440, part2 = 5905);
+//! ```
+//!
+//! in the day's tests checks the answers of both parts on it. Days with a
+//! different example for part 2 name the second file `day7_part2.example`
+//! and test it with `example_test!(day7_part2, part2 = 5905)`.
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::registry;
+
+/// Generates a module named like the example, with a test per part.
+#[macro_export]
+macro_rules! example_test {
+    ($example:ident $(, $part:ident = $answer:expr)+ $(,)?) => {
+        mod $example {
+            $(
+                #[test]
+                fn $part() -> anyhow::Result<()> {
+                    $crate::example::check(
+                        module_path!(),
+                        include_str!(concat!(stringify!($example), ".example")),
+                        stringify!($part),
+                        &$answer.to_string(),
+                    )
+                }
+            )+
+        }
+    };
+}
+
+/// Finds the year and day in the path of a test module like
+/// `aoc2023::aoc2023::day7::tests::day7`.
+fn puzzle_of(module: &str) -> Option<(u16, u8)> {
+    let segments = module.split("::").collect::<Vec<_>>();
+    let year = segments
+        .iter()
+        .rev()
+        .find_map(|s| s.strip_prefix("aoc")?.parse().ok())?;
+    let day = segments
+        .iter()
+        .find_map(|s| s.strip_prefix("day")?.parse().ok())?;
+
+    Some((year, day))
+}
+
+/// Solves `part` of the day that `module` belongs to on `example` and
+/// compares the answer with `expected`.
+pub fn check(module: &str, example: &str, part: &str, expected: &str) -> Result<()> {
+    let (year, day) = puzzle_of(module).ok_or_else(|| anyhow!("No day in {module}"))?;
+    let puzzle = registry::find(year, day).ok_or_else(|| anyhow!("Day {day} isn't registered"))?;
+    let part = part
+        .strip_prefix("part")
+        .and_then(|p| p.parse().ok())
+        .ok_or_else(|| anyhow!("Expected part1 or part2, got {part}"))?;
+
+    let answer = (puzzle.solve_part)(example, part)
+        .with_context(|| format!("Day {day} part {part} failed on the example"))?
+        .ok_or_else(|| anyhow!("Day {day} has no part {part}"))?;
+
+    assert_eq!(answer.answer.to_string(), expected, "day {day} part {part}");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_puzzle() {
+        assert_eq!(
+            puzzle_of("aoc2023::aoc2023::day7::tests::day7_part2"),
+            Some((2023, 7))
+        );
+        assert_eq!(puzzle_of("aoc2023::tests"), None);
+    }
+}
@@ -0,0 +1,110 @@
+//! Downloads puzzle inputs and descriptions from the Advent of Code
+//! website into the input directory, and submits answers. Inputs are personal and part 2 is
+//! only shown once part 1 is solved, so this needs the session cookie of
+//! a logged in browser.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{input, output::Message};
+
+/// The website asks automated tools to say who they are.
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_REPOSITORY"),
+    " by ",
+    env!("CARGO_PKG_AUTHORS")
+);
+
+#[must_use]
+pub fn url(year: u16, day: u8) -> String {
+    format!("{}/input", puzzle_url(year, day))
+}
+
+#[must_use]
+pub fn puzzle_url(year: u16, day: u8) -> String {
+    format!("https://adventofcode.com/{year}/day/{day}")
+}
+
+fn download(url: &str, session: &str) -> Result<String> {
+    ureq::get(url)
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .with_context(|| format!("Failed to download {url}"))?
+        .into_string()
+        .with_context(|| format!("Failed to read {url}"))
+}
+
+fn save(path: &Path, contents: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Downloads the input of a day to where [`input::load`] looks for it,
+/// unless it is already there. Returns the path and whether it was
+/// downloaded.
+pub fn input(year: u16, day: u8, session: &str) -> Result<(PathBuf, bool)> {
+    let path = input::path(year, day);
+    if path.exists() {
+        return Ok((path, false));
+    }
+
+    save(&path, &download(&url(year, day), session)?)?;
+
+    Ok((path, true))
+}
+
+/// Submits `answer` for `part` and returns the page with the reply.
+pub fn answer(year: u16, day: u8, part: u8, answer: &str, session: &str) -> Result<String> {
+    let url = format!("{}/answer", puzzle_url(year, day));
+
+    ureq::post(&url)
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .with_context(|| format!("Failed to submit to {url}"))?
+        .into_string()
+        .with_context(|| format!("Failed to read {url}"))
+}
+
+/// Where the page of a puzzle is kept, next to its input.
+#[must_use]
+pub fn puzzle_path(year: u16, day: u8) -> PathBuf {
+    input::path(year, day).with_extension("html")
+}
+
+/// The page of a puzzle. It is downloaded only if it isn't kept yet or
+/// still lacks part 2, and there is a session to download it with.
+pub fn puzzle(year: u16, day: u8, session: Option<&str>) -> Result<String> {
+    let path = puzzle_path(year, day);
+    let kept = fs::read_to_string(&path).ok();
+
+    match (kept, session) {
+        (Some(page), _) if page.matches("<article").count() >= 2 => Ok(page),
+        (_, Some(session)) => {
+            let page = download(&puzzle_url(year, day), session)?;
+            save(&path, &page)?;
+            Ok(page)
+        }
+        (Some(page), None) => Ok(page),
+        (None, None) => Err(anyhow!("{}", Message::NeedsSession)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_point_to_inputs() {
+        assert_eq!(url(2023, 7), "https://adventofcode.com/2023/day/7/input");
+        assert_eq!(puzzle_url(2023, 7), "https://adventofcode.com/2023/day/7");
+        assert!(USER_AGENT.starts_with("https://github.com/"));
+    }
+}
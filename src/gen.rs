@@ -0,0 +1,70 @@
+//! Random but valid puzzle inputs of any size, to measure how solutions
+//! scale beyond the official inputs.
+//!
+//! Inputs only depend on the seed and the parameters, so a slow case can
+//! be reproduced.
+
+use std::sync::OnceLock;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::solution::ExplainQuery;
+
+/// A generator with a stable algorithm, unlike `rand`'s `StdRng`, so a
+/// seed gives the same input with every version.
+pub type Rng = ChaCha8Rng;
+
+#[must_use]
+pub fn rng(seed: u64) -> Rng {
+    Rng::seed_from_u64(seed)
+}
+
+static PARAMS: OnceLock<ExplainQuery> = OnceLock::new();
+
+/// Tunes the generators with `key=value` pairs like those of `explain`.
+/// Only the first call has an effect.
+pub fn set_params(params: ExplainQuery) {
+    let _ = PARAMS.set(params);
+}
+
+/// The parameters for the generators. Which ones a day knows is up to
+/// it.
+#[must_use]
+pub fn params() -> &'static ExplainQuery {
+    PARAMS.get_or_init(ExplainQuery::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for puzzle in registry::all() {
+            let Ok(input) = (puzzle.generate)(&mut rng(1), 50) else {
+                continue;
+            };
+
+            assert_eq!((puzzle.lint)(&input), [], "day {}", puzzle.day);
+        }
+    }
+
+    #[cfg(feature = "day7")]
+    #[test]
+    fn seeds_reproduce_inputs() -> anyhow::Result<()> {
+        let puzzle = registry::find(2023, 7).unwrap();
+
+        assert_eq!(
+            (puzzle.generate)(&mut rng(7), 10)?,
+            (puzzle.generate)(&mut rng(7), 10)?
+        );
+        assert_ne!(
+            (puzzle.generate)(&mut rng(7), 10)?,
+            (puzzle.generate)(&mut rng(8), 10)?
+        );
+
+        Ok(())
+    }
+}
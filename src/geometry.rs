@@ -0,0 +1,605 @@
+//! Coordinates and directions on grids. Rows come first and count
+//! downwards, columns count to the right, and both are named, so they
+//! can't be mixed up like the halves of a tuple. Puzzles in space have
+//! points and boxes with `x`, `y` and `z`, where `z` points up.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    num::TryFromIntError,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use num_integer::Integer;
+
+use crate::math::gcd;
+
+/// A position inside a grid.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Point2 {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A step between positions, or a position that may lie above or left
+/// of a grid.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Vec2 {
+    pub row: isize,
+    pub col: isize,
+}
+
+/// A position or step in space. Coordinates are `i64` unless said
+/// otherwise, as the inputs in space have large ones.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Point3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// The cells from `min` to `max` in space, including both. Each
+/// coordinate of `min` is at most the one of `max`, so a box is never
+/// empty.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Aabb3 {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+/// A direction on a grid, with north pointing up.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Point2 {
+    #[must_use]
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// The point `offset` away, unless that is above or left of the
+    /// first row and column.
+    #[must_use]
+    pub fn checked_add(self, offset: Vec2) -> Option<Self> {
+        Some(Self {
+            row: self.row.checked_add_signed(offset.row)?,
+            col: self.col.checked_add_signed(offset.col)?,
+        })
+    }
+
+    /// Steps between the points, along rows and columns.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+
+    /// Steps between the points, when diagonal steps are allowed, too.
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.row
+            .abs_diff(other.row)
+            .max(self.col.abs_diff(other.col))
+    }
+}
+
+impl Vec2 {
+    pub const UP: Self = Self::new(-1, 0);
+    pub const DOWN: Self = Self::new(1, 0);
+    pub const LEFT: Self = Self::new(0, -1);
+    pub const RIGHT: Self = Self::new(0, 1);
+
+    /// The steps to the four cells that share an edge, in reading order.
+    pub const SIDES: [Self; 4] = [Self::UP, Self::LEFT, Self::RIGHT, Self::DOWN];
+
+    /// The steps to the eight surrounding cells, in reading order.
+    pub const AROUND: [Self; 8] = [
+        Self::new(-1, -1),
+        Self::UP,
+        Self::new(-1, 1),
+        Self::LEFT,
+        Self::RIGHT,
+        Self::new(1, -1),
+        Self::DOWN,
+        Self::new(1, 1),
+    ];
+
+    #[must_use]
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+
+    /// Steps along rows and columns to cover the vector.
+    #[must_use]
+    pub fn manhattan_len(self) -> usize {
+        self.row.unsigned_abs() + self.col.unsigned_abs()
+    }
+}
+
+impl Direction {
+    /// All directions, clockwise from north.
+    pub const ALL: [Self; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The step to the next cell in this direction.
+    #[must_use]
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Direction::North => Vec2::UP,
+            Direction::East => Vec2::RIGHT,
+            Direction::South => Vec2::DOWN,
+            Direction::West => Vec2::LEFT,
+        }
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.row + other.row, self.col + other.col)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.row, -self.col)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, factor: isize) -> Self {
+        Self::new(self.row * factor, self.col * factor)
+    }
+}
+
+impl Mul<usize> for Point2 {
+    type Output = Self;
+
+    fn mul(self, factor: usize) -> Self {
+        Self::new(self.row * factor, self.col * factor)
+    }
+}
+
+/// The step from `other` to `self`.
+impl Sub for Point2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Self) -> Vec2 {
+        Vec2::from(self) - Vec2::from(other)
+    }
+}
+
+impl From<Point2> for Vec2 {
+    fn from(p: Point2) -> Self {
+        Self::new(p.row as isize, p.col as isize)
+    }
+}
+
+/// Fails for positions above or left of the grid.
+impl TryFrom<Vec2> for Point2 {
+    type Error = TryFromIntError;
+
+    fn try_from(v: Vec2) -> Result<Self, Self::Error> {
+        Ok(Self::new(v.row.try_into()?, v.col.try_into()?))
+    }
+}
+
+impl<T> Point3<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Integer + Copy> Point3<T> {
+    /// Steps between the points along the axes.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        let diff = |a: T, b: T| if a < b { b - a } else { a - b };
+        diff(self.x, other.x) + diff(self.y, other.y) + diff(self.z, other.z)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+/// Three numbers separated by commas, with or without spaces, like
+/// `19, 13, 30`.
+impl<T> FromStr for Point3<T>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let coords = s
+            .split(',')
+            .map(|c| {
+                c.trim()
+                    .parse()
+                    .with_context(|| format!("Can't parse coordinate {c}"))
+            })
+            .collect::<Result<Vec<T>>>()?;
+
+        let Ok([x, y, z]) = <[T; 3]>::try_from(coords) else {
+            bail!("Expected three coordinates: {s}");
+        };
+        Ok(Self::new(x, y, z))
+    }
+}
+
+/// Like it is parsed, without spaces.
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl Aabb3 {
+    /// The box with corners `a` and `b`, which may be any opposite ones.
+    #[must_use]
+    pub fn new(a: Point3, b: Point3) -> Self {
+        Self {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// The number of cells in the box.
+    #[must_use]
+    pub fn volume(&self) -> u64 {
+        let size = self.max - self.min;
+        (size.x.unsigned_abs() + 1) * (size.y.unsigned_abs() + 1) * (size.z.unsigned_abs() + 1)
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// Whether the boxes share a cell.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Whether the boxes would share a cell if they were at the same
+    /// height, like a brick that falls onto another.
+    #[must_use]
+    pub fn overlaps_xy(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    /// The cells that are in both boxes, if there are any.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Point3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Point3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+    }
+
+    /// The box moved by `offset`.
+    #[must_use]
+    pub fn shifted(&self, offset: Point3) -> Self {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// The cells of the box, layer by layer from the bottom, and each
+    /// layer by `y`, then `x`.
+    pub fn points(&self) -> impl Iterator<Item = Point3> + '_ {
+        (self.min.z..=self.max.z).flat_map(move |z| {
+            (self.min.y..=self.max.y)
+                .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point3::new(x, y, z)))
+        })
+    }
+}
+
+/// Two corners separated by `~`, like the bricks of day 22:
+/// `1,0,1~1,2,1`.
+impl FromStr for Aabb3 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (a, b) = s
+            .split_once('~')
+            .ok_or_else(|| anyhow!("Expected two corners separated by ~: {s}"))?;
+        Ok(Self::new(a.parse()?, b.parse()?))
+    }
+}
+
+impl Display for Aabb3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}~{}", self.min, self.max)
+    }
+}
+
+/// Twice the area of the polygon with `corners`, in order around it, by
+/// the shoelace formula. Twice, so that lattice polygons have an integer
+/// area. The corners may as well be every point along the edges.
+#[must_use]
+pub fn double_area(corners: &[Vec2]) -> usize {
+    corners
+        .iter()
+        .zip(corners.iter().cycle().skip(1))
+        .map(|(a, b)| a.col * b.row - b.col * a.row)
+        .sum::<isize>()
+        .unsigned_abs()
+}
+
+/// The lattice points on the edges of the polygon with `corners`.
+#[must_use]
+pub fn boundary_points(corners: &[Vec2]) -> usize {
+    corners
+        .iter()
+        .zip(corners.iter().cycle().skip(1))
+        .map(|(a, b)| {
+            let edge = *b - *a;
+            gcd(edge.row.unsigned_abs(), edge.col.unsigned_abs())
+        })
+        .sum()
+}
+
+/// The lattice points strictly inside the polygon with `corners`, which
+/// must not cross itself. Pick's theorem relates them to the area `A`
+/// and the points `B` on the boundary: `A = I + B / 2 - 1`.
+#[must_use]
+pub fn interior_points(corners: &[Vec2]) -> usize {
+    if corners.len() < 3 {
+        return 0;
+    }
+
+    (double_area(corners) + 2).saturating_sub(boundary_points(corners)) / 2
+}
+
+/// Takes both the letters `U`, `R`, `D` and `L` and the arrows `^`, `>`,
+/// `v` and `<`.
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        Ok(match c {
+            'U' | '^' => Direction::North,
+            'R' | '>' => Direction::East,
+            'D' | 'v' => Direction::South,
+            'L' | '<' => Direction::West,
+            _ => bail!("Invalid direction: {c}"),
+        })
+    }
+}
+
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => Err(anyhow!("Invalid direction: {s}")),
+        }
+    }
+}
+
+/// The letter the direction is parsed from.
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self {
+            Direction::North => 'U',
+            Direction::East => 'R',
+            Direction::South => 'D',
+            Direction::West => 'L',
+        };
+        write!(f, "{letter}")
+    }
+}
+
+impl Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "row {}, column {}", self.row, self.col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_work() {
+        let p = Point2::new(2, 5);
+
+        assert_eq!(p.checked_add(Vec2::UP * 2), Some(Point2::new(0, 5)));
+        assert_eq!(p.checked_add(Vec2::UP * 3), None);
+        assert_eq!(Point2::new(0, 1) - p, Vec2::new(-2, -4));
+        assert_eq!(
+            (Point2::new(0, 1) - p).manhattan_len(),
+            p.manhattan(Point2::new(0, 1))
+        );
+        assert_eq!(p.chebyshev(Point2::new(0, 1)), 4);
+        assert_eq!(-Vec2::LEFT, Vec2::RIGHT);
+        assert_eq!(Point2::try_from(Vec2::new(1, -1)).ok(), None);
+        assert_eq!(Point2::try_from(Vec2::from(p)), Ok(p));
+        assert_eq!(p * 2, Point2::new(4, 10));
+        assert!(Point2::new(0, 9) < Point2::new(1, 0));
+    }
+
+    #[test]
+    fn polygons_work() {
+        let corners = [
+            Vec2::new(0, 0),
+            Vec2::new(0, 4),
+            Vec2::new(3, 4),
+            Vec2::new(3, 0),
+        ];
+        assert_eq!(double_area(&corners), 24);
+        assert_eq!(boundary_points(&corners), 14);
+        assert_eq!(interior_points(&corners), 6);
+
+        // The same rectangle tile by tile, like a loop in a maze.
+        let tiles = corners
+            .iter()
+            .zip(corners.iter().cycle().skip(1))
+            .flat_map(|(a, b)| {
+                let edge = *b - *a;
+                let step = Vec2::new(edge.row.signum(), edge.col.signum());
+                (0..edge.manhattan_len() as isize).map(move |i| *a + step * i)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(tiles.len(), 14);
+        assert_eq!(double_area(&tiles), 24);
+        assert_eq!(interior_points(&tiles), 6);
+
+        let triangle = [Vec2::new(0, 0), Vec2::new(0, 4), Vec2::new(4, 0)];
+        assert_eq!(double_area(&triangle), 16);
+        assert_eq!(interior_points(&triangle), 3);
+        assert_eq!(interior_points(&corners[..2]), 0);
+    }
+
+    #[test]
+    fn space_works() -> Result<()> {
+        let p = "19, 13, -30".parse::<Point3>()?;
+        assert_eq!(p, Point3::new(19, 13, -30));
+        assert_eq!(p.to_string(), "19,13,-30");
+        assert_eq!(p - Point3::new(1, 1, 1), Point3::new(18, 12, -31));
+        assert_eq!(-p * 2, Point3::new(-38, -26, 60));
+        assert_eq!(p.manhattan(Point3::default()), 62);
+        assert_eq!(
+            Point3::<u8>::new(1, 5, 2).manhattan(Point3::new(3, 0, 2)),
+            7
+        );
+        assert!("1,2".parse::<Point3>().is_err());
+        assert!("1,2,x".parse::<Point3>().is_err());
+
+        let a = "2,2,2~0,0,1".parse::<Aabb3>()?;
+        assert_eq!(a.to_string(), "0,0,1~2,2,2");
+        assert_eq!(a.volume(), 18);
+        assert_eq!(a.points().count(), 18);
+        assert_eq!(a.points().nth(1), Some(Point3::new(1, 0, 1)));
+        assert!(a.contains(Point3::new(2, 1, 1)) && !a.contains(Point3::new(2, 1, 0)));
+
+        let b = "1,2,0~1,5,0".parse::<Aabb3>()?;
+        assert!(!a.overlaps(&b) && a.overlaps_xy(&b));
+        assert_eq!(
+            a.intersection(&b.shifted(Point3::new(0, 0, 2))),
+            Some(Aabb3::new(Point3::new(1, 2, 2), Point3::new(1, 2, 2)))
+        );
+        assert!(!a.overlaps_xy(&b.shifted(Point3::new(2, 0, 0))));
+        assert!("1,2,3".parse::<Aabb3>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn directions_work() -> Result<()> {
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_left(), d.reverse());
+            assert_eq!(d.reverse().offset(), -d.offset());
+            assert_eq!(d.to_string().parse::<Direction>()?, d);
+        }
+
+        assert_eq!(Direction::try_from('v')?, Direction::South);
+        assert_eq!("<".parse::<Direction>()?, Direction::West);
+        assert!("LR".parse::<Direction>().is_err());
+        assert!(Direction::try_from('x').is_err());
+
+        Ok(())
+    }
+}
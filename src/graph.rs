@@ -0,0 +1,419 @@
+//! Searches over graphs that are given as a function from a node to its
+//! successors, so days don't need to build the whole graph first. Nodes
+//! are usually positions or small states like `(position, direction)`.
+//! Orderings and components need all nodes, so they take a list to start
+//! from and find the rest through the successors.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// The nodes a search reached, each with the index of the node it was
+/// reached from.
+struct Visited<N>(Vec<(N, Option<usize>)>);
+
+impl<N: Clone> Visited<N> {
+    fn push(&mut self, node: N, parent: Option<usize>) -> usize {
+        self.0.push((node, parent));
+        self.0.len() - 1
+    }
+
+    /// The nodes from the start to the one at `last`.
+    fn path(&self, last: usize) -> Vec<N> {
+        let mut path = vec![];
+        let mut current = Some(last);
+
+        while let Some(i) = current {
+            let (node, parent) = &self.0[i];
+            path.push(node.clone());
+            current = *parent;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// The shortest path from `start` to a node that is a `goal`, counted in
+/// steps, including both ends.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut visited = Visited(vec![]);
+    let mut queue = VecDeque::from([visited.push(start, None)]);
+
+    while let Some(i) = queue.pop_front() {
+        let node = visited.0[i].0.clone();
+        if goal(&node) {
+            return Some(visited.path(i));
+        }
+
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                queue.push_back(visited.push(next, Some(i)));
+            }
+        }
+    }
+
+    None
+}
+
+/// The number of steps to each node that can be reached from `start`.
+pub fn bfs_distances<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// The nodes that can be reached from `start`, in depth-first order.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+
+        let first = stack.len();
+        stack.extend(successors(&node).into_iter().filter(|n| !seen.contains(n)));
+        // The first successor is visited first.
+        stack[first..].reverse();
+
+        order.push(node);
+    }
+
+    order
+}
+
+/// The cheapest path from `start` to a node that is a `goal` and its
+/// cost. `successors` gives each next node with the cost of the step
+/// there, which must not be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), goal)
+}
+
+/// Like [`dijkstra`], but tries nodes that `heuristic` deems close to a
+/// goal first. The path is only the cheapest if `heuristic` never
+/// overestimates the remaining cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut visited = Visited(vec![]);
+    // The index of the cheapest way to each node found so far, and its
+    // cost. Queue entries for other indices are stale.
+    let mut best = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    let first = visited.push(start.clone(), None);
+    best.insert(start.clone(), (first, C::default()));
+    queue.push(Reverse((heuristic(&start), C::default(), first)));
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        let node = visited.0[i].0.clone();
+        if best[&node].0 != i {
+            continue;
+        }
+        if goal(&node) {
+            return Some((visited.path(i), cost));
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if best.get(&next).is_some_and(|&(_, c)| c <= next_cost) {
+                continue;
+            }
+
+            let estimate = next_cost + heuristic(&next);
+            let j = visited.push(next.clone(), Some(i));
+            best.insert(next, (j, next_cost));
+            queue.push(Reverse((estimate, next_cost, j)));
+        }
+    }
+
+    None
+}
+
+/// All nodes reachable from `nodes`, in the order they are found, with
+/// the indices of each node's successors.
+fn index_graph<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> (Vec<N>, Vec<Vec<usize>>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut indices = HashMap::new();
+    let mut all = vec![];
+    let mut index = |node: N, all: &mut Vec<N>| {
+        *indices.entry(node.clone()).or_insert_with(|| {
+            all.push(node);
+            all.len() - 1
+        })
+    };
+
+    for node in nodes {
+        index(node, &mut all);
+    }
+
+    let mut edges = vec![];
+    while edges.len() < all.len() {
+        let next = successors(&all[edges.len()])
+            .into_iter()
+            .map(|n| index(n, &mut all))
+            .collect();
+        edges.push(next);
+    }
+
+    (all, edges)
+}
+
+/// The strongly connected components of the graph, by Tarjan's
+/// algorithm: the largest sets of nodes that can all reach each other.
+/// A component comes after all components it can reach, so reversing
+/// the list orders them topologically.
+pub fn strongly_connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    const UNSEEN: usize = usize::MAX;
+
+    let (all, edges) = index_graph(nodes, successors);
+    let mut order = vec![UNSEEN; all.len()];
+    // The earliest node on the stack that each node can get back to.
+    let mut low = vec![0; all.len()];
+    let mut on_stack = vec![false; all.len()];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut next = 0;
+
+    for root in 0..all.len() {
+        if order[root] != UNSEEN {
+            continue;
+        }
+
+        // Nodes with the number of successors they have tried so far,
+        // instead of recursion, which deep graphs would overflow.
+        let mut path = vec![(root, 0)];
+        while let Some(&(v, tried)) = path.last() {
+            if tried == 0 && order[v] == UNSEEN {
+                (order[v], low[v]) = (next, next);
+                next += 1;
+                stack.push(v);
+                on_stack[v] = true;
+            }
+
+            if let Some(&w) = edges[v].get(tried) {
+                path.last_mut().expect("path isn't empty").1 += 1;
+                if order[w] == UNSEEN {
+                    path.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(order[w]);
+                }
+                continue;
+            }
+
+            path.pop();
+            if let Some(&(parent, _)) = path.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+
+            if low[v] == order[v] {
+                let mut component = vec![];
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(all[w].clone());
+                    if w == v {
+                        break;
+                    }
+                }
+                component.reverse();
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// The nodes ordered so that each comes before its successors, by Kahn's
+/// algorithm, or `None` if there is a cycle. Ties go to the node that was
+/// found first, starting with `nodes` in their order.
+pub fn topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (all, edges) = index_graph(nodes, successors);
+    let mut incoming = vec![0; all.len()];
+    for &w in edges.iter().flatten() {
+        incoming[w] += 1;
+    }
+
+    let mut ready = (0..all.len())
+        .filter(|&v| incoming[v] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+    let mut order = vec![];
+
+    while let Some(Reverse(v)) = ready.pop() {
+        order.push(all[v].clone());
+        for &w in &edges[v] {
+            incoming[w] -= 1;
+            if incoming[w] == 0 {
+                ready.push(Reverse(w));
+            }
+        }
+    }
+
+    (order.len() == all.len()).then_some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn searches_work() {
+        let steps = |n: &u32| [n + 1, n * 2];
+        assert_eq!(bfs(1, steps, |n| *n == 10), Some(vec![1, 2, 4, 5, 10]));
+        assert_eq!(
+            bfs(1, |n| [n + 1].into_iter().filter(|n| *n < 5), |n| *n == 10),
+            None
+        );
+        assert_eq!(
+            bfs_distances(1, |n| [n * 2].into_iter().filter(|n| *n < 20))[&16],
+            4
+        );
+
+        assert_eq!(
+            dfs(0, |n| [(n + 3) % 10, (n + 1) % 10]),
+            [0, 3, 6, 9, 2, 5, 8, 1, 4, 7]
+        );
+        assert_eq!(dfs(0, |n| [(n + 2) % 6]), [0, 2, 4]);
+    }
+
+    #[test]
+    fn orders_and_components_are_found() {
+        // Two cycles, 1 -> 2 -> 3 -> 1 and 4 <-> 5, with 6 after both.
+        let edges = |n: &u32| match n {
+            1 => vec![2],
+            2 => vec![3, 4],
+            3 => vec![1],
+            4 => vec![5],
+            5 => vec![4, 6],
+            _ => vec![],
+        };
+        let mut components = strongly_connected_components([1], edges);
+        assert_eq!(components, [vec![6], vec![4, 5], vec![1, 2, 3]]);
+        assert_eq!(topological_sort([1], edges), None);
+
+        // Only the nodes reachable from the given ones.
+        components = strongly_connected_components([4], edges);
+        assert_eq!(components, [vec![6], vec![4, 5]]);
+
+        // The divisors of 12, from 1 up to 12.
+        let multiples = |&n: &u32| (2..=12 / n).map(move |k| n * k).filter(|m| 12 % m == 0);
+        assert_eq!(
+            topological_sort([1], multiples),
+            Some(vec![1, 2, 3, 4, 6, 12])
+        );
+        assert_eq!(strongly_connected_components([1], multiples).len(), 6);
+
+        // A long chain stays off the call stack.
+        let chain = |n: &u32| (*n < 1_000_000).then_some(n + 1);
+        assert_eq!(strongly_connected_components([0], chain).len(), 1_000_001);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let edges = [
+            (1, 2, 7),
+            (1, 3, 9),
+            (1, 6, 14),
+            (2, 3, 10),
+            (2, 4, 15),
+            (3, 4, 11),
+            (3, 6, 2),
+            (4, 5, 6),
+            (5, 6, 9),
+        ];
+        let successors = |n: &u32| {
+            edges
+                .iter()
+                .filter_map(|&(a, b, cost)| {
+                    (a == *n)
+                        .then_some((b, cost))
+                        .or((b == *n).then_some((a, cost)))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dijkstra(1, successors, |n| *n == 5),
+            Some((vec![1, 3, 6, 5], 20))
+        );
+        assert_eq!(dijkstra(1, successors, |n| *n == 7), None);
+
+        // Steps of one or three, where three costs two.
+        let line = |n: &i32| [(n - 1, 1), (n + 1, 1), (n + 3, 2)];
+        assert_eq!(
+            astar(0, line, |n| (10 - n).abs() * 2 / 3, |n| *n == 10).map(|(_, cost)| cost),
+            Some(7)
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Undirected graphs in Graphviz's DOT language, to look at the
+//! structure of an input with `dot`, `neato` and friends.
+
+use std::fmt::Write;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Graph {
+    name: String,
+    nodes: Vec<(String, Vec<(&'static str, String)>)>,
+    edges: Vec<(String, String)>,
+}
+
+/// Quotes `s` as DOT identifier.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Graph {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds node `id` with attributes like `("label", "467")`.
+    pub fn node(&mut self, id: impl Into<String>, attrs: &[(&'static str, &str)]) -> &mut Self {
+        self.nodes.push((
+            id.into(),
+            attrs.iter().map(|(k, v)| (*k, (*v).to_owned())).collect(),
+        ));
+        self
+    }
+
+    pub fn edge(&mut self, from: impl Into<String>, to: impl Into<String>) -> &mut Self {
+        self.edges.push((from.into(), to.into()));
+        self
+    }
+
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = format!("graph {} {{\n", quote(&self.name));
+
+        for (id, attrs) in &self.nodes {
+            let _ = write!(dot, "  {}", quote(id));
+            if !attrs.is_empty() {
+                let attrs = attrs
+                    .iter()
+                    .map(|(k, v)| format!("{k}={}", quote(v)))
+                    .collect::<Vec<_>>();
+                let _ = write!(dot, " [{}]", attrs.join(", "));
+            }
+            dot.push_str(";\n");
+        }
+
+        for (from, to) in &self.edges {
+            let _ = writeln!(dot, "  {} -- {};", quote(from), quote(to));
+        }
+
+        dot + "}\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_dot() {
+        let mut graph = Graph::new("parts");
+        graph
+            .node("n1", &[("label", "4\"67"), ("shape", "box")])
+            .node("s1", &[])
+            .edge("n1", "s1");
+
+        assert_eq!(
+            graph.to_dot(),
+            "graph \"parts\" {
+  \"n1\" [label=\"4\\\"67\", shape=\"box\"];
+  \"s1\";
+  \"n1\" -- \"s1\";
+}
+"
+        );
+    }
+}
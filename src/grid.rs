@@ -0,0 +1,428 @@
+//! Rectangular grids of cells, which is what most puzzle inputs are.
+//! Cells are addressed by row and column, counted from the top left.
+
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::geometry::{Point2, Vec2};
+
+/// A connected area of a grid, found by [`Grid::flood_fill`].
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Region {
+    /// In the order they were reached.
+    pub cells: Vec<Point2>,
+    /// The cells next to the region that weren't passable, once each.
+    pub boundary: Vec<Point2>,
+    /// Whether the region reaches the outermost rows or columns, which
+    /// tells the outside of a loop from the inside.
+    pub touches_edge: bool,
+}
+
+/// The cells are stored row by row in a single `Vec`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+/// A grid without any cells.
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self {
+            cells: vec![],
+            rows: 0,
+            cols: 0,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid of `rows`, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some(i) = rows.iter().position(|r| r.len() != cols) {
+            bail!(
+                "Row {} has {} cells, but the first has {cols}",
+                i + 1,
+                rows[i].len()
+            );
+        }
+
+        Ok(Self {
+            rows: rows.len(),
+            cols,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a grid with a line per row, turning each character into a
+    /// cell with `cell`.
+    pub fn parse(s: &str, mut cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let rows = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line.chars()
+                    .map(&mut cell)
+                    .collect::<Result<Vec<_>>>()
+                    .with_context(|| format!("Can't parse line {}", i + 1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point2) -> bool {
+        p.row < self.rows && p.col < self.cols
+    }
+
+    /// The cell at `p`, or `None` outside of the grid.
+    #[must_use]
+    pub fn get(&self, p: Point2) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.row * self.cols + p.col])
+    }
+
+    pub fn get_mut(&mut self, p: Point2) -> Option<&mut T> {
+        self.contains(p)
+            .then(|| &mut self.cells[p.row * self.cols + p.col])
+    }
+
+    /// The cell at `p`, or `default` outside of the grid.
+    #[must_use]
+    pub fn get_or(&self, p: Point2, default: T) -> T
+    where
+        T: Copy,
+    {
+        self.get(p).copied().unwrap_or(default)
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        (0..self.rows).map(move |row| &self[Point2::new(row, col)])
+    }
+
+    /// All cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// All cells with their position, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (Point2::new(i / self.cols, i % self.cols), cell))
+    }
+
+    /// The cells `steps` away from `p` that are inside the grid.
+    fn offset(&self, p: Point2, steps: &'static [Vec2]) -> impl Iterator<Item = Point2> + '_ {
+        steps
+            .iter()
+            .filter_map(move |step| p.checked_add(*step))
+            .filter(|p| self.contains(*p))
+    }
+
+    /// The up to four cells that share an edge with the one at `p`.
+    pub fn neighbors(&self, p: Point2) -> impl Iterator<Item = Point2> + '_ {
+        self.offset(p, &Vec2::SIDES)
+    }
+
+    /// The up to eight cells around the one at `p`, including the
+    /// diagonal ones.
+    pub fn neighbors8(&self, p: Point2) -> impl Iterator<Item = Point2> + '_ {
+        self.offset(p, &Vec2::AROUND)
+    }
+
+    /// The cells reachable from `start` through cells that are
+    /// `passable`, stepping to the sides. Empty if `start` itself isn't
+    /// passable.
+    pub fn flood_fill(&self, start: Point2, passable: impl FnMut(Point2, &T) -> bool) -> Region {
+        self.fill(start, &Vec2::SIDES, passable)
+    }
+
+    /// Like [`flood_fill`](Self::flood_fill), but stepping diagonally,
+    /// too.
+    pub fn flood_fill8(&self, start: Point2, passable: impl FnMut(Point2, &T) -> bool) -> Region {
+        self.fill(start, &Vec2::AROUND, passable)
+    }
+
+    fn fill(
+        &self,
+        start: Point2,
+        steps: &'static [Vec2],
+        mut passable: impl FnMut(Point2, &T) -> bool,
+    ) -> Region {
+        let mut region = Region::default();
+        // Cells that were looked at, passable or not.
+        let mut seen = self.map(|_| false);
+        let mut stack = vec![];
+
+        if let Some(cell) = self.get(start) {
+            seen[start] = true;
+            if passable(start, cell) {
+                stack.push(start);
+            }
+        }
+
+        while let Some(p) = stack.pop() {
+            region.cells.push(p);
+            region.touches_edge |=
+                p.row == 0 || p.col == 0 || p.row + 1 == self.rows || p.col + 1 == self.cols;
+
+            for next in self.offset(p, steps) {
+                if std::mem::replace(&mut seen[next], true) {
+                    continue;
+                }
+
+                if passable(next, &self[next]) {
+                    stack.push(next);
+                } else {
+                    region.boundary.push(next);
+                }
+            }
+        }
+
+        region
+    }
+
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// A grid of the same size with each row replaced by what `f` makes
+    /// of it. Panics if `f` changes the length of a row.
+    #[must_use]
+    pub fn map_rows<U>(&self, mut f: impl FnMut(&[T]) -> Vec<U>) -> Grid<U> {
+        let cells = self
+            .iter_rows()
+            .flat_map(|row| {
+                let mapped = f(row);
+                assert_eq!(mapped.len(), row.len(), "Rows must keep their length");
+                mapped
+            })
+            .collect();
+
+        Grid {
+            cells,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    #[must_use]
+    pub fn to_rows(&self) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        self.iter_rows().map(<[T]>::to_vec).collect()
+    }
+
+    /// The grid mirrored along its diagonal, so rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: (0..self.cols)
+                .flat_map(|col| self.column(col).cloned())
+                .collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// The grid turned a quarter clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: (0..self.cols)
+                .flat_map(|col| self.column(col).rev().cloned().collect::<Vec<_>>())
+                .collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// The grid turned a quarter counterclockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: (0..self.cols)
+                .rev()
+                .flat_map(|col| self.column(col).cloned())
+                .collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// The grid mirrored left to right, so each row is reversed.
+    #[must_use]
+    pub fn flip_h(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: self
+                .iter_rows()
+                .flat_map(|row| row.iter().rev().cloned())
+                .collect(),
+            ..*self
+        }
+    }
+
+    /// The grid mirrored top to bottom, so the rows come in reverse.
+    #[must_use]
+    pub fn flip_v(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: (0..self.rows)
+                .rev()
+                .flat_map(|row| self.row(row).iter().cloned())
+                .collect(),
+            ..*self
+        }
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    /// Panics outside of the grid.
+    fn index(&self, p: Point2) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, p: Point2) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p} is outside of the grid"))
+    }
+}
+
+/// Parses cells that know which character they are.
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: Into<anyhow::Error>,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, |c| T::try_from(c).map_err(Into::into))
+    }
+}
+
+/// A line per row, like the puzzle input.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grids_work() -> Result<()> {
+        let grid = Grid::<char>::from_str("abc\ndef\n")?;
+
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[Point2::new(1, 2)], 'f');
+        assert_eq!(grid.get(Point2::new(2, 0)), None);
+        assert_eq!(grid.get_or(Point2::new(0, 3), '.'), '.');
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.neighbors(Point2::default()).collect::<Vec<_>>(),
+            [Point2::new(0, 1), Point2::new(1, 0)]
+        );
+        assert_eq!(grid.neighbors8(Point2::new(1, 1)).count(), 5);
+
+        let maze = Grid::<char>::from_str("#.#..\n#.#.#\n##..#\n")?;
+        let fill = maze.flood_fill(Point2::new(0, 1), |_, c| *c == '.');
+        assert_eq!(fill.cells, [Point2::new(0, 1), Point2::new(1, 1)]);
+        assert_eq!(fill.boundary.len(), 5);
+        assert!(fill.touches_edge);
+        assert_eq!(
+            maze.flood_fill8(Point2::new(0, 1), |_, c| *c == '.')
+                .cells
+                .len(),
+            7
+        );
+        assert_eq!(
+            maze.flood_fill(Point2::default(), |_, c| *c == '.'),
+            Region::default()
+        );
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_h().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_v().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.rotate_cw(), grid.transpose().flip_h());
+        assert_eq!(grid.rotate_ccw(), grid.transpose().flip_v());
+        assert_eq!(grid.flip_h().flip_v(), grid.rotate_cw().rotate_cw());
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).row(0), ['A', 'B', 'C']);
+
+        assert!(Grid::<char>::from_str("ab\nc").is_err());
+        assert!(Grid::parse("a.", |c| match c {
+            '.' => Ok(()),
+            _ => bail!("Invalid cell {c}"),
+        })
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+//! Timings of earlier runs, to notice when a refactoring made a day
+//! slower. Stored as TOML next to the answers.
+
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// How the time was measured. Only times measured the same way are
+/// compared.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// A single regular run.
+    Run,
+    /// The median of `bench`.
+    Bench,
+}
+
+/// Debug builds are much slower, so they are tracked separately.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Build {
+    Debug,
+    Release,
+}
+
+impl Build {
+    #[must_use]
+    pub fn current() -> Self {
+        if cfg!(debug_assertions) {
+            Build::Debug
+        } else {
+            Build::Release
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub mode: Mode,
+    pub build: Build,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algo: Option<String>,
+    pub time_ns: u64,
+    /// When the run happened, in seconds since the epoch.
+    pub timestamp: u64,
+}
+
+impl Entry {
+    #[must_use]
+    pub fn new(year: u16, day: u8, mode: Mode, algo: Option<&str>, time: Duration) -> Self {
+        Self {
+            year,
+            day,
+            mode,
+            build: Build::current(),
+            algo: algo.map(str::to_owned),
+            time_ns: u64::try_from(time.as_nanos()).unwrap_or(u64::MAX),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |t| t.as_secs()),
+        }
+    }
+
+    /// Whether both were measured under the same conditions.
+    fn comparable(&self, other: &Entry) -> bool {
+        (self.year, self.day, self.mode, self.build, &self.algo)
+            == (other.year, other.day, other.mode, other.build, &other.algo)
+    }
+}
+
+/// A day got slower than its best recorded time.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Regression {
+    pub best: Duration,
+    /// How much slower, in percent of the best time.
+    pub percent: u64,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "run")]
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Loads the history from `path`. A missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => {
+                toml::from_str(&s).with_context(|| format!("Failed to parse {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The fastest recorded time measured like `entry`.
+    #[must_use]
+    pub fn best(&self, entry: &Entry) -> Option<Duration> {
+        self.entries
+            .iter()
+            .filter(|e| e.comparable(entry))
+            .map(|e| e.time_ns)
+            .min()
+            .map(Duration::from_nanos)
+    }
+
+    /// The most recent time measured like `entry`, as guess how long the
+    /// next run takes.
+    #[must_use]
+    pub fn latest(&self, entry: &Entry) -> Option<Duration> {
+        self.entries
+            .iter()
+            .filter(|e| e.comparable(entry))
+            .max_by_key(|e| e.timestamp)
+            .map(|e| Duration::from_nanos(e.time_ns))
+    }
+
+    /// Adds `entry` and reports whether it is more than `threshold`
+    /// percent slower than the best time so far.
+    pub fn record(&mut self, entry: Entry, threshold: u64) -> Option<Regression> {
+        let regression = self.best(&entry).and_then(|best| {
+            let best_ns = u64::try_from(best.as_nanos()).unwrap_or(u64::MAX).max(1);
+            let percent = entry.time_ns.saturating_sub(best_ns).saturating_mul(100) / best_ns;
+
+            (percent > threshold).then_some(Regression { best, percent })
+        });
+
+        self.entries.push(entry);
+        regression
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, mode: Mode, millis: u64) -> Entry {
+        Entry::new(2023, day, mode, None, Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let mut history = History::default();
+
+        assert_eq!(history.record(entry(1, Mode::Run, 100), 20), None);
+        assert_eq!(history.record(entry(1, Mode::Run, 80), 20), None);
+        assert_eq!(history.record(entry(1, Mode::Run, 96), 20), None);
+        assert_eq!(
+            history.record(entry(1, Mode::Run, 100), 20),
+            Some(Regression {
+                best: Duration::from_millis(80),
+                percent: 25
+            })
+        );
+
+        // Other days and modes have their own best times.
+        assert_eq!(history.record(entry(2, Mode::Run, 500), 20), None);
+        assert_eq!(history.record(entry(1, Mode::Bench, 500), 20), None);
+
+        assert_eq!(
+            history.latest(&entry(1, Mode::Run, 0)),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(history.latest(&entry(3, Mode::Run, 0)), None);
+    }
+
+    #[test]
+    fn history_roundtrips() -> Result<()> {
+        let mut history = History::default();
+        history.record(entry(1, Mode::Run, 100), 20);
+        history.record(
+            Entry::new(2023, 12, Mode::Bench, Some("rle"), Duration::from_micros(3)),
+            20,
+        );
+
+        assert_eq!(
+            toml::from_str::<History>(&toml::to_string(&history)?)?,
+            history
+        );
+
+        Ok(())
+    }
+}
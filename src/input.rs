@@ -0,0 +1,164 @@
+//! Loading puzzle inputs and helpers shared by the solutions for
+//! slicing them up.
+//!
+//! Inputs are personal, so they are read at runtime from
+//! `<dir>/<year>/day<N>.txt` instead of being built into the binary.
+
+use std::{
+    fs,
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::output::Message;
+
+static DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Selects the directory with the inputs. Only the first call has an
+/// effect.
+pub fn set_dir(dir: impl Into<PathBuf>) {
+    let _ = DIR.set(dir.into());
+}
+
+#[must_use]
+pub fn dir() -> &'static Path {
+    DIR.get().map_or(Path::new("inputs"), PathBuf::as_path)
+}
+
+static OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Uses `input` instead of the one from the inputs directory, e.g. a
+/// generated one. Only the first call has an effect.
+pub fn set_override(input: impl Into<String>) {
+    let _ = OVERRIDE.set(input.into());
+}
+
+#[must_use]
+pub fn path(year: u16, day: u8) -> PathBuf {
+    dir().join(year.to_string()).join(format!("day{day}.txt"))
+}
+
+/// The input of a puzzle: the override if there is one, otherwise the
+/// file from the inputs directory.
+pub fn load(year: u16, day: u8) -> Result<String> {
+    if let Some(input) = OVERRIDE.get() {
+        return Ok(input.clone());
+    }
+
+    let path = path(year, day);
+
+    fs::read_to_string(&path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => anyhow!("{}", Message::MissingInput(&path)),
+        _ => anyhow!(e).context(format!("Failed to read {}", path.display())),
+    })
+}
+
+/// Loads an input for tests, which run in the crate root.
+#[cfg(test)]
+pub fn load_for_test(year: u16, day: u8) -> String {
+    load(year, day).unwrap_or_else(|e| panic!("Test needs {}: {e}", path(year, day).display()))
+}
+
+/// Splits `input` into chunks of roughly `target` bytes. Chunks only end
+/// after a newline (or at the end of the input), so every line is in
+/// exactly one chunk and chunks can be processed independently.
+#[must_use]
+pub fn line_chunks(input: &str, target: usize) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        // Bytes, as `target` may fall into a character. A newline always
+        // ends one.
+        let start = target.max(1).min(rest.len());
+        let end = rest.as_bytes()[start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(rest.len(), |newline| start + newline + 1);
+
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+
+    chunks
+}
+
+/// Reads whole lines from a reader in batches of roughly `target` bytes,
+/// like [`line_chunks`] does for inputs that are already in memory.
+pub struct LineBatches<R> {
+    reader: R,
+    target: usize,
+}
+
+impl<R: BufRead> Iterator for LineBatches<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = String::new();
+
+        while batch.len() < self.target.max(1) {
+            match self.reader.read_line(&mut batch) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        (!batch.is_empty()).then_some(Ok(batch))
+    }
+}
+
+/// Splits what `reader` yields into batches of lines, so inputs that
+/// don't fit into memory can be processed a batch at a time.
+pub fn line_batches<R: BufRead>(reader: R, target: usize) -> LineBatches<R> {
+    LineBatches { reader, target }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_end_on_lines() {
+        let input = "a\nbb\nccc\ndddd\n";
+
+        for target in 0..=input.len() + 1 {
+            let chunks = line_chunks(input, target);
+
+            assert_eq!(chunks.concat(), input);
+            assert!(chunks.iter().all(|c| c.ends_with('\n')), "{chunks:?}");
+        }
+
+        assert_eq!(line_chunks(input, 3), ["a\nbb\n", "ccc\n", "dddd\n"]);
+        assert_eq!(line_chunks("no newline", 3), ["no newline"]);
+        assert!(line_chunks("", 3).is_empty());
+
+        // Targets inside of characters still split.
+        let input = "äöü\n€€\nß\n";
+        for target in 0..=input.len() + 1 {
+            let chunks = line_chunks(input, target);
+            assert_eq!(chunks.concat(), input);
+            assert!(chunks.iter().all(|c| c.ends_with('\n')), "{chunks:?}");
+        }
+        assert_eq!(line_chunks(input, 1), ["äöü\n", "€€\n", "ß\n"]);
+        assert_eq!(line_chunks(input, 8), ["äöü\n€€\n", "ß\n"]);
+    }
+
+    #[test]
+    fn batches_end_on_lines() -> io::Result<()> {
+        let input = "a\nbb\nccc\ndddd";
+
+        assert_eq!(
+            line_batches(input.as_bytes(), 3).collect::<io::Result<Vec<_>>>()?,
+            ["a\nbb\n", "ccc\n", "dddd"]
+        );
+        assert_eq!(line_batches(input.as_bytes(), 0).count(), 4);
+        assert_eq!(line_batches("".as_bytes(), 3).count(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Iterator adapters for patterns that puzzles keep coming back to, so
+//! days don't each get the edge cases of grouping and counting wrong.
+
+use std::{cmp::Reverse, hash::Hash};
+
+use itertools::Itertools;
+
+pub trait AocIteratorExt: Iterator + Sized {
+    /// Groups lines into the blocks between blank lines, like the patterns
+    /// of day 13. Lines with only whitespace count as blank, and several
+    /// of them in a row or at either end don't make empty blocks.
+    fn split_on_blank_lines(self) -> SplitOnBlankLines<Self>
+    where
+        Self::Item: AsRef<str>,
+    {
+        SplitOnBlankLines { lines: self }
+    }
+
+    /// How many unordered pairs of different items there are, without
+    /// going through them like `tuple_combinations` does.
+    fn pairwise_combinations_count(self) -> usize {
+        let n = self.count();
+        n * n.saturating_sub(1) / 2
+    }
+
+    /// How often each distinct item comes up, most frequent first, like
+    /// the shape of a poker hand.
+    fn counts_sorted_desc(self) -> Vec<usize>
+    where
+        Self::Item: Eq + Hash,
+    {
+        self.counts()
+            .into_values()
+            .sorted_unstable_by_key(|&n| Reverse(n))
+            .collect()
+    }
+}
+
+impl<I: Iterator> AocIteratorExt for I {}
+
+/// See [`AocIteratorExt::split_on_blank_lines`].
+#[derive(Debug, Clone)]
+pub struct SplitOnBlankLines<I> {
+    lines: I,
+}
+
+impl<I> Iterator for SplitOnBlankLines<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut block = vec![];
+
+        for line in self.lines.by_ref() {
+            if !line.as_ref().trim().is_empty() {
+                block.push(line);
+            } else if !block.is_empty() {
+                return Some(block);
+            }
+        }
+
+        (!block.is_empty()).then_some(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn helpers_work() {
+        let text = "\na\nb\n\n  \nc\n\n";
+        assert_eq!(
+            text.lines().split_on_blank_lines().collect::<Vec<_>>(),
+            [vec!["a", "b"], vec!["c"]]
+        );
+        assert_eq!("".lines().split_on_blank_lines().count(), 0);
+
+        assert_eq!((0..9).pairwise_combinations_count(), 36);
+        assert_eq!([1].iter().pairwise_combinations_count(), 0);
+        assert_eq!(std::iter::empty::<u8>().pairwise_combinations_count(), 0);
+
+        assert_eq!("KTJJT".chars().counts_sorted_desc(), [2, 2, 1]);
+        assert_eq!("AAAAA".chars().counts_sorted_desc(), [5]);
+        assert!("".chars().counts_sorted_desc().is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+//! The answers and timings of the latest run of each part, stored as
+//! TOML:
+//!
+//! ```toml
+//! [2023.13.part1]
+//! answer = "30575"
+//! time_ns = 5512345
+//! ```
+//!
+//! Comparing a run with it shows what a refactoring changed, and
+//! `record` takes answers from it.
+
+use std::{collections::BTreeMap, fs, io, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::summary::Summary;
+
+/// Timings closer than this to the last run count as unchanged, however
+/// many percent apart they are. Short parts vary a lot between runs.
+const NOISE: Duration = Duration::from_millis(1);
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct PartRun {
+    pub answer: String,
+    pub time_ns: u64,
+}
+
+/// TOML keys are always strings, so years, days and parts are kept as
+/// such.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LastRun(BTreeMap<String, BTreeMap<String, BTreeMap<String, PartRun>>>);
+
+/// How a part differs from the last run.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Change {
+    Answer {
+        day: u8,
+        part: u8,
+        before: String,
+        after: String,
+    },
+    /// The part got faster or slower by more than the threshold.
+    Timing {
+        day: u8,
+        part: u8,
+        before: Duration,
+        after: Duration,
+    },
+}
+
+impl LastRun {
+    /// Loads the last run from `path`. A missing file is a run without
+    /// any parts.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => {
+                toml::from_str(&s).with_context(|| format!("Failed to parse {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    #[must_use]
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&PartRun> {
+        self.0
+            .get(&year.to_string())?
+            .get(&day.to_string())?
+            .get(&format!("part{part}"))
+    }
+
+    /// Replaces the parts that `summary` has results for. Other days
+    /// stay as they were.
+    pub fn update(&mut self, summary: &Summary) {
+        let year = self.0.entry(summary.year.to_string()).or_default();
+
+        for day in &summary.days {
+            let parts = year.entry(day.day.to_string()).or_default();
+
+            for part in &day.parts {
+                parts.insert(
+                    format!("part{}", part.part),
+                    PartRun {
+                        answer: part.answer.to_string(),
+                        time_ns: u64::try_from(part.time.as_nanos()).unwrap_or(u64::MAX),
+                    },
+                );
+            }
+        }
+    }
+
+    /// The parts of `summary` with a different answer than last time or
+    /// a time that changed by more than `threshold` percent. Parts that
+    /// didn't run before aren't changes.
+    #[must_use]
+    pub fn diff(&self, summary: &Summary, threshold: u64) -> Vec<Change> {
+        let mut changes = vec![];
+
+        for day in &summary.days {
+            for part in &day.parts {
+                let Some(before) = self.get(summary.year, day.day, part.part) else {
+                    continue;
+                };
+
+                let after = part.answer.to_string();
+                if before.answer != after {
+                    changes.push(Change::Answer {
+                        day: day.day,
+                        part: part.part,
+                        before: before.answer.clone(),
+                        after,
+                    });
+                }
+
+                let before = Duration::from_nanos(before.time_ns);
+                let delta = before.abs_diff(part.time);
+                let before_ns = before.as_nanos().max(1);
+                if delta > NOISE && delta.as_nanos() * 100 / before_ns > u128::from(threshold) {
+                    changes.push(Change::Timing {
+                        day: day.day,
+                        part: part.part,
+                        before,
+                        after: part.time,
+                    });
+                }
+            }
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{results::PartResult, solution::Answer, summary::DaySummary};
+
+    fn summary(parts: &[(u8, u64, u64)]) -> Summary {
+        let mut summary = Summary::new(2023);
+        summary.push(DaySummary {
+            day: 1,
+            parts: parts
+                .iter()
+                .map(|&(part, answer, millis)| PartResult {
+                    part,
+                    answer: Answer::UInt(answer),
+                    time: Duration::from_millis(millis),
+                })
+                .collect(),
+            parse_time: None,
+            stats: None,
+            error: None,
+        });
+        summary
+    }
+
+    #[test]
+    fn finds_changes() -> Result<()> {
+        let mut last_run = LastRun::default();
+        last_run.update(&summary(&[(1, 142, 100), (2, 281, 1)]));
+        assert_eq!(
+            toml::from_str::<LastRun>(&toml::to_string(&last_run)?)?,
+            last_run
+        );
+
+        // Part 2 is three times slower, but by less than the noise.
+        assert_eq!(
+            last_run.diff(&summary(&[(1, 142, 120), (2, 281, 2)]), 25),
+            []
+        );
+        assert_eq!(
+            last_run.diff(&summary(&[(1, 143, 50)]), 25),
+            [
+                Change::Answer {
+                    day: 1,
+                    part: 1,
+                    before: "142".to_owned(),
+                    after: "143".to_owned(),
+                },
+                Change::Timing {
+                    day: 1,
+                    part: 1,
+                    before: Duration::from_millis(100),
+                    after: Duration::from_millis(50),
+                }
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+// Answers have to be bit-identical across platforms, so solver cores
+// stick to integer arithmetic. See `util` for the helpers.
+#![deny(clippy::float_arithmetic)]
+
+pub mod answers;
+pub mod aoc2023;
+pub mod baseline;
+pub mod bench;
+pub mod bitgrid;
+pub mod cancel;
+pub mod chart;
+pub mod cycle;
+pub mod dump;
+pub mod error;
+pub mod events;
+#[cfg(test)]
+pub mod example;
+pub mod fetch;
+pub mod gen;
+pub mod geometry;
+pub mod graph;
+pub mod graphviz;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod iter;
+pub mod last_run;
+pub mod linalg;
+pub mod lint;
+pub mod man;
+pub mod math;
+pub mod memo;
+pub mod output;
+pub mod parallel;
+pub mod parse;
+pub mod profile;
+pub mod puzzle;
+pub mod rect;
+pub mod registry;
+pub mod report;
+pub mod results;
+pub mod rpc;
+pub mod scaffold;
+pub mod serve;
+pub mod shrink;
+pub mod solution;
+pub mod sparse_grid;
+pub mod stars;
+pub mod status;
+pub mod submit;
+pub mod summary;
+pub mod template;
+#[cfg(test)]
+pub mod testutil;
+pub mod tool;
+pub mod union_find;
+pub mod util;
+pub mod viz;
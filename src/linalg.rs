@@ -0,0 +1,199 @@
+//! Solving small systems of linear equations with integer coefficients
+//! exactly, like the ones for the position and velocity of a rock that
+//! hits a set of hailstones. Floats lose the last digits of answers in
+//! the hundreds of trillions, so this stays with integers throughout.
+
+use std::fmt::{self, Display};
+
+use num_integer::Integer;
+
+/// A fraction in lowest terms with a positive denominator.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Ratio {
+    numer: i128,
+    denom: i128,
+}
+
+impl Ratio {
+    /// Panics if `denom` is zero.
+    #[must_use]
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "{numer}/0 is not a number");
+
+        let gcd = numer.gcd(&denom) * denom.signum();
+        Self {
+            numer: numer / gcd,
+            denom: denom / gcd,
+        }
+    }
+
+    #[must_use]
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    #[must_use]
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    /// The value if it is a whole number.
+    #[must_use]
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denom == 1).then_some(self.numer)
+    }
+}
+
+impl From<i128> for Ratio {
+    fn from(n: i128) -> Self {
+        Self { numer: n, denom: 1 }
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/// Brings the square matrix `m` with `extra` columns on its right into
+/// diagonal form by fraction-free Gauss-Jordan elimination (Bareiss). All
+/// divisions are exact and the entries stay as small as the minors of
+/// `m`, but the products before a division are larger than that. Panics
+/// if they don't fit into an `i128`.
+///
+/// Returns the determinant, which each diagonal entry ends up equal to,
+/// or `None` if `m` is singular.
+fn eliminate(m: &mut [Vec<i128>], extra: usize) -> Option<i128> {
+    let n = m.len();
+    assert!(
+        m.iter().all(|row| row.len() == n + extra),
+        "the matrix has to be square"
+    );
+
+    let mut sign = 1;
+    let mut previous = 1;
+
+    for k in 0..n {
+        let pivot = (k..n).find(|&i| m[i][k] != 0)?;
+        if pivot != k {
+            m.swap(pivot, k);
+            sign = -sign;
+        }
+
+        for i in (0..n).filter(|&i| i != k) {
+            for j in (0..n + extra).filter(|&j| j != k) {
+                m[i][j] = m[k][k]
+                    .checked_mul(m[i][j])
+                    .zip(m[i][k].checked_mul(m[k][j]))
+                    .and_then(|(a, b)| a.checked_sub(b))
+                    .expect("coefficients too large for exact elimination")
+                    / previous;
+            }
+            m[i][k] = 0;
+        }
+
+        previous = m[k][k];
+    }
+
+    Some(sign * previous)
+}
+
+/// The determinant of the square matrix `a`. Panics if its products
+/// overflow, like [`solve`].
+#[must_use]
+pub fn determinant(a: &[Vec<i128>]) -> i128 {
+    eliminate(&mut a.to_vec(), 0).unwrap_or(0)
+}
+
+/// The `x` with `a x = b` for a square matrix `a`, or `None` if there
+/// isn't exactly one. Panics if the products during elimination overflow
+/// an `i128`, so large coefficients should be reduced first, e.g. by
+/// subtracting one equation from the others.
+#[must_use]
+pub fn solve(a: &[Vec<i128>], b: &[i128]) -> Option<Vec<Ratio>> {
+    assert_eq!(a.len(), b.len(), "there has to be a value per equation");
+
+    let mut m = a
+        .iter()
+        .zip(b)
+        .map(|(row, &value)| row.iter().copied().chain([value]).collect())
+        .collect::<Vec<Vec<_>>>();
+    eliminate(&mut m, 1)?;
+
+    let n = m.len();
+    Some(
+        m.iter()
+            .enumerate()
+            .map(|(i, row)| Ratio::new(row[n], row[i]))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng as _;
+
+    use super::*;
+    use crate::gen;
+
+    #[test]
+    fn ratios_are_normalized() {
+        assert_eq!(Ratio::new(6, -4), Ratio::new(-3, 2));
+        assert_eq!(Ratio::new(0, -5), Ratio::from(0));
+        assert_eq!(Ratio::new(-8, -2).to_integer(), Some(4));
+        assert_eq!(Ratio::new(1, 3).to_integer(), None);
+        assert_eq!(Ratio::new(3, -9).to_string(), "-1/3");
+    }
+
+    #[test]
+    fn systems_are_solved() {
+        // x + 2y = 5, 3x - y = 1
+        let a = [vec![1, 2], vec![3, -1]];
+        assert_eq!(
+            solve(&a, &[5, 1]),
+            Some(vec![Ratio::from(1), Ratio::from(2)])
+        );
+        assert_eq!(determinant(&a), -7);
+
+        // The first pivot is zero and the answer isn't whole.
+        let a = [vec![0, 2, 1], vec![1, 1, 0], vec![2, 0, 3]];
+        assert_eq!(
+            solve(&a, &[1, 0, 0]),
+            Some(vec![Ratio::new(-3, 8), Ratio::new(3, 8), Ratio::new(1, 4)])
+        );
+        assert_eq!(determinant(&a), -8);
+
+        let singular = [vec![1, 2], vec![2, 4]];
+        assert_eq!(solve(&singular, &[3, 6]), None);
+        assert_eq!(determinant(&singular), 0);
+    }
+
+    #[test]
+    fn large_systems_are_exact() {
+        let mut rng = gen::rng(24);
+
+        for _ in 0..100 {
+            let x = (0..6)
+                .map(|_| rng.gen_range(-1_000_000_000_000..1_000_000_000_000))
+                .collect::<Vec<i128>>();
+            let a = (0..6)
+                .map(|_| (0..6).map(|_| rng.gen_range(-100..100)).collect())
+                .collect::<Vec<Vec<i128>>>();
+            let b = a
+                .iter()
+                .map(|row| row.iter().zip(&x).map(|(a, x)| a * x).sum())
+                .collect::<Vec<_>>();
+
+            let solution = solve(&a, &b).expect("random matrices aren't singular");
+            assert_eq!(
+                solution.iter().map(Ratio::to_integer).collect::<Vec<_>>(),
+                x.into_iter().map(Some).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Checks inputs against the format a day expects and points at the line
+//! and column where they deviate, instead of just failing to parse.
+
+use std::fmt::Display;
+
+/// A problem in an input. Lines and columns count from 1.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// A problem within a single line.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LineError {
+    column: usize,
+    message: String,
+}
+
+/// Walks through a line from left to right. Each step either consumes
+/// what it expects or reports the column where it didn't find it.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    #[must_use]
+    pub fn new(line: &'a str) -> Self {
+        Self { line, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.line[..self.pos].chars().count() + 1
+    }
+
+    #[must_use]
+    pub fn error(&self, message: impl Display) -> LineError {
+        LineError {
+            column: self.column(),
+            message: message.to_string(),
+        }
+    }
+
+    /// What comes next, for error messages.
+    fn found(&self) -> String {
+        self.rest()
+            .chars()
+            .next()
+            .map_or_else(|| "end of line".to_owned(), |c| format!("{c:?}"))
+    }
+
+    #[must_use]
+    pub fn is_at_end(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    pub fn end(&self) -> Result<(), LineError> {
+        if self.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected end of line, got {}", self.found())))
+        }
+    }
+
+    /// Consumes `literal` if the line continues with it.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.pos += literal.len();
+        }
+
+        found
+    }
+
+    pub fn literal(&mut self, literal: &str) -> Result<(), LineError> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected {literal:?}, got {}", self.found())))
+        }
+    }
+
+    pub fn one_of<'b>(&mut self, options: &[&'b str]) -> Result<&'b str, LineError> {
+        options
+            .iter()
+            .copied()
+            .find(|o| self.eat(o))
+            .ok_or_else(|| self.error(format!("Expected one of {options:?}, got {}", self.found())))
+    }
+
+    /// Consumes at least one space.
+    pub fn spaces(&mut self) -> Result<(), LineError> {
+        self.many(|c| c == ' ', "a space").map(|_| ())
+    }
+
+    /// Consumes at least one character for which `allowed` holds and
+    /// returns them.
+    pub fn many(
+        &mut self,
+        allowed: impl Fn(char) -> bool,
+        what: &str,
+    ) -> Result<&'a str, LineError> {
+        let len = self
+            .rest()
+            .find(|c| !allowed(c))
+            .unwrap_or(self.rest().len());
+
+        if len == 0 {
+            return Err(self.error(format!("Expected {what}, got {}", self.found())));
+        }
+
+        let taken = &self.rest()[..len];
+        self.pos += len;
+
+        Ok(taken)
+    }
+
+    /// Consumes the rest of the line, which must not be empty and may
+    /// only consist of characters for which `allowed` holds.
+    pub fn only(&mut self, allowed: impl Fn(char) -> bool) -> Result<&'a str, LineError> {
+        if self.is_at_end() {
+            return Err(self.error("Unexpected end of line"));
+        }
+
+        if let Some(bad) = self.rest().find(|c| !allowed(c)) {
+            self.pos += bad;
+
+            return Err(self.error(format!("Unexpected character {}", self.found())));
+        }
+
+        let taken = self.rest();
+        self.pos = self.line.len();
+
+        Ok(taken)
+    }
+
+    pub fn number(&mut self) -> Result<u64, LineError> {
+        let start = self.clone();
+        let digits = self.many(|c| c.is_ascii_digit(), "a number")?;
+
+        digits
+            .parse()
+            .map_err(|_| start.error(format!("Number too large: {digits}")))
+    }
+
+    /// A number with an optional minus sign.
+    pub fn integer(&mut self) -> Result<i64, LineError> {
+        let start = self.clone();
+        let negative = self.eat("-");
+        let digits = self.many(|c| c.is_ascii_digit(), "a number")?;
+
+        digits
+            .parse::<i64>()
+            .map(|n| if negative { -n } else { n })
+            .map_err(|_| start.error(format!("Number too large: {digits}")))
+    }
+}
+
+/// Collects the diagnostics for a whole input.
+#[derive(Debug, Default)]
+pub struct Linter {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Linter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn error(&mut self, line: usize, column: usize, message: impl Display) {
+        self.diagnostics.push(Diagnostic {
+            line,
+            column,
+            message: message.to_string(),
+        });
+    }
+
+    /// Checks line number `number` with `check`. Returns what `check`
+    /// found if the line is fine.
+    pub fn line<'a, T>(
+        &mut self,
+        (number, line): (usize, &'a str),
+        check: impl FnOnce(&mut Cursor<'a>) -> Result<T, LineError>,
+    ) -> Option<T> {
+        check(&mut Cursor::new(line))
+            .map_err(|e| self.error(number, e.column, e.message))
+            .ok()
+    }
+
+    /// Checks that `lines` form a rectangle of characters for which
+    /// `allowed` holds.
+    pub fn grid(&mut self, lines: &[(usize, &str)], allowed: impl Fn(char) -> bool) {
+        let Some(width) = lines.first().map(|(_, l)| l.chars().count()) else {
+            return;
+        };
+
+        for line in lines {
+            self.line(*line, |c| {
+                let row = c.only(&allowed)?.chars().count();
+                if row == width {
+                    Ok(())
+                } else {
+                    Err(c.error(format!(
+                        "Row has {row} characters, but the first one has {width}"
+                    )))
+                }
+            });
+        }
+    }
+
+    #[must_use]
+    pub fn finish(self) -> Vec<Diagnostic> {
+        self.diagnostics
+    }
+}
+
+/// The lines of `input` with their numbers. An empty input is reported
+/// right away.
+pub fn lines<'a>(linter: &mut Linter, input: &'a str) -> Vec<(usize, &'a str)> {
+    if input.trim().is_empty() {
+        linter.error(1, 1, "The input is empty");
+    }
+
+    input.lines().enumerate().map(|(i, l)| (i + 1, l)).collect()
+}
+
+/// Splits numbered lines into blocks separated by empty lines.
+#[must_use]
+pub fn blocks<'a>(lines: &[(usize, &'a str)]) -> Vec<Vec<(usize, &'a str)>> {
+    lines
+        .split(|(_, l)| l.is_empty())
+        .filter(|b| !b.is_empty())
+        .map(<[_]>::to_vec)
+        .collect()
+}
+
+/// Checks every line of `input` with `check`, for inputs that are just
+/// a list of similar lines.
+pub fn each_line(
+    input: &str,
+    check: impl Fn(&mut Cursor) -> Result<(), LineError>,
+) -> Vec<Diagnostic> {
+    let mut linter = Linter::new();
+
+    for line in lines(&mut linter, input) {
+        linter.line(line, &check);
+    }
+
+    linter.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_reports_columns() {
+        let mut c = Cursor::new("Card  1: 41 x");
+
+        assert_eq!(c.literal("Card"), Ok(()));
+        assert_eq!(c.spaces(), Ok(()));
+        assert_eq!(c.number(), Ok(1));
+        assert!(c.eat(": "));
+        assert_eq!(c.integer(), Ok(41));
+        assert_eq!(c.spaces(), Ok(()));
+        assert_eq!(c.number().map_err(|e| e.column), Err(13));
+        assert_eq!(c.one_of(&["y", "x"]), Ok("x"));
+        assert_eq!(c.end(), Ok(()));
+
+        assert_eq!(
+            Cursor::new("..#ä.").only(|c| ".#".contains(c)),
+            Err(LineError {
+                column: 4,
+                message: "Unexpected character 'ä'".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn finds_ragged_grids() {
+        let mut linter = Linter::new();
+        let lines = lines(&mut linter, "..#\n.#.\n..\n\n#..\n");
+        let blocks = blocks(&lines);
+
+        assert_eq!(blocks.len(), 2);
+        linter.grid(&blocks[0], |c| ".#".contains(c));
+        linter.grid(&blocks[1], |c| ".#".contains(c));
+
+        assert_eq!(
+            linter
+                .finish()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["3:3: Row has 2 characters, but the first one has 3"]
+        );
+
+        assert_eq!(
+            each_line("", |_| Ok(())),
+            [Diagnostic {
+                line: 1,
+                column: 1,
+                message: "The input is empty".to_owned()
+            }]
+        );
+    }
+}
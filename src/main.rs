@@ -1,62 +1,1387 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-use anyhow::{bail, Result};
-use clap::Parser;
-use colored::Colorize;
+use std::{
+    fs,
+    io::{self, Write},
+    iter, panic,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use aoc2023::{
+    answers::Answers,
+    baseline::{self, Baselines, PartTime},
+    bench, cancel, dump,
+    events::{self, Event},
+    fetch, gen,
+    history::{Entry, History, Mode},
+    input,
+    last_run::LastRun,
+    man,
+    output::{self, Lang, Message},
+    puzzle,
+    registry::{self, Puzzle},
+    report::{self, DayResults},
+    results::{self, PartResult, Recorder},
+    rpc, scaffold, serve,
+    solution::{self, ExplainQuery},
+    stars::{Calendar, Progress},
+    status,
+    submit::{self, Guesses, Verdict},
+    summary::{self, DaySummary, Summary},
+    tool,
+    viz::Animation,
+};
+use clap::{CommandFactory, Parser, Subcommand};
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true
+)]
 struct Args {
-    /// Selects the solution to run.
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Without a subcommand, `aoc2023 7` is short for `aoc2023 run 7`.
+    #[command(flatten)]
+    run: RunArgs,
+
+    /// Selects the event. Defaults to the most recent one.
+    #[arg(long, global = true)]
+    year: Option<u16>,
+
+    /// Gives up on a day after this many seconds and reports it as timed out.
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Selects an alternative algorithm for days that have one, e.g. `lcm`
+    /// for day 8. The other days use their default.
+    #[arg(long, global = true)]
+    algo: Option<String>,
+
+    /// Directory with the puzzle inputs, as `<year>/day<N>.txt`.
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        env = "AOC_INPUT_DIR",
+        default_value = "inputs"
+    )]
+    input_dir: PathBuf,
+
+    /// Reads the puzzle input from this file instead of the input
+    /// directory. Needs a single day to be selected.
+    #[arg(long, global = true, value_name = "FILE")]
+    input: Option<PathBuf>,
+
+    /// File with the known correct answers.
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        default_value = "answers.toml"
+    )]
+    answers: PathBuf,
+
+    /// File with the answers and timings of the latest run of each day,
+    /// for `run --diff` and `record --from-last-run`.
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        default_value = "last-run.toml"
+    )]
+    last_run: PathBuf,
+
+    /// Language of the output: en or de.
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_LANG",
+        default_value = "en",
+        value_parser = Lang::from_str
+    )]
+    lang: Lang,
+
+    /// Prints what the run found out at the end, as table, json, html or
+    /// csv. The formats other than table replace the regular output.
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        value_parser = summary::Format::from_str
+    )]
+    summary: Option<summary::Format>,
+
+    /// Writes what the run is doing as JSON lines to stderr, e.g.
+    /// `{"event":"part_started","day":12,"part":2}`.
+    #[arg(long, global = true)]
+    progress_json: bool,
+
+    /// Reads requests like `{"day":5,"part":2,"input":"..."}` as JSON
+    /// lines from stdin and writes the answers to stdout, until stdin
+    /// ends.
+    #[arg(long)]
+    rpc: bool,
+
+    /// Writes man pages for the program and its subcommands into the
+    /// given directory.
+    #[arg(long, value_name = "DIR")]
+    generate_man: Option<PathBuf>,
+}
+
+impl Args {
+    /// The day the command works on, if it is about a single one.
+    fn single_day(&self) -> Option<u8> {
+        match &self.command {
+            None => self.run.day,
+            Some(Command::Run(run)) => run.day,
+            Some(Command::Bench(bench)) => bench.day,
+            Some(Command::Verify { day }) => *day,
+            Some(Command::Explain { day, .. } | Command::LintInput { day, .. }) => Some(*day),
+            Some(_) => None,
+        }
+    }
+
+    /// Whether stdout is reserved for output meant for programs.
+    fn machine_output(&self) -> bool {
+        matches!(
+            self.command,
+            Some(Command::Bench(BenchArgs { json: true, .. }))
+        ) || self
+            .summary
+            .is_some_and(summary::Format::is_machine_readable)
+    }
+}
+
+/// Where the timings of runs go and when they count as regression.
+#[derive(clap::Args, Debug)]
+struct HistoryArgs {
+    /// File with the timings of earlier runs.
+    #[arg(long, value_name = "FILE", default_value = "history.toml")]
+    history: PathBuf,
+
+    /// Doesn't compare with or add to the timing history.
+    #[arg(long)]
+    no_history: bool,
+
+    /// Warns when a day is more than this many percent slower than its
+    /// best recorded time.
+    #[arg(long, value_name = "PERCENT", default_value_t = 20)]
+    regression: u64,
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    /// Selects the solution to run. Runs all days by default.
     day: Option<u8>,
+
+    /// Writes a flamegraph of each part of each day as SVG into the given
+    /// directory.
+    #[arg(
+        long,
+        value_name = "DIR",
+        num_args = 0..=1,
+        default_missing_value = "profiles"
+    )]
+    profile: Option<PathBuf>,
+
+    /// Writes intermediate state of the day as CSV or JSON, depending on
+    /// the extension. Needs a single day to be selected.
+    #[arg(long, value_name = "FILE")]
+    dump_state: Option<PathBuf>,
+
+    /// Draws the grid of the day into a PNG or SVG file, depending on the
+    /// extension. Only SVGs have labels. Needs a single day to be
+    /// selected.
+    #[arg(long, value_name = "FILE")]
+    render: Option<PathBuf>,
+
+    /// Writes the frames the day draws while solving into an animated
+    /// GIF. Needs a single day to be selected, and isn't timed.
+    #[arg(long, value_name = "FILE")]
+    animate: Option<PathBuf>,
+
+    /// Prints the answers of each day as they come instead of a table at
+    /// the end when running several days.
+    #[arg(long)]
+    stream: bool,
+
+    /// Compares answers and timings with the last run and shows what
+    /// changed.
+    #[arg(long)]
+    diff: bool,
+
+    /// How many percent faster or slower than in the last run a part has
+    /// to get to show up in --diff.
+    #[arg(long, value_name = "PERCENT", default_value_t = 25)]
+    diff_threshold: u64,
+
+    /// Leaves out these days, e.g. `--skip 10,12` for the slow ones.
+    #[arg(long, value_name = "DAYS", value_delimiter = ',')]
+    skip: Vec<u8>,
+
+    /// Skips days that took longer than what is left of this many
+    /// seconds in their latest recorded run. Days without history always
+    /// run.
+    #[arg(long, value_name = "SECS")]
+    budget: Option<u64>,
+
+    #[command(flatten)]
+    history: HistoryArgs,
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+#[derive(clap::Args, Debug)]
+struct BenchArgs {
+    /// Selects the solution to measure. Measures all days by default.
+    day: Option<u8>,
+
+    /// Number of measured runs per day.
+    #[arg(long, default_value_t = 10)]
+    iterations: usize,
+
+    /// Number of unmeasured runs per day before measuring.
+    #[arg(long, default_value_t = 1)]
+    warmup: usize,
+
+    /// Prints the statistics of all days as JSON instead.
+    #[arg(long)]
+    json: bool,
+
+    /// Stores the median time of each part as baseline with this name.
+    #[arg(long, value_name = "NAME")]
+    save_baseline: Option<String>,
+
+    /// Fails if a part is slower than in the baseline with this name.
+    #[arg(long, value_name = "NAME")]
+    compare: Option<String>,
+
+    /// How many percent slower than the baseline a part may get.
+    #[arg(long, value_name = "PERCENT", default_value_t = 10)]
+    threshold: u64,
+
+    /// File with the baselines.
+    #[arg(long, value_name = "FILE", default_value = "baselines.toml")]
+    baselines: PathBuf,
+
+    #[command(flatten)]
+    history: HistoryArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Runs the solutions of one or all days.
+    Run(RunArgs),
+
+    /// Measures how long each solution takes instead of just running it
+    /// once.
+    Bench(BenchArgs),
+
+    /// Runs the reference solvers of each day as well and fails if they
+    /// disagree with the optimized ones.
+    Verify {
+        /// Selects the day to verify. Verifies all days by default.
+        day: Option<u8>,
+    },
+
+    /// Downloads puzzle inputs into the input directory. Inputs that are
+    /// already there are kept.
+    Fetch {
+        /// Selects the day to download. Downloads all solved days by
+        /// default.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// The session cookie of a browser logged in to Advent of Code.
+        #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+        session: String,
+    },
+
+    /// Shows the description of a puzzle. It is kept in the input
+    /// directory and downloaded again for as long as it lacks part 2.
+    Puzzle {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// The session cookie of a browser logged in to Advent of Code.
+        /// Part 2 is only shown to those who solved part 1.
+        #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+        session: Option<String>,
+    },
+
+    /// Submits an answer, unless earlier guesses show that it is wrong
+    /// or the website still wants us to wait.
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// The answer to submit. Runs the solution of the day by default.
+        answer: Option<String>,
+
+        /// The session cookie of a browser logged in to Advent of Code.
+        #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+        session: String,
+
+        /// File with the answers submitted so far.
+        #[arg(long, value_name = "FILE", default_value = "guesses.toml")]
+        guesses: PathBuf,
+    },
+
+    /// Stores answers the website accepted as the correct ones.
+    Record {
+        /// The day of the answer. All days of the last run by default.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// The part of the answer. Both parts by default.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        #[arg(requires_all = ["day", "part"])]
+        answer: Option<String>,
+
+        /// Takes the answers from the last run instead.
+        #[arg(long, conflicts_with = "answer", required_unless_present = "answer")]
+        from_last_run: bool,
+    },
+
+    /// Starts a new day: writes a skeleton solution and registers it.
+    /// Run it in the repository.
+    Scaffold {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+
+    /// Prints diagnostics about a day's input and how it is solved.
+    Explain {
+        day: u8,
+
+        /// What to explain, as key=value pairs. Depends on the day.
+        params: Vec<String>,
+    },
+
+    /// Shows which parts are solved correctly, like the calendar on the
+    /// Advent of Code website.
+    Stars {
+        /// Also writes the calendar as HTML page.
+        #[arg(long, value_name = "FILE")]
+        html: Option<PathBuf>,
+
+        /// Stores answers that can't be verified yet as the correct ones.
+        #[arg(long)]
+        accept: bool,
+    },
+
+    /// Shows which days have part 1 or both parts solved, going by the
+    /// registered solutions and recorded answers. Runs nothing.
+    Calendar,
+
+    /// Generates a random but valid input for a day, to see how the
+    /// solution scales.
+    Gen {
+        day: u8,
+
+        /// How big the input gets. Usually the number of lines.
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+
+        /// The same seed always gives the same input.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Writes the input to this file instead of stdout.
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Compares the solvers with their references on the input, like
+        /// `verify`, instead of printing it. If they disagree, the input is shrunk
+        /// and the reproducer written to --output or dayN.shrunk.txt.
+        #[arg(long)]
+        check: bool,
+
+        /// Tunes the generator, as key=value pairs. Depends on the day.
+        params: Vec<String>,
+    },
+
+    /// Shows which days are implemented, registered and tested.
+    List,
+
+    /// Runs all days and lists answers and timings of each part.
+    Report {
+        /// Also writes the results as HTML page with a timing chart.
+        #[arg(long, value_name = "FILE")]
+        html: Option<PathBuf>,
+    },
+
+    /// Checks that an input file has the format the day expects.
+    LintInput {
+        day: u8,
+
+        /// The file to check. Defaults to the day's input.
+        file: Option<PathBuf>,
+    },
+
+    /// Serves the solutions over HTTP: `POST /solve/DAY/PART` with the
+    /// input as body answers with JSON.
+    Serve {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+
+    /// Runs a utility for investigating a single day. See `tool --help`
+    /// for the list.
+    #[command(disable_help_flag = true)]
+    Tool {
+        /// The tool and its arguments.
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
+
+/// How long each day may take when collecting results for stars or
+/// reports, unless `--timeout` says otherwise.
+const COLLECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Quietly runs all days of `year` and returns their results.
+fn collect_results(args: &Args, year: u16) -> Vec<DayResults> {
+    let timeout = args.timeout.map_or(COLLECT_TIMEOUT, Duration::from_secs);
+
+    output::set_quiet(true);
+    let days = registry::year(year)
+        .iter()
+        .map(|puzzle| DayResults {
+            day: puzzle.day,
+            parts: results::collect(puzzle, timeout),
+            breakdown: report::breakdown(puzzle),
+        })
+        .collect();
+    output::set_quiet(false);
+
+    days
+}
+
+/// How large `--render` scales pictures up to, in pixels.
+const RENDER_SIZE: usize = 1000;
+
+/// How large `--animate` scales frames up to, in pixels. Animations have
+/// a lot of pixels already.
+const ANIMATION_SIZE: usize = 500;
+
+/// How long `--animate` shows each frame.
+const FRAME_DELAY: Duration = Duration::from_millis(40);
+
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn explain(year: u16, day: u8, params: &[String]) -> Result<()> {
+    let puzzle = registry::find(year, day).ok_or_else(|| anyhow!("{}", Message::InvalidDay))?;
+    let query = ExplainQuery::from_str(&params.join(" "))?;
+
+    output::say(Message::Explanation(&(puzzle.explain)(
+        &puzzle.input()?,
+        &query,
+    )?));
+
+    Ok(())
+}
+
+/// Tools are registered by the days, so their arguments are only known
+/// at runtime and parsed separately.
+fn tool(year: u16, args: &[String]) -> Result<()> {
+    let matches = tool::command(year)
+        .get_matches_from(iter::once("tool").chain(args.iter().map(String::as_str)));
+
+    output::say(Message::Explanation(&tool::run(year, &matches)?));
+
+    Ok(())
+}
+
+/// Returns whether the input is fine.
+fn lint_input(args: &Args, year: u16, day: u8, file: Option<&Path>) -> Result<bool> {
+    let puzzle = registry::find(year, day).ok_or_else(|| anyhow!("{}", Message::InvalidDay))?;
+    let (path, input) = match file.or(args.input.as_deref()) {
+        Some(path) => (
+            path.to_owned(),
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?,
+        ),
+        None => (input::path(year, day), puzzle.input()?),
+    };
+
+    let diagnostics = (puzzle.lint)(&input);
+    for diagnostic in &diagnostics {
+        output::say(Message::LintProblem {
+            path: &path,
+            diagnostic,
+        });
+    }
+    output::say(Message::LintSummary {
+        path: &path,
+        problems: diagnostics.len(),
+    });
+
+    Ok(diagnostics.is_empty())
+}
+
+fn generate(year: u16, day: u8, size: usize, seed: u64, output: Option<&Path>) -> Result<()> {
+    let puzzle = registry::find(year, day).ok_or_else(|| anyhow!("{}", Message::InvalidDay))?;
+    let input = (puzzle.generate)(&mut gen::rng(seed), size)?;
+
+    match output {
+        Some(path) => write_file(path, &input),
+        None => io::stdout()
+            .write_all(input.as_bytes())
+            .context("Failed to write the input"),
+    }
+}
+
+/// Runs `--check` on a generated input and shrinks it if that fails.
+fn check_generated(
+    year: u16,
+    day: u8,
+    size: usize,
+    seed: u64,
+    output: Option<&Path>,
+) -> Result<()> {
+    let puzzle = registry::find(year, day).ok_or_else(|| anyhow!("{}", Message::InvalidDay))?;
+    let input = (puzzle.generate)(&mut gen::rng(seed), size)?;
+
+    if !(puzzle.disagrees)(&input) {
+        return (puzzle.check)(&input);
+    }
+
+    let shrunk = (puzzle.shrink_input)(&input, &mut |i| (puzzle.disagrees)(i));
+    let path = output.map_or_else(
+        || PathBuf::from(format!("day{day}.shrunk.txt")),
+        Path::to_owned,
+    );
+    write_file(&path, &shrunk)?;
+
+    output::say(Message::Shrunk {
+        path: &path,
+        from: input.lines().count(),
+        to: shrunk.lines().count(),
+    });
+
+    (puzzle.check)(&shrunk)
+}
+
+fn list(year: u16) {
+    output::say(Message::StatusHeader);
+    for day in status::days(year) {
+        output::say(Message::DayStatus(&day));
+    }
+}
+
+fn stars(args: &Args, year: u16, html: Option<&Path>, accept: bool) -> Result<()> {
+    let mut known = Answers::load(&args.answers)?;
+    let produced = collect_results(args, year)
+        .into_iter()
+        .flat_map(|d| d.parts.into_iter().map(move |p| (d.day, p.part, p.answer)))
+        .collect::<Vec<_>>();
+
+    if accept {
+        let mut accepted = 0;
+
+        for (day, part, answer) in &produced {
+            if known.get(year, *day, *part).is_none() {
+                known.set(year, *day, *part, answer.to_string())?;
+                accepted += 1;
+            }
+        }
+
+        known.save(&args.answers)?;
+        output::say(Message::AcceptedAnswers(accepted));
+    }
+
+    let progress = Progress::new(year, &produced, &known);
+
+    println!("{}", progress.render_terminal());
+    output::say(Message::StarLegend);
+    output::say(Message::StarCount {
+        year,
+        stars: progress.stars(),
+        max: progress.max_stars(),
+    });
+
+    if let Some(html) = html {
+        write_file(html, &progress.render_html()?)?;
+    }
+
+    Ok(())
+}
+
+fn report(args: &Args, year: u16, html: Option<&Path>) -> Result<()> {
+    let days = collect_results(args, year);
+
+    for day in &days {
+        for part in &day.parts {
+            output::say(Message::PartTiming {
+                day: day.day,
+                part: part.part,
+                answer: &part.answer,
+                time: part.time,
+            });
+        }
+    }
+
+    if let Some(html) = html {
+        write_file(html, &report::render_html(year, &days)?)?;
+    }
+
+    Ok(())
+}
+
+/// Runs a day's `solve` or `check` and turns panics and timeouts into
+/// errors, so one broken day doesn't take down the whole run. The
+/// results of its parts go to `recorder`, if there is one.
+fn run_day(
+    day: u8,
+    solve: impl FnOnce(&str) -> Result<()> + Send + panic::UnwindSafe + 'static,
+    input: &Arc<str>,
+    timeout: Option<Duration>,
+    recorder: Option<&Recorder>,
+    animation: Option<&Animation>,
+) -> Result<()> {
+    let input = Arc::clone(input);
+    let recorder = recorder.cloned();
+    let animation = animation.cloned();
+    let run = move || {
+        events::attach(day);
+        if let Some(recorder) = recorder {
+            recorder.attach();
+        }
+        if let Some(animation) = animation {
+            animation.attach();
+        }
+        solve(&input)
+    };
+
+    panic::catch_unwind(move || match timeout {
+        Some(limit) => cancel::run_with_timeout(limit, run),
+        None => run(),
+    })
+    .unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".to_owned());
+
+        Err(anyhow!("panicked: {message}"))
+    })
+}
 
-    let solutions: Vec<fn() -> Result<()>> = vec![
-        day1::solve,
-        day2::solve,
-        day3::solve,
-        day4::solve,
-        day5::solve,
-        day6::solve,
-        day7::solve,
-        day8::solve,
-        day9::solve,
-        day10::solve,
-        day11::solve,
-        day12::solve,
-        day13::solve,
-    ];
-
-    println!("🎄 Advent of Code 2023 🎄");
-
-    if let Some(day) = args.day {
-        if let Some(f) = solutions.get(usize::from(day - 1)) {
-            println!("🎅 Running Day {} ...", day.to_string().bold());
-            f()
+/// The median time of each part over the measured runs, leaving out
+/// the `warmup` runs that came first.
+fn part_times(puzzle: &Puzzle, results: &[PartResult], warmup: usize) -> Vec<PartTime> {
+    (1..=2)
+        .filter_map(|part| {
+            let samples = results
+                .iter()
+                .filter(|r| r.part == part)
+                .skip(warmup)
+                .map(|r| r.time)
+                .collect::<Vec<_>>();
+
+            (!samples.is_empty()).then(|| {
+                PartTime::new(
+                    puzzle.year,
+                    puzzle.day,
+                    part,
+                    bench::summarize(&samples).median,
+                )
+            })
+        })
+        .collect()
+}
+
+/// Saves or compares with the baselines as `bench` asks for. Returns
+/// whether no part got slower.
+fn check_baselines(bench: &BenchArgs, times: &[PartTime]) -> Result<bool> {
+    let mut baselines = Baselines::load(&bench.baselines)?;
+    let mut holds = true;
+
+    if let Some(name) = &bench.compare {
+        let baseline = baselines
+            .get(name)
+            .ok_or_else(|| anyhow!("{}", Message::UnknownBaseline(name)))?;
+
+        for slowdown in baseline::compare(baseline, times, bench.threshold) {
+            output::complain(Message::BaselineSlowdown {
+                name,
+                slowdown: &slowdown,
+            });
+            holds = false;
+        }
+    }
+
+    if let Some(name) = &bench.save_baseline {
+        baselines.update(name, times);
+        baselines.save(&bench.baselines)?;
+        output::say(Message::BaselineSaved {
+            name,
+            parts: times.len(),
+        });
+    }
+
+    Ok(holds)
+}
+
+fn fetch(year: u16, day: Option<u8>, session: &str) -> Result<()> {
+    let days = match day {
+        Some(day) => vec![day],
+        None => registry::year(year).iter().map(|p| p.day).collect(),
+    };
+
+    for day in days {
+        let (path, downloaded) = fetch::input(year, day, session)?;
+
+        output::say(if downloaded {
+            Message::Fetched(&path)
         } else {
-            bail!("Invalid day or no solution yet!")
+            Message::AlreadyFetched(&path)
+        });
+    }
+
+    Ok(())
+}
+
+fn submit(
+    args: &Args,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: Option<&str>,
+    session: &str,
+    guesses_path: &Path,
+) -> Result<()> {
+    let answer = match answer {
+        Some(answer) => answer.to_owned(),
+        None => {
+            let puzzle =
+                registry::find(year, day).ok_or_else(|| anyhow!("{}", Message::InvalidDay))?;
+            let timeout = args.timeout.map_or(COLLECT_TIMEOUT, Duration::from_secs);
+
+            output::set_quiet(true);
+            let results = results::collect(&puzzle, timeout);
+            output::set_quiet(false);
+
+            results
+                .into_iter()
+                .find(|r| r.part == part)
+                .ok_or_else(|| anyhow!("{}", Message::InvalidDay))?
+                .answer
+                .to_string()
         }
+    };
+
+    let mut guesses = Guesses::load(guesses_path)?;
+    let now = submit::now();
+    if let Some(refusal) = guesses.refusal(year, day, part, &answer, now) {
+        bail!("{}", Message::SubmitRefused(&refusal));
+    }
+
+    let response = submit::parse_response(&fetch::answer(year, day, part, &answer, session)?)?;
+    guesses.record(year, day, part, &answer, response, now);
+    guesses.save(guesses_path)?;
+
+    match response {
+        submit::Response::Judged(verdict, wait) => {
+            output::say(Message::Submitted {
+                part,
+                answer: &answer,
+                verdict,
+            });
+
+            if verdict == Verdict::Right {
+                let mut known = Answers::load(&args.answers)?;
+                known.set(year, day, part, answer)?;
+                known.save(&args.answers)?;
+            } else if wait > Duration::ZERO {
+                output::say(Message::NextAnswerIn(wait));
+            }
+        }
+        submit::Response::TooSoon(wait) => output::say(Message::SubmitTooSoon(wait)),
+        submit::Response::WrongLevel => output::say(Message::SubmitWrongLevel),
+    }
+
+    Ok(())
+}
+
+/// Stores `answer` or those of the last run as correct.
+fn record(
+    args: &Args,
+    year: u16,
+    day: Option<u8>,
+    part: Option<u8>,
+    answer: Option<&str>,
+) -> Result<()> {
+    let recorded = if let (Some(day), Some(part), Some(answer)) = (day, part, answer) {
+        vec![(day, part, answer.to_owned())]
     } else {
-        for (day, f) in solutions.into_iter().enumerate() {
-            println!("🎅 Running Day {} ...", (day + 1).to_string().bold());
-            f()?;
+        let last_run = LastRun::load(&args.last_run)?;
+
+        (1..=25)
+            .filter(|d| day.is_none_or(|day| day == *d))
+            .flat_map(|d| (1..=2).map(move |p| (d, p)))
+            .filter(|(_, p)| part.is_none_or(|part| part == *p))
+            .filter_map(|(d, p)| Some((d, p, last_run.get(year, d, p)?.answer.clone())))
+            .collect()
+    };
+
+    if recorded.is_empty() {
+        bail!("{}", Message::NothingToRecord(&args.last_run));
+    }
+
+    let mut known = Answers::load(&args.answers)?;
+    for (day, part, answer) in recorded {
+        output::say(Message::Recorded {
+            day,
+            part,
+            answer: &answer,
+        });
+        known.set(year, day, part, answer)?;
+    }
+
+    known.save(&args.answers)
+}
+
+/// The puzzles to work on: `day` or all of the year.
+fn select(year: u16, day: Option<u8>) -> Result<Vec<Puzzle>> {
+    let puzzles = if let Some(day) = day {
+        vec![registry::find(year, day).ok_or_else(|| anyhow!("{}", Message::InvalidDay))?]
+    } else {
+        registry::year(year)
+    };
+
+    if puzzles.is_empty() {
+        bail!("{}", Message::InvalidYear(year));
+    }
+
+    Ok(puzzles)
+}
+
+/// What happens with each selected day.
+#[derive(Debug, Clone, Copy)]
+enum Action<'a> {
+    Run(&'a RunArgs),
+    Bench(&'a BenchArgs),
+    Verify,
+}
+
+impl Action<'_> {
+    fn history(&self) -> Option<&HistoryArgs> {
+        match self {
+            Action::Run(run) => Some(&run.history),
+            Action::Bench(bench) => Some(&bench.history),
+            Action::Verify => None,
+        }
+    }
+}
+
+/// How many of the slowest parts the totals of a run list.
+const SLOWEST_PARTS: usize = 3;
+
+/// Prints where the time of a run went.
+fn print_totals(summary: &Summary) {
+    output::say(Message::RunTotals {
+        wall: summary.wall_time,
+        parse: summary.parse_time(),
+        solve: summary.solve_time(),
+    });
+
+    let slowest = summary.slowest_parts(SLOWEST_PARTS);
+    if !slowest.is_empty() {
+        output::say(Message::SlowestParts);
+    }
+    for (day, part) in slowest {
+        output::say(Message::SlowPart {
+            day,
+            part: part.part,
+            time: part.time,
+        });
+    }
+}
+
+/// Runs `action` for each of `puzzles` and reports the days that failed.
+fn run_days(args: &Args, puzzles: &[Puzzle], action: Action) -> Result<ExitCode> {
+    let start = Instant::now();
+    let timeout = args.timeout.map(Duration::from_secs);
+
+    if let Action::Run(RunArgs {
+        dump_state: Some(path),
+        ..
+    }) = action
+    {
+        let [puzzle] = puzzles[..] else {
+            bail!("{}", Message::DumpNeedsDay);
+        };
+        let format = dump::Format::from_path(path)?;
+
+        write_file(path, &(puzzle.dump)(&puzzle.input()?)?.render(format)?)?;
+    }
+
+    if let Action::Run(RunArgs {
+        render: Some(path), ..
+    }) = action
+    {
+        let [puzzle] = puzzles[..] else {
+            bail!("{}", Message::RenderNeedsDay);
+        };
+
+        (puzzle.render)(&puzzle.input()?)?.save(path, RENDER_SIZE)?;
+    }
+
+    if let Action::Run(RunArgs {
+        animate: Some(_), ..
+    }) = action
+    {
+        if puzzles.len() != 1 {
+            bail!("{}", Message::AnimateNeedsDay);
         }
+    }
+
+    // Timings with a different input say nothing about the solver.
+    let history_args = action
+        .history()
+        .filter(|h| !h.no_history && args.input.is_none());
+    let mut history = history_args
+        .map(|h| History::load(&h.history))
+        .transpose()?;
+    let (skip, budget) = match action {
+        Action::Run(run) => (&run.skip[..], run.budget.map(Duration::from_secs)),
+        _ => (&[][..], None),
+    };
+    let mut failures = vec![];
+    let mut measured_parts = vec![];
+    let mut summary = Summary::new(puzzles.first().map_or(0, |p| p.year));
+
+    // Several days printing their answers and debug output are hard to
+    // follow, so they get a table at the end instead.
+    let format = args.summary.or_else(|| {
+        (matches!(action, Action::Run(run) if !run.stream) && puzzles.len() > 1)
+            .then_some(summary::Format::Table)
+    });
+    if args.summary.is_none() && format.is_some() {
+        output::set_quiet(true);
+    }
+
+    events::emit(&Event::RunStarted {
+        days: puzzles.iter().map(|p| p.day).collect(),
+    });
+
+    for puzzle in puzzles {
+        let day = usize::from(puzzle.day);
+
+        if skip.contains(&puzzle.day) {
+            output::say(Message::SkippedDay(day));
+            events::emit(&Event::DaySkipped {
+                day: puzzle.day,
+                expected_ns: None,
+            });
+            summary.skipped.push(puzzle.day);
+            continue;
+        }
+
+        let expected = history.as_ref().and_then(|history| {
+            history.latest(&Entry::new(
+                puzzle.year,
+                puzzle.day,
+                Mode::Run,
+                solution::algo(),
+                Duration::ZERO,
+            ))
+        });
+        if let (Some(budget), Some(expected)) = (budget, expected) {
+            let remaining = budget.saturating_sub(start.elapsed());
 
-        Ok(())
+            if expected > remaining {
+                output::say(Message::OverBudget {
+                    day,
+                    expected,
+                    remaining,
+                });
+                events::emit(&Event::DaySkipped {
+                    day: puzzle.day,
+                    expected_ns: Some(expected.as_nanos()),
+                });
+                summary.skipped.push(puzzle.day);
+                continue;
+            }
+        }
+
+        output::say(Message::RunningDay(day));
+        events::emit(&Event::DayStarted { day: puzzle.day });
+
+        let recorder = Recorder::default();
+        let mut stats = None;
+        let result = puzzle.input().and_then(|input| {
+            let input = Arc::from(input);
+
+            match action {
+                Action::Bench(bench) => {
+                    output::set_quiet(true);
+                    let samples = bench::measure(
+                        || {
+                            run_day(
+                                puzzle.day,
+                                puzzle.solve,
+                                &input,
+                                timeout,
+                                Some(&recorder),
+                                None,
+                            )
+                        },
+                        bench.warmup,
+                        bench.iterations,
+                    );
+                    output::set_quiet(args.machine_output());
+
+                    measured_parts.extend(part_times(puzzle, &recorder.results(), bench.warmup));
+
+                    samples.map(|samples| {
+                        let day_stats = bench::summarize(&samples);
+                        output::say(Message::Benchmark(&day_stats));
+                        if day_stats.is_noisy() {
+                            output::complain(Message::NoisyBenchmark {
+                                day,
+                                percent: day_stats.variation_percent(),
+                            });
+                        }
+                        stats = Some(day_stats);
+                        Some((Mode::Bench, day_stats.median))
+                    })
+                }
+                Action::Verify => {
+                    run_day(puzzle.day, puzzle.check, &input, timeout, None, None).map(|()| None)
+                }
+                Action::Run(RunArgs {
+                    profile: Some(dir), ..
+                }) => {
+                    let (dir, profile) = (dir.clone(), puzzle.profile);
+                    let (year, day) = (puzzle.year, puzzle.day);
+
+                    run_day(
+                        puzzle.day,
+                        move |input| {
+                            profile(input, &|part| {
+                                dir.join(format!("{year}-day{day:02}-part{part}.svg"))
+                            })
+                        },
+                        &input,
+                        timeout,
+                        Some(&recorder),
+                        None,
+                    )
+                    .map(|()| None)
+                }
+                Action::Run(RunArgs {
+                    animate: Some(path),
+                    ..
+                }) => {
+                    let animation = Animation::default();
+                    run_day(
+                        puzzle.day,
+                        puzzle.solve,
+                        &input,
+                        timeout,
+                        Some(&recorder),
+                        Some(&animation),
+                    )?;
+
+                    animation
+                        .save(path, ANIMATION_SIZE, FRAME_DELAY)
+                        .map(|()| None)
+                }
+                Action::Run(_) => {
+                    let start = Instant::now();
+                    run_day(
+                        puzzle.day,
+                        puzzle.solve,
+                        &input,
+                        timeout,
+                        Some(&recorder),
+                        None,
+                    )
+                    .map(|()| Some((Mode::Run, start.elapsed())))
+                }
+            }
+        });
+
+        if let (Some(history), Some(history_args), Ok(Some((mode, time)))) =
+            (&mut history, history_args, &result)
+        {
+            let entry = Entry::new(puzzle.year, puzzle.day, *mode, solution::algo(), *time);
+
+            if let Some(regression) = history.record(entry, history_args.regression) {
+                output::complain(Message::Regression {
+                    day,
+                    time: *time,
+                    regression: &regression,
+                });
+            }
+        }
+
+        summary.push(DaySummary::new(
+            puzzle.day,
+            &recorder,
+            stats,
+            result.as_ref().err(),
+        ));
+
+        events::emit(&Event::DayFinished {
+            day: puzzle.day,
+            error: result.as_ref().err().map(|e| format!("{e:#}")),
+        });
+
+        if let Err(error) = result {
+            output::complain(Message::DayFailed { day, error: &error });
+            failures.push(day);
+        }
+    }
+
+    summary.wall_time = start.elapsed();
+    events::emit(&Event::RunFinished {
+        failed: summary
+            .days
+            .iter()
+            .filter(|d| d.error.is_some())
+            .map(|d| d.day)
+            .collect(),
+        wall_time_ns: summary.wall_time.as_nanos(),
+    });
+    output::set_quiet(args.machine_output());
+
+    if let Some(format) = format {
+        print!("{}", format.reporter().report(&summary)?);
+    }
+    if matches!(action, Action::Run(_)) && puzzles.len() > 1 {
+        print_totals(&summary);
+    }
+    if !summary.skipped.is_empty() {
+        output::say(Message::SkippedDays(&summary.skipped));
+    }
+
+    if let (Some(history), Some(history_args)) = (&history, history_args) {
+        history.save(&history_args.history)?;
+    }
+
+    // Runs on a different input can't be compared with or recorded as
+    // correct.
+    if let (Action::Run(run), None) = (action, &args.input) {
+        let mut last_run = LastRun::load(&args.last_run)?;
+
+        if run.diff {
+            let changes = last_run.diff(&summary, run.diff_threshold);
+            if changes.is_empty() {
+                output::say(Message::Unchanged);
+            }
+            for change in &changes {
+                output::say(Message::Changed(change));
+            }
+        }
+
+        last_run.update(&summary);
+        last_run.save(&args.last_run)?;
+    }
+
+    if let Action::Bench(bench) = action {
+        if bench.json {
+            let stats = summary
+                .days
+                .iter()
+                .filter_map(|d| Some((d.day, d.stats?)))
+                .collect::<Vec<_>>();
+            print!("{}", bench::to_json(&stats)?);
+        }
+        if !check_baselines(bench, &measured_parts)? {
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    if failures.is_empty() {
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    output::complain(Message::FailureSummary {
+        failed: &failures,
+        total: puzzles.len(),
+    });
+
+    Ok(ExitCode::FAILURE)
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    output::set_lang(args.lang);
+    if args.progress_json {
+        events::enable();
+    }
+    if let Some(algo) = &args.algo {
+        solution::set_algo(algo);
+    }
+    input::set_dir(&args.input_dir);
+    if let Some(path) = &args.input {
+        if args.single_day().is_none() {
+            bail!("{}", Message::InputNeedsDay);
+        }
+
+        input::set_override(
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?,
+        );
+    }
+
+    let year = args.year.unwrap_or_else(registry::latest_year);
+
+    if let Some(dir) = &args.generate_man {
+        // The real arguments of `tool` are only known at runtime.
+        let cmd = Args::command().mut_subcommand("tool", |_| tool::command(year));
+
+        for path in man::write(cmd, dir)? {
+            output::say(Message::ManPage(&path));
+        }
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if args.rpc {
+        output::set_quiet(true);
+        rpc::run(
+            year,
+            io::stdin().lock(),
+            io::stdout().lock(),
+            args.timeout.map(Duration::from_secs),
+        )?;
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    // Generated inputs and JSON may go to stdout, so they must not start
+    // with the banner.
+    output::set_quiet(args.machine_output());
+    if !matches!(args.command, Some(Command::Gen { check: false, .. })) {
+        output::say(Message::Banner(year));
+    }
+
+    match &args.command {
+        None => run_days(&args, &select(year, args.run.day)?, Action::Run(&args.run)),
+        Some(Command::Run(run)) => run_days(&args, &select(year, run.day)?, Action::Run(run)),
+        Some(Command::Bench(bench)) => {
+            run_days(&args, &select(year, bench.day)?, Action::Bench(bench))
+        }
+        Some(Command::Verify { day }) => run_days(&args, &select(year, *day)?, Action::Verify),
+        Some(Command::Fetch { day, session }) => {
+            fetch(year, *day, session)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Puzzle { day, session }) => {
+            print!(
+                "{}",
+                puzzle::render(&fetch::puzzle(year, *day, session.as_deref())?)
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Submit {
+            day,
+            part,
+            answer,
+            session,
+            guesses,
+        }) => {
+            submit(
+                &args,
+                year,
+                *day,
+                *part,
+                answer.as_deref(),
+                session,
+                guesses,
+            )?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Record {
+            day, part, answer, ..
+        }) => {
+            record(&args, year, *day, *part, answer.as_deref())?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Scaffold { day }) => {
+            output::say(Message::Scaffolded(&scaffold::day(
+                Path::new("."),
+                year,
+                *day,
+            )?));
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Explain { day, params }) => {
+            explain(year, *day, params)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Stars { html, accept }) => {
+            stars(&args, year, html.as_deref(), *accept)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Calendar) => {
+            let registered = registry::year(year)
+                .iter()
+                .map(|p| p.day)
+                .collect::<Vec<_>>();
+            let calendar = Calendar::new(year, &registered, &Answers::load(&args.answers)?);
+
+            println!("{}", calendar.render_terminal());
+            output::say(Message::CalendarLegend);
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Gen {
+            day,
+            size,
+            seed,
+            output,
+            check,
+            params,
+        }) => {
+            gen::set_params(ExplainQuery::from_str(&params.join(" "))?);
+            if *check {
+                check_generated(year, *day, *size, *seed, output.as_deref())?;
+            } else {
+                generate(year, *day, *size, *seed, output.as_deref())?;
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::List) => {
+            list(year);
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Report { html }) => {
+            report(&args, year, html.as_deref())?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::LintInput { day, file }) => {
+            Ok(if lint_input(&args, year, *day, file.as_deref())? {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        Some(Command::Tool { args }) => {
+            tool(year, args)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Serve { port }) => {
+            serve::serve(year, *port, args.timeout.map(Duration::from_secs))?;
+            Ok(ExitCode::SUCCESS)
+        }
     }
 }
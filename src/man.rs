@@ -0,0 +1,140 @@
+//! Man pages generated from the command line definition: one for the
+//! program and one for each subcommand, like `git` has them.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use clap::Command;
+use clap_mangen::{
+    roff::{bold, roman, Roff},
+    Man,
+};
+
+/// All environment variables that any argument of `cmd` or its
+/// subcommands reads, with the help of the argument.
+fn environment(cmd: &Command) -> BTreeMap<String, String> {
+    let mut variables = BTreeMap::new();
+
+    for arg in cmd.get_arguments().filter(|a| !a.is_hide_env_set()) {
+        if let Some(env) = arg.get_env() {
+            variables
+                .entry(env.to_string_lossy().into_owned())
+                .or_insert_with(|| arg.get_help().map(ToString::to_string).unwrap_or_default());
+        }
+    }
+
+    for sub in cmd.get_subcommands() {
+        for (name, help) in environment(sub) {
+            variables.entry(name).or_insert(help);
+        }
+    }
+
+    variables
+}
+
+fn page(cmd: Command, environment: &BTreeMap<String, String>) -> Result<String> {
+    let mut out = vec![];
+    Man::new(cmd).render(&mut out)?;
+    let mut page = String::from_utf8(out)?;
+
+    if !environment.is_empty() {
+        let mut roff = Roff::new();
+        roff.control("SH", ["ENVIRONMENT"]);
+        for (name, help) in environment {
+            roff.control("TP", [])
+                .text([bold(name)])
+                .text([roman(help)]);
+        }
+
+        let at = page.find(".SH VERSION").unwrap_or(page.len());
+        page.insert_str(at, &roff.to_roff());
+    }
+
+    Ok(page)
+}
+
+/// The pages as file name and content. The page of the program lists the
+/// environment variables of all subcommands.
+pub fn pages(mut cmd: Command) -> Result<Vec<(String, String)>> {
+    cmd.build();
+    let name = cmd.get_name().to_owned();
+
+    let mut pages = vec![(format!("{name}.1"), page(cmd.clone(), &environment(&cmd))?)];
+
+    for sub in cmd
+        .get_subcommands()
+        .filter(|s| !s.is_hide_set() && s.get_name() != "help")
+    {
+        let sub_name = format!("{name}-{}", sub.get_name());
+        pages.push((
+            format!("{sub_name}.1"),
+            page(sub.clone().name(sub_name), &BTreeMap::new())?,
+        ));
+    }
+
+    Ok(pages)
+}
+
+/// Writes the pages into `dir` and returns their paths.
+pub fn write(cmd: Command, dir: &Path) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    pages(cmd)?
+        .into_iter()
+        .map(|(file, page)| {
+            let path = dir.join(file);
+            fs::write(&path, page)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Arg;
+
+    use super::*;
+
+    #[test]
+    fn pages_cover_subcommands_and_environment() -> Result<()> {
+        let cmd = Command::new("aoc")
+            .version("1.0")
+            .arg(
+                Arg::new("dir")
+                    .long("dir")
+                    .env("AOC_DIR")
+                    .help("Where inputs are"),
+            )
+            .subcommand(
+                Command::new("fetch").about("Downloads inputs").arg(
+                    Arg::new("session")
+                        .long("session")
+                        .env("AOC_SESSION")
+                        .help("The cookie"),
+                ),
+            );
+
+        let pages = pages(cmd)?;
+
+        assert_eq!(
+            pages
+                .iter()
+                .map(|(file, _)| file.as_str())
+                .collect::<Vec<_>>(),
+            ["aoc.1", "aoc-fetch.1"]
+        );
+
+        let main = &pages[0].1;
+        assert!(main.contains(".SH ENVIRONMENT"));
+        assert!(main.contains("AOC_SESSION"));
+        assert!(main.find(".SH ENVIRONMENT") < main.find(".SH VERSION"));
+        assert!(pages[1].1.contains("\\-\\-session"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! Number theory and algebra that puzzles keep coming back to. Like
+//! [`util`](crate::util), everything is exact integer arithmetic.
+
+use std::ops::RangeInclusive;
+
+use num_integer::Integer;
+
+pub use num_integer::{gcd, lcm};
+
+use crate::util::isqrt;
+
+/// The greatest common divisor `g` of `a` and `b` together with `x` and
+/// `y` such that `a * x + b * y == g`.
+#[must_use]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Combines `x ≡ a (mod m)` and `x ≡ b (mod n)` into a single
+/// congruence, if they have a common solution. The moduli don't need to
+/// be coprime.
+#[must_use]
+pub fn crt((a, m): (usize, usize), (b, n): (usize, usize)) -> Option<(usize, usize)> {
+    let (a, m, b, n) = (a as i128, m as i128, b as i128, n as i128);
+    let (gcd, x, _) = extended_gcd(m, n);
+    let diff = b - a;
+
+    if diff % gcd != 0 {
+        return None;
+    }
+
+    let lcm = m / gcd * n;
+    let k = (diff / gcd * x).rem_euclid(n / gcd);
+
+    Some(((a + k * m).rem_euclid(lcm) as usize, lcm as usize))
+}
+
+/// The integers `x` with `a * x² + b * x + c <= 0`, for `a > 0`. The
+/// parabola opens upwards, so they form a range between its roots, or
+/// there are none if it stays above zero.
+#[must_use]
+pub fn quadratic_below(a: i128, b: i128, c: i128) -> Option<RangeInclusive<i128>> {
+    assert!(a > 0, "the parabola must open upwards");
+
+    let discriminant = b * b - 4 * a * c;
+    if discriminant < 0 {
+        return None;
+    }
+
+    // The roots are `(-b ± √discriminant) / 2a`. The integer square
+    // root is less than one below the real one, so the first and last
+    // integer in between are among two candidates each.
+    let root = isqrt(discriminant);
+    let f = |x: &i128| a * x * x + b * x + c <= 0;
+
+    let low = (Integer::div_ceil(&(-b - root - 1), &(2 * a))
+        ..=Integer::div_ceil(&(-b - root), &(2 * a)))
+        .find(f)?;
+    let high = (Integer::div_floor(&(-b + root), &(2 * a))
+        ..=Integer::div_floor(&(-b + root + 1), &(2 * a)))
+        .rev()
+        .find(f)?;
+
+    Some(low..=high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extended_gcd_works() {
+        for (a, b) in [(240, 46), (46, 240), (-12, 18), (7, 0), (0, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b), "{a} {b}");
+            assert_eq!(a * x + b * y, g, "{a} {b}");
+        }
+        assert_eq!(lcm(4, 6), 12);
+    }
+
+    #[test]
+    fn crt_works() {
+        assert_eq!(crt((2, 3), (3, 5)), Some((8, 15)));
+        assert_eq!(crt((1, 4), (3, 6)), Some((9, 12)));
+        assert_eq!(crt((0, 4), (1, 6)), None);
+    }
+
+    #[test]
+    fn quadratic_below_works() {
+        // (x - 2)(x - 5)
+        assert_eq!(quadratic_below(1, -7, 10), Some(2..=5));
+        assert_eq!(quadratic_below(1, 0, 1), None);
+        assert_eq!(quadratic_below(1, 0, 0), Some(0..=0));
+
+        for a in 1..4 {
+            for b in -20..20 {
+                for c in -20..20 {
+                    let below = (-50..50)
+                        .filter(|x| a * x * x + b * x + c <= 0)
+                        .collect::<Vec<_>>();
+                    let expected = below.first().map(|low| *low..=*below.last().unwrap());
+                    assert_eq!(quadratic_below(a, b, c), expected, "{a} {b} {c}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Caching for recursive solvers, which would compute the same
+//! subproblems over and over otherwise. [`Memo`] runs a recursive closure
+//! of a state, and [`memoized!`](crate::memoized) caches the body of a
+//! function that passes its own map on to its recursive calls.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A recursive function of `K` with the values it has computed so far.
+/// The function gets the memo to look up the states it depends on.
+pub struct Memo<'f, K, V> {
+    cache: HashMap<K, V>,
+    f: &'f dyn Fn(&mut Self, K) -> V,
+}
+
+impl<'f, K, V> Memo<'f, K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    #[must_use]
+    pub fn new(f: &'f dyn Fn(&mut Self, K) -> V) -> Self {
+        Self {
+            cache: HashMap::new(),
+            f,
+        }
+    }
+
+    /// The value of `key`, which is only computed the first time.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+
+        let f = self.f;
+        let value = f(self, key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// The number of states computed so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+/// The value of the recursive function `f` at `key`, with a cache that
+/// lives as long as the call.
+pub fn memoize<K, V>(key: K, f: impl Fn(&mut Memo<K, V>, K) -> V) -> V
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    Memo::new(&f).get(key)
+}
+
+/// Evaluates to the value `$memo` has for `$key`, or evaluates `$value`
+/// and stores it there first. `$memo` is a map like `HashMap` and should
+/// be a plain variable, as it is used more than once:
+///
+/// ```ignore
+/// fn paths(n: u64, memo: &mut HashMap<u64, u64>) -> u64 {
+///     memoized!(memo, n, match n {
+///         0 | 1 => 1,
+///         _ => paths(n - 1, memo) + paths(n - 2, memo),
+///     })
+/// }
+/// ```
+#[macro_export]
+macro_rules! memoized {
+    ($memo:expr, $key:expr, $value:expr $(,)?) => {{
+        let key = $key;
+        if let Some(value) = $memo.get(&key) {
+            value.clone()
+        } else {
+            let value = $value;
+            $memo.insert(key, value.clone());
+            value
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    fn paths(n: u64, memo: &mut HashMap<u64, u64>) -> u64 {
+        crate::memoized!(
+            memo,
+            n,
+            match n {
+                0 | 1 => 1,
+                _ => paths(n - 1, memo) + paths(n - 2, memo),
+            }
+        )
+    }
+
+    #[test]
+    fn memos_work() {
+        let calls = Cell::new(0);
+        let fibonacci = memoize(90, |memo, n: u64| {
+            calls.set(calls.get() + 1);
+            match n {
+                0 | 1 => n,
+                _ => memo.get(n - 1) + memo.get(n - 2),
+            }
+        });
+
+        assert_eq!(fibonacci, 2_880_067_194_370_816_120);
+        assert_eq!(calls.get(), 91);
+
+        let mut memo = HashMap::new();
+        assert_eq!(paths(90, &mut memo), 4_660_046_610_375_530_309);
+        assert_eq!(memo.len(), 91);
+    }
+}
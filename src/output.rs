@@ -0,0 +1,726 @@
+//! User-facing messages.
+//!
+//! Everything the runner and the solutions print goes through this
+//! module, so it can be shown in the language selected with `--lang`
+//! or `AOC_LANG`.
+
+use std::{
+    fmt::Display,
+    path::Path,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+    time::Duration,
+};
+
+use anyhow::bail;
+use colored::Colorize;
+
+use crate::{
+    baseline,
+    bench::Stats,
+    history,
+    last_run::Change,
+    lint::Diagnostic,
+    solution::{Answer, Report},
+    status::DayStatus,
+    submit::{Refusal, Verdict},
+    summary::DaySummary,
+};
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Lang {
+    #[default]
+    En,
+    De,
+}
+
+impl FromStr for Lang {
+    type Err = anyhow::Error;
+
+    /// Accepts plain language codes as well as locale strings like
+    /// `de_DE.UTF-8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.split(['_', '.', '-']).next().unwrap_or_default();
+
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Ok(Lang::En),
+            "de" => Ok(Lang::De),
+            _ => bail!("Unsupported language: {s}"),
+        }
+    }
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Selects the output language. Only the first call has an effect.
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+#[must_use]
+pub fn lang() -> Lang {
+    LANG.get().copied().unwrap_or_default()
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppresses regular output, e.g. while benchmarking. Complaints
+/// still go to stderr.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub enum Message<'a> {
+    Banner(u16),
+    RunningDay(usize),
+    Solution {
+        part: u8,
+        answer: &'a dyn Display,
+    },
+    DayFailed {
+        day: usize,
+        error: &'a anyhow::Error,
+    },
+    FailureSummary {
+        failed: &'a [usize],
+        total: usize,
+    },
+    OverBudget {
+        day: usize,
+        expected: Duration,
+        remaining: Duration,
+    },
+    SkippedDay(usize),
+    SkippedDays(&'a [u8]),
+    InvalidDay,
+    InvalidYear(u16),
+    InputNeedsDay,
+    DumpNeedsDay,
+    RenderNeedsDay,
+    AnimateNeedsDay,
+    MissingInput(&'a Path),
+    NeedsSession,
+    Fetched(&'a Path),
+    AlreadyFetched(&'a Path),
+    Scaffolded(&'a Path),
+    ManPage(&'a Path),
+    LintProblem {
+        path: &'a Path,
+        diagnostic: &'a Diagnostic,
+    },
+    LintSummary {
+        path: &'a Path,
+        problems: usize,
+    },
+    Regression {
+        day: usize,
+        time: Duration,
+        regression: &'a history::Regression,
+    },
+    Benchmark(&'a Stats),
+    BaselineSlowdown {
+        name: &'a str,
+        slowdown: &'a baseline::Slowdown,
+    },
+    BaselineSaved {
+        name: &'a str,
+        parts: usize,
+    },
+    UnknownBaseline(&'a str),
+    NoisyBenchmark {
+        day: usize,
+        percent: u128,
+    },
+    CheckAgrees {
+        part: u8,
+        answer: &'a Answer,
+    },
+    CheckDisagrees {
+        part: u8,
+        answer: &'a Answer,
+        reference: &'a Answer,
+    },
+    NoReference,
+    PartNotImplemented(u8),
+    Serving(u16),
+    Shrunk {
+        path: &'a Path,
+        from: usize,
+        to: usize,
+    },
+    Explanation(&'a Report),
+    TimedOut(Duration),
+    StarCount {
+        year: u16,
+        stars: usize,
+        max: usize,
+    },
+    StarLegend,
+    CalendarLegend,
+    SubmitRefused(&'a Refusal),
+    Submitted {
+        part: u8,
+        answer: &'a str,
+        verdict: Verdict,
+    },
+    SubmitTooSoon(Duration),
+    SubmitWrongLevel,
+    NextAnswerIn(Duration),
+    AcceptedAnswers(usize),
+    Recorded {
+        day: u8,
+        part: u8,
+        answer: &'a str,
+    },
+    NothingToRecord(&'a Path),
+    PartTiming {
+        day: u8,
+        part: u8,
+        answer: &'a Answer,
+        time: Duration,
+    },
+    StatusHeader,
+    DayStatus(&'a DayStatus),
+    SummaryHeader,
+    SummaryRow(&'a DaySummary),
+    RunTotals {
+        wall: Duration,
+        parse: Duration,
+        solve: Duration,
+    },
+    SlowestParts,
+    SlowPart {
+        day: u8,
+        part: u8,
+        time: Duration,
+    },
+    Changed(&'a Change),
+    Unchanged,
+}
+
+impl Message<'_> {
+    #[must_use]
+    pub fn localize(&self, lang: Lang) -> String {
+        use Lang::*;
+        use Message::*;
+
+        match (lang, self) {
+            (_, Banner(year)) => format!("🎄 Advent of Code {year} 🎄"),
+            (En, RunningDay(day)) => format!("🎅 Running Day {} ...", day.to_string().bold()),
+            (De, RunningDay(day)) => format!("🎅 Starte Tag {} ...", day.to_string().bold()),
+            (En, Solution { part, answer }) => {
+                format!("🎁 Part {part} Solution: {}", answer.to_string().bold())
+            }
+            (De, Solution { part, answer }) => {
+                format!("🎁 Lösung Teil {part}: {}", answer.to_string().bold())
+            }
+            (En, DayFailed { day, error }) => format!("💥 Day {day} failed: {error:#}"),
+            (De, DayFailed { day, error }) => format!("💥 Tag {day} fehlgeschlagen: {error:#}"),
+            (En, FailureSummary { failed, total }) => format!(
+                "💥 {} of {total} days failed: {}",
+                failed.len(),
+                day_list(failed)
+            ),
+            (De, FailureSummary { failed, total }) => format!(
+                "💥 {} von {total} Tagen fehlgeschlagen: {}",
+                failed.len(),
+                day_list(failed)
+            ),
+            (
+                En,
+                OverBudget {
+                    day,
+                    expected,
+                    remaining,
+                },
+            ) => format!(
+                "⏭️  Skipping day {day}: it took {expected:.1?} last time, but only {remaining:.1?} of the budget are left"
+            ),
+            (
+                De,
+                OverBudget {
+                    day,
+                    expected,
+                    remaining,
+                },
+            ) => format!(
+                "⏭️  Überspringe Tag {day}: er brauchte zuletzt {expected:.1?}, aber vom Budget sind nur noch {remaining:.1?} übrig"
+            ),
+            (En, SkippedDay(day)) => format!("⏭️  Skipping day {day}"),
+            (De, SkippedDay(day)) => format!("⏭️  Überspringe Tag {day}"),
+            (En, SkippedDays(days)) => format!(
+                "⏭️  Skipped {} days, the run is incomplete: {}",
+                days.len(),
+                day_list(days)
+            ),
+            (De, SkippedDays(days)) => format!(
+                "⏭️  {} Tage übersprungen, der Lauf ist unvollständig: {}",
+                days.len(),
+                day_list(days)
+            ),
+            (En, InvalidDay) => "Invalid day or no solution yet!".to_owned(),
+            (De, InvalidDay) => "Ungültiger Tag oder noch keine Lösung!".to_owned(),
+            (En, InputNeedsDay) => "--input only works with a single day!".to_owned(),
+            (De, InputNeedsDay) => "--input geht nur mit einem einzelnen Tag!".to_owned(),
+            (En, DumpNeedsDay) => "--dump-state only works with a single day!".to_owned(),
+            (De, DumpNeedsDay) => "--dump-state geht nur mit einem einzelnen Tag!".to_owned(),
+            (En, RenderNeedsDay) => "--render only works with a single day!".to_owned(),
+            (De, RenderNeedsDay) => "--render geht nur mit einem einzelnen Tag!".to_owned(),
+            (En, AnimateNeedsDay) => "--animate only works with a single day!".to_owned(),
+            (De, AnimateNeedsDay) => "--animate geht nur mit einem einzelnen Tag!".to_owned(),
+            (
+                En,
+                Regression {
+                    day,
+                    time,
+                    regression,
+                },
+            ) => format!(
+                "🐌 Day {day} took {time:.1?}, {}% slower than its best of {:.1?}",
+                regression.percent, regression.best
+            ),
+            (
+                De,
+                Regression {
+                    day,
+                    time,
+                    regression,
+                },
+            ) => format!(
+                "🐌 Tag {day} brauchte {time:.1?}, {}% langsamer als die Bestzeit von {:.1?}",
+                regression.percent, regression.best
+            ),
+            (En, MissingInput(path)) => format!(
+                "Input {} is missing. Put it there with `fetch` or point --input-dir or AOC_INPUT_DIR elsewhere.",
+                path.display()
+            ),
+            (De, MissingInput(path)) => format!(
+                "Eingabe {} fehlt. Lege sie dort mit `fetch` ab oder zeige mit --input-dir oder AOC_INPUT_DIR woanders hin.",
+                path.display()
+            ),
+            (En, NeedsSession) => {
+                "Downloading needs the session cookie. Pass --session or set AOC_SESSION.".to_owned()
+            }
+            (De, NeedsSession) => {
+                "Zum Herunterladen fehlt das Session-Cookie. Übergib --session oder setze AOC_SESSION.".to_owned()
+            }
+            (En, Fetched(path)) => format!("📥 Downloaded {}", path.display()),
+            (De, Fetched(path)) => format!("📥 {} heruntergeladen", path.display()),
+            (En, AlreadyFetched(path)) => format!("📦 {} is already there", path.display()),
+            (De, AlreadyFetched(path)) => format!("📦 {} ist schon da", path.display()),
+            (En, ManPage(path)) => format!("📖 Wrote {}", path.display()),
+            (De, ManPage(path)) => format!("📖 {} geschrieben", path.display()),
+            (En, Scaffolded(path)) => format!("🛠 Created {} and registered it", path.display()),
+            (De, Scaffolded(path)) => {
+                format!("🛠 {} angelegt und registriert", path.display())
+            }
+            (_, LintProblem { path, diagnostic }) => {
+                format!("{}:{diagnostic}", path.display())
+            }
+            (En, LintSummary { path, problems: 0 }) => {
+                format!("✅ {} looks fine", path.display())
+            }
+            (De, LintSummary { path, problems: 0 }) => {
+                format!("✅ {} sieht gut aus", path.display())
+            }
+            (En, LintSummary { path, problems }) => {
+                format!("💥 {} has {problems} problems", path.display())
+            }
+            (De, LintSummary { path, problems }) => {
+                format!("💥 {} hat {problems} Probleme", path.display())
+            }
+            (En, InvalidYear(year)) => format!("No solutions for {year}!"),
+            (De, InvalidYear(year)) => format!("Keine Lösungen für {year}!"),
+            (En, Benchmark(s)) => format!(
+                "⏱️  median {:?} (MAD {:?}), mean {:?} (σ {:?}), min {:?}, max {:?} (samples: {}, outliers: {})",
+                s.median, s.mad, s.mean, s.stddev, s.min, s.max, s.samples, s.rejected
+            ),
+            (De, Benchmark(s)) => format!(
+                "⏱️  Median {:?} (MAD {:?}), Mittel {:?} (σ {:?}), Min. {:?}, Max. {:?} (Messungen: {}, Ausreißer: {})",
+                s.median, s.mad, s.mean, s.stddev, s.min, s.max, s.samples, s.rejected
+            ),
+            (En, BaselineSlowdown { name, slowdown: s }) => format!(
+                "🐌 Day {} part {} took {:.1?}, {}% slower than {:.1?} in baseline {name}",
+                s.day, s.part, s.time, s.percent, s.baseline
+            ),
+            (De, BaselineSlowdown { name, slowdown: s }) => format!(
+                "🐌 Tag {} Teil {} brauchte {:.1?}, {}% langsamer als {:.1?} in Baseline {name}",
+                s.day, s.part, s.time, s.percent, s.baseline
+            ),
+            (En, BaselineSaved { name, parts }) => {
+                format!("📏 Saved the times of {parts} parts as baseline {name}")
+            }
+            (De, BaselineSaved { name, parts }) => {
+                format!("📏 Zeiten von {parts} Teilen als Baseline {name} gespeichert")
+            }
+            (En, UnknownBaseline(name)) => format!("There is no baseline {name}"),
+            (De, UnknownBaseline(name)) => format!("Es gibt keine Baseline {name}"),
+            (En, NoisyBenchmark { day, percent }) => format!(
+                "⚠️  Day {day} varies by {percent}% between runs, its timings are unreliable"
+            ),
+            (De, NoisyBenchmark { day, percent }) => format!(
+                "⚠️  Tag {day} schwankt um {percent}% zwischen den Läufen, seine Zeiten sind unzuverlässig"
+            ),
+            (En, CheckAgrees { part, answer }) => {
+                format!("✅ Part {part}: {answer} matches the reference")
+            }
+            (De, CheckAgrees { part, answer }) => {
+                format!("✅ Teil {part}: {answer} stimmt mit der Referenz überein")
+            }
+            (
+                En,
+                CheckDisagrees {
+                    part,
+                    answer,
+                    reference,
+                },
+            ) => format!("part {part} says {answer}, but the reference says {reference}"),
+            (
+                De,
+                CheckDisagrees {
+                    part,
+                    answer,
+                    reference,
+                },
+            ) => format!("Teil {part} sagt {answer}, aber die Referenz sagt {reference}"),
+            (En, NoReference) => "🤷 No reference solver to check against".to_owned(),
+            (De, NoReference) => "🤷 Keine Referenzlösung zum Vergleichen".to_owned(),
+            (En, PartNotImplemented(part)) => format!("⏳ Part {part}: not implemented"),
+            (De, PartNotImplemented(part)) => format!("⏳ Teil {part}: nicht implementiert"),
+            (En, Serving(port)) => {
+                format!("🌐 Serving solutions on http://localhost:{port}/solve/DAY/PART")
+            }
+            (De, Serving(port)) => {
+                format!("🌐 Lösungen unter http://localhost:{port}/solve/TAG/TEIL erreichbar")
+            }
+            (En, Shrunk { path, from, to }) => format!(
+                "🔍 Shrunk the disagreeing input from {from} to {to} lines: {}",
+                path.display()
+            ),
+            (De, Shrunk { path, from, to }) => format!(
+                "🔍 Abweichende Eingabe von {from} auf {to} Zeilen verkleinert: {}",
+                path.display()
+            ),
+            (En, TimedOut(limit)) => format!("timed out after {limit:?}"),
+            (De, TimedOut(limit)) => format!("Zeitlimit von {limit:?} überschritten"),
+            (En, StarCount { year, stars, max }) => format!("⭐ {year}: {stars} of {max} stars"),
+            (De, StarCount { year, stars, max }) => format!("⭐ {year}: {stars} von {max} Sternen"),
+            (En, StarLegend) => format!(
+                "{} correct  {} unverified  {} wrong  {} missing",
+                "*".yellow().bold(),
+                "?".cyan(),
+                "x".red(),
+                ".".dimmed()
+            ),
+            (De, StarLegend) => format!(
+                "{} richtig  {} ungeprüft  {} falsch  {} fehlt",
+                "*".yellow().bold(),
+                "?".cyan(),
+                "x".red(),
+                ".".dimmed()
+            ),
+            (En, SubmitRefused(Refusal::Cooldown(wait))) => {
+                format!("Not submitting, the website wants us to wait {wait:?} more")
+            }
+            (De, SubmitRefused(Refusal::Cooldown(wait))) => {
+                format!("Nicht abgeschickt, die Webseite will noch {wait:?} Pause")
+            }
+            (En, SubmitRefused(Refusal::Solved(answer))) => {
+                format!("Not submitting, this part is already solved with {answer}")
+            }
+            (De, SubmitRefused(Refusal::Solved(answer))) => {
+                format!("Nicht abgeschickt, dieser Teil ist mit {answer} schon gelöst")
+            }
+            (En, SubmitRefused(Refusal::KnownWrong)) => {
+                "Not submitting, this answer was wrong before".to_owned()
+            }
+            (De, SubmitRefused(Refusal::KnownWrong)) => {
+                "Nicht abgeschickt, diese Antwort war schon einmal falsch".to_owned()
+            }
+            (En, SubmitRefused(Refusal::TooHigh(guess))) => {
+                format!("Not submitting, {guess} was already too high")
+            }
+            (De, SubmitRefused(Refusal::TooHigh(guess))) => {
+                format!("Nicht abgeschickt, {guess} war schon zu hoch")
+            }
+            (En, SubmitRefused(Refusal::TooLow(guess))) => {
+                format!("Not submitting, {guess} was already too low")
+            }
+            (De, SubmitRefused(Refusal::TooLow(guess))) => {
+                format!("Nicht abgeschickt, {guess} war schon zu niedrig")
+            }
+            (
+                En,
+                Submitted {
+                    part,
+                    answer,
+                    verdict,
+                },
+            ) => match verdict {
+                Verdict::Right => format!("⭐ Part {part}: {answer} is right!"),
+                Verdict::Wrong => format!("❌ Part {part}: {answer} is wrong"),
+                Verdict::TooHigh => format!("📈 Part {part}: {answer} is too high"),
+                Verdict::TooLow => format!("📉 Part {part}: {answer} is too low"),
+            },
+            (
+                De,
+                Submitted {
+                    part,
+                    answer,
+                    verdict,
+                },
+            ) => match verdict {
+                Verdict::Right => format!("⭐ Teil {part}: {answer} ist richtig!"),
+                Verdict::Wrong => format!("❌ Teil {part}: {answer} ist falsch"),
+                Verdict::TooHigh => format!("📈 Teil {part}: {answer} ist zu hoch"),
+                Verdict::TooLow => format!("📉 Teil {part}: {answer} ist zu niedrig"),
+            },
+            (En, SubmitTooSoon(wait)) => {
+                format!("⏳ The last answer was too recent, wait {wait:?} before the next")
+            }
+            (De, SubmitTooSoon(wait)) => {
+                format!("⏳ Die letzte Antwort war zu kurz davor, warte {wait:?} bis zur nächsten")
+            }
+            (En, SubmitWrongLevel) => {
+                "🤔 This part is already solved or not unlocked yet".to_owned()
+            }
+            (De, SubmitWrongLevel) => {
+                "🤔 Dieser Teil ist schon gelöst oder noch nicht freigeschaltet".to_owned()
+            }
+            (En, NextAnswerIn(wait)) => format!("⏳ The next answer can go in after {wait:?}"),
+            (De, NextAnswerIn(wait)) => format!("⏳ Die nächste Antwort geht nach {wait:?}"),
+            (En, CalendarLegend) => format!(
+                "{} both parts  {} part 1",
+                " ** ".black().on_yellow(),
+                " * ".black().on_white()
+            ),
+            (De, CalendarLegend) => format!(
+                "{} beide Teile  {} Teil 1",
+                " ** ".black().on_yellow(),
+                " * ".black().on_white()
+            ),
+            (En, AcceptedAnswers(n)) => format!("📝 Stored {n} new answers as correct"),
+            (De, AcceptedAnswers(n)) => format!("📝 {n} neue Antworten als richtig gespeichert"),
+            (En, Recorded { day, part, answer }) => {
+                format!("📝 Recorded {answer} as answer of day {day} part {part}")
+            }
+            (De, Recorded { day, part, answer }) => {
+                format!("📝 {answer} als Antwort von Tag {day} Teil {part} gespeichert")
+            }
+            (En, NothingToRecord(path)) => {
+                format!("{} has no answers to record", path.display())
+            }
+            (De, NothingToRecord(path)) => {
+                format!("{} enthält keine Antworten zum Speichern", path.display())
+            }
+            (
+                En,
+                PartTiming {
+                    day,
+                    part,
+                    answer,
+                    time,
+                },
+            ) => format!("Day {day:>2} part {part}: {answer:<20} {time:>10.1?}"),
+            (
+                De,
+                PartTiming {
+                    day,
+                    part,
+                    answer,
+                    time,
+                },
+            ) => format!("Tag {day:>2} Teil {part}: {answer:<20} {time:>10.1?}"),
+            (En, StatusHeader) => {
+                status_columns("Day", ["Module", "Registered", "Part 1", "Part 2", "Examples"])
+            }
+            (De, StatusHeader) => {
+                status_columns("Tag", ["Modul", "Registriert", "Teil 1", "Teil 2", "Beispiele"])
+            }
+            (_, DayStatus(s)) => {
+                let row = status_columns(
+                    &s.day.to_string(),
+                    [s.module, s.registered, s.part1, s.part2, s.example_tests]
+                        .map(|flag| if flag { "✔" } else { "·" }),
+                );
+
+                if s.has_gap() {
+                    row.yellow().to_string()
+                } else {
+                    row
+                }
+            }
+            (En, SummaryHeader) => summary_columns("Day", ["Part 1", "Time", "Part 2", "Time"]),
+            (De, SummaryHeader) => summary_columns("Tag", ["Teil 1", "Zeit", "Teil 2", "Zeit"]),
+            (lang, SummaryRow(d)) => match &d.error {
+                Some(error) => format!("{:>3}  💥 {error}", d.day).red().to_string(),
+                None => {
+                    let missing = match lang {
+                        En => "⏳ not implemented",
+                        De => "⏳ nicht implementiert",
+                    };
+                    let [(a1, t1), (a2, t2)] = [1, 2].map(|part| {
+                        d.part(part).map_or((missing.to_owned(), String::new()), |p| {
+                            (p.answer.to_string(), format!("{:.1?}", p.time))
+                        })
+                    });
+                    summary_columns(&d.day.to_string(), [&a1, &t1, &a2, &t2])
+                }
+            },
+            (En, RunTotals { wall, parse, solve }) => {
+                format!("⏱️  All days took {wall:.1?}: {parse:.1?} parsing, {solve:.1?} solving")
+            }
+            (De, RunTotals { wall, parse, solve }) => format!(
+                "⏱️  Alle Tage brauchten {wall:.1?}: {parse:.1?} Einlesen, {solve:.1?} Lösen"
+            ),
+            (En, SlowestParts) => "🐢 Slowest parts:".to_owned(),
+            (De, SlowestParts) => "🐢 Langsamste Teile:".to_owned(),
+            (En, SlowPart { day, part, time }) => {
+                format!("   Day {day:>2} part {part}: {time:>10.1?}")
+            }
+            (De, SlowPart { day, part, time }) => {
+                format!("   Tag {day:>2} Teil {part}: {time:>10.1?}")
+            }
+            (
+                En,
+                Changed(Change::Answer {
+                    day,
+                    part,
+                    before,
+                    after,
+                }),
+            ) => format!("Day {day} part {part}: {before} → {after}")
+                .red()
+                .to_string(),
+            (
+                De,
+                Changed(Change::Answer {
+                    day,
+                    part,
+                    before,
+                    after,
+                }),
+            ) => format!("Tag {day} Teil {part}: {before} → {after}")
+                .red()
+                .to_string(),
+            (
+                En,
+                Changed(Change::Timing {
+                    day,
+                    part,
+                    before,
+                    after,
+                }),
+            ) => format!("Day {day} part {part}: {before:.1?} → {after:.1?}")
+                .yellow()
+                .to_string(),
+            (
+                De,
+                Changed(Change::Timing {
+                    day,
+                    part,
+                    before,
+                    after,
+                }),
+            ) => format!("Tag {day} Teil {part}: {before:.1?} → {after:.1?}")
+                .yellow()
+                .to_string(),
+            (En, Unchanged) => "✅ Same answers and timings as the last run".to_owned(),
+            (De, Unchanged) => "✅ Gleiche Antworten und Zeiten wie im letzten Lauf".to_owned(),
+            (_, Explanation(report)) => format!("🔍 {}", report.to_string().trim_end()),
+        }
+    }
+}
+
+fn status_columns(day: &str, columns: [&str; 5]) -> String {
+    columns
+        .iter()
+        .fold(format!("{day:>3} "), |row, c| format!("{row} {c:<12}"))
+        .trim_end()
+        .to_owned()
+}
+
+fn summary_columns(day: &str, [a1, t1, a2, t2]: [&str; 4]) -> String {
+    format!("{day:>3}  {a1:<20} {t1:>10}  {a2:<20} {t2:>10}")
+        .trim_end()
+        .to_owned()
+}
+
+fn day_list(days: &[impl Display]) -> String {
+    days.iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Display for Message<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.localize(lang()))
+    }
+}
+
+/// Prints a message to stdout.
+pub fn say(message: Message) {
+    if !QUIET.load(Ordering::Relaxed) {
+        println!("{message}");
+    }
+}
+
+/// Prints a message to stderr.
+pub fn complain(message: Message) {
+    eprintln!("{message}");
+}
+
+/// Prints the answer to one part of a puzzle.
+pub fn solution(part: u8, answer: &Answer) {
+    say(Message::Solution { part, answer });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_languages() -> anyhow::Result<()> {
+        assert_eq!(Lang::from_str("en")?, Lang::En);
+        assert_eq!(Lang::from_str("DE")?, Lang::De);
+        assert_eq!(Lang::from_str("de_DE.UTF-8")?, Lang::De);
+        assert!(Lang::from_str("tlh").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn messages_are_localized() {
+        colored::control::set_override(false);
+
+        let failed = [3, 7];
+        let summary = Message::FailureSummary {
+            failed: &failed,
+            total: 13,
+        };
+
+        assert_eq!(summary.localize(Lang::En), "💥 2 of 13 days failed: 3, 7");
+        assert_eq!(
+            summary.localize(Lang::De),
+            "💥 2 von 13 Tagen fehlgeschlagen: 3, 7"
+        );
+
+        let answer = 42;
+        let solution = Message::Solution {
+            part: 2,
+            answer: &answer,
+        };
+
+        assert_eq!(solution.localize(Lang::En), "🎁 Part 2 Solution: 42");
+        assert_eq!(solution.localize(Lang::De), "🎁 Lösung Teil 2: 42");
+
+        assert_eq!(
+            Message::SkippedDays(&[10, 12]).localize(Lang::En),
+            "⏭️  Skipped 2 days, the run is incomplete: 10, 12"
+        );
+    }
+}
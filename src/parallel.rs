@@ -0,0 +1,70 @@
+//! Parallel helpers shared by the solutions.
+
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+/// Maps `f` over `items` in parallel and records how long each call
+/// took. The results keep the order of `items`.
+pub fn par_map_timed<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<(R, Duration)>
+where
+    T: Sync,
+    R: Send,
+{
+    items
+        .par_iter()
+        .map(|item| {
+            let start = Instant::now();
+            let result = f(item);
+
+            (result, start.elapsed())
+        })
+        .collect()
+}
+
+/// Below this many items, fanning out costs more than it saves.
+pub const PAR_THRESHOLD: usize = 16;
+
+/// Maps `f` over `items`, in parallel if there are at least
+/// [`PAR_THRESHOLD`] of them. The results keep the order of `items`.
+pub fn maybe_par_map<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    if items.len() < PAR_THRESHOLD {
+        items.iter().map(f).collect()
+    } else {
+        items.par_iter().map(f).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_map_timed_keeps_order() {
+        let items = (0..1000).collect::<Vec<u32>>();
+
+        assert_eq!(
+            par_map_timed(&items, |v| v * 2)
+                .into_iter()
+                .map(|(v, _)| v)
+                .collect::<Vec<_>>(),
+            (0..1000).map(|v| v * 2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn maybe_par_map_keeps_order() {
+        for len in [0, 3, PAR_THRESHOLD, 1000] {
+            let items = (0..len).collect::<Vec<usize>>();
+
+            assert_eq!(
+                maybe_par_map(&items, |v| v + 1),
+                (1..=len).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Helpers for the shapes puzzle inputs come in: lines of numbers,
+//! labeled lines, sections separated by blank lines and fields of fixed
+//! width. Errors name the token that is wrong, so days only need to add
+//! where it is.
+
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum Error {
+    #[error("Can't parse number {token}: {reason}")]
+    Number { token: String, reason: String },
+    #[error("Expected a line starting with {0}")]
+    Label(String),
+}
+
+/// The numbers separated by whitespace in `s`. Anything else in between
+/// is an error.
+pub fn ints<T>(s: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.split_ascii_whitespace()
+        .map(|token| {
+            token.parse().map_err(|e: T::Err| Error::Number {
+                token: token.to_owned(),
+                reason: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// What follows `label` in `line`, like the numbers of `Time: 7 15 30`.
+pub fn labeled<'a>(line: &'a str, label: &str) -> Result<&'a str, Error> {
+    line.strip_prefix(label)
+        .ok_or_else(|| Error::Label(label.to_owned()))
+}
+
+/// The blocks of lines in `s` that are separated by blank lines, without
+/// their final line break.
+#[must_use]
+pub fn sections(s: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    // The byte range of the current section.
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for line in s.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+
+        if content.trim().is_empty() {
+            sections.extend(current.take().map(|(start, end)| &s[start..end]));
+        } else {
+            let start = current.map_or(offset, |(start, _)| start);
+            current = Some((start, offset + content.len()));
+        }
+
+        offset += line.len();
+    }
+    sections.extend(current.map(|(start, end)| &s[start..end]));
+
+    sections
+}
+
+/// Cuts `line` into fields of `width` characters, like the columns of a
+/// table, and trims them. Blank fields are kept as empty strings, so the
+/// others stay in their column.
+#[must_use]
+pub fn fixed_width(line: &str, width: usize) -> Vec<&str> {
+    assert!(width > 0, "fields need a width");
+
+    let bounds = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .step_by(width)
+        .chain([line.len()])
+        .collect::<Vec<_>>();
+
+    bounds.windows(2).map(|w| line[w[0]..w[1]].trim()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn helpers_work() -> Result<(), Error> {
+        assert_eq!(ints::<i64>(" 1 -2\t30 ")?, [1, -2, 30]);
+        assert!(ints::<u32>("")?.is_empty());
+        assert_eq!(
+            format!("{:#}", ints::<u32>("1 x").unwrap_err()),
+            "Can't parse number x: invalid digit found in string"
+        );
+
+        assert_eq!(labeled("Time: 7 15", "Time:")?, " 7 15");
+        assert_eq!(
+            labeled("Distance: 9", "Time:"),
+            Err(Error::Label("Time:".to_owned()))
+        );
+
+        assert_eq!(sections("a\nb\n\n\nc\r\n  \nd"), ["a\nb", "c", "d"]);
+        assert_eq!(sections("\n\na\n"), ["a"]);
+        assert!(sections("").is_empty());
+
+        assert_eq!(fixed_width("[A]     [C]", 4), ["[A]", "", "[C]"]);
+        assert!(fixed_width("", 4).is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+//! Flamegraph profiling of solutions.
+//!
+//! Sampling needs the `profile` feature, because it pulls in `pprof`
+//! and only works on Unix-like systems.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+/// Runs `f` while sampling its call stacks and writes the result as
+/// an SVG flamegraph to `svg_path`.
+#[cfg(feature = "profile")]
+pub fn flamegraph<T>(svg_path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    use std::fs::{self, File};
+
+    use anyhow::Context;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(1000)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .context("Failed to start profiler")?;
+
+    let result = f();
+
+    if let Some(dir) = svg_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    guard
+        .report()
+        .build()
+        .context("Failed to build profile report")?
+        .flamegraph(File::create(svg_path)?)
+        .with_context(|| format!("Failed to write {}", svg_path.display()))?;
+
+    result
+}
+
+#[cfg(not(feature = "profile"))]
+pub fn flamegraph<T>(_svg_path: &Path, _f: impl FnOnce() -> Result<T>) -> Result<T> {
+    anyhow::bail!("Profiling support is not compiled in. Rebuild with `--features profile`.")
+}
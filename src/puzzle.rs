@@ -0,0 +1,268 @@
+//! Puzzle descriptions from the Advent of Code website, rendered for the
+//! terminal. The pages only use a handful of tags, so this is far from a
+//! general HTML renderer.
+
+use colored::{ColoredString, Colorize};
+use itertools::Itertools;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Style {
+    bold: bool,
+    star: bool,
+    code: bool,
+    link: bool,
+    pre: bool,
+}
+
+impl Style {
+    /// The style of the content of `tag`.
+    fn enter(self, tag: &str, attrs: &str) -> Style {
+        let mut style = self;
+        match tag {
+            "em" if attrs.contains("star") => style.star = true,
+            "h2" | "em" | "strong" => style.bold = true,
+            "code" => style.code = true,
+            "a" => style.link = true,
+            "pre" => style.pre = true,
+            _ => {}
+        }
+        style
+    }
+
+    fn apply(self, text: &str) -> ColoredString {
+        let mut text = text.normal();
+        if self.code {
+            text = text.cyan();
+        }
+        if self.star {
+            text = text.yellow().bold();
+        } else if self.bold {
+            text = text.bright_white().bold();
+        }
+        if self.link {
+            text = text.underline();
+        }
+        text
+    }
+}
+
+/// Tags without content.
+const VOID: [&str; 3] = ["br", "hr", "img"];
+
+/// Replaces character references like `&lt;` with the characters.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut rest = text;
+
+    while let Some(at) = rest.find('&') {
+        unescaped.push_str(&rest[..at]);
+        rest = &rest[at..];
+
+        let reference = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                number => char::from_u32(
+                    number
+                        .strip_prefix("#x")
+                        .map(|hex| u32::from_str_radix(hex, 16))
+                        .or_else(|| number.strip_prefix('#').map(str::parse))?
+                        .ok()?,
+                )?,
+            };
+            Some((c, end))
+        });
+
+        match reference {
+            Some((c, end)) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+#[derive(Debug, Default)]
+struct Renderer {
+    out: String,
+    /// Whether the text so far ended in whitespace that is yet to be
+    /// written.
+    space: bool,
+}
+
+impl Renderer {
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n') || self.out.ends_with("• ")
+    }
+
+    fn break_line(&mut self) {
+        self.space = false;
+        if !self.at_line_start() {
+            self.out.push('\n');
+        }
+    }
+
+    fn paragraph(&mut self) {
+        self.break_line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn open(&mut self, tag: &str) {
+        match tag {
+            "h2" | "p" | "pre" | "ul" => self.paragraph(),
+            "li" => {
+                self.break_line();
+                self.out.push_str("  • ");
+            }
+            "br" => self.break_line(),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, tag: &str) {
+        match tag {
+            "h2" | "p" | "pre" | "ul" => self.paragraph(),
+            "li" => self.break_line(),
+            _ => {}
+        }
+    }
+
+    /// Writes text like a browser does: runs of whitespace become a
+    /// single space, except in preformatted text.
+    fn text(&mut self, text: &str, style: Style) {
+        let text = unescape(text);
+
+        if style.pre {
+            self.out.push_str(&style.apply(&text).to_string());
+            return;
+        }
+
+        let words = text.split_whitespace().join(" ");
+        if words.is_empty() {
+            self.space |= !text.is_empty();
+            return;
+        }
+
+        if (self.space || text.starts_with(char::is_whitespace)) && !self.at_line_start() {
+            self.out.push(' ');
+        }
+        self.out.push_str(&style.apply(&words).to_string());
+        self.space = text.ends_with(char::is_whitespace);
+    }
+
+    fn article(&mut self, html: &str) {
+        let mut stack: Vec<(&str, Style)> = vec![];
+        let mut style = Style::default();
+        let mut rest = html;
+
+        self.paragraph();
+
+        while !rest.is_empty() {
+            if let Some(tag) = rest.strip_prefix('<') {
+                let Some((tag, after)) = tag.split_once('>') else {
+                    break;
+                };
+                rest = after;
+
+                if let Some(name) = tag.strip_prefix('/') {
+                    let name = name.trim();
+                    if let Some(at) = stack.iter().rposition(|(n, _)| *n == name) {
+                        style = stack[at].1;
+                        stack.truncate(at);
+                    }
+                    self.close(name);
+                } else {
+                    let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+                    let name = name.trim_end_matches('/');
+
+                    self.open(name);
+                    if !tag.ends_with('/') && !VOID.contains(&name) {
+                        stack.push((name, style));
+                        style = style.enter(name, attrs);
+                    }
+                }
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.text(&rest[..end], style);
+                rest = &rest[end..];
+            }
+        }
+    }
+}
+
+/// The descriptions of both parts on the page of a puzzle, as far as
+/// they are shown.
+#[must_use]
+pub fn render(page: &str) -> String {
+    let mut renderer = Renderer::default();
+    let mut rest = page;
+
+    while let Some(start) = rest.find("<article") {
+        rest = &rest[start..];
+        let Some(content) = rest.find('>') else {
+            break;
+        };
+        let end = rest.find("</article>").unwrap_or(rest.len());
+
+        renderer.article(&rest[content + 1..end]);
+        rest = &rest[end..];
+    }
+
+    renderer.out.trim_end().to_owned() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_both_parts() {
+        colored::control::set_override(false);
+
+        let page = "<html><main>
+<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with
+<a href=\"/\">global snow</a> &amp; such.</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul>
+<li>One</li>
+<li>Two &lt;3 <em class=\"star\">*</em></li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>142</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>More&#33;</p></article>
+</main></html>";
+
+        assert_eq!(
+            render(page),
+            "--- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow & such.
+
+1abc2
+pqr3stu8vwx
+
+  • One
+  • Two <3 *
+
+--- Part Two ---
+
+More!
+"
+        );
+    }
+}
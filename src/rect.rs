@@ -0,0 +1,242 @@
+//! Areas on a plane as sets of rectangles, for regions far too large to
+//! have a cell each, like the interior of a trench that is dug millions
+//! of meters at a time. What ranges are on a line, these are on a grid.
+
+use std::ops::Range;
+
+/// The cells in `rows` and `cols`, which leave out their ends like all
+/// ranges. Empty if either of them is.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Rect {
+    pub rows: Range<i64>,
+    pub cols: Range<i64>,
+}
+
+impl Rect {
+    #[must_use]
+    pub fn new(rows: Range<i64>, cols: Range<i64>) -> Self {
+        Self { rows, cols }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty() || self.cols.is_empty()
+    }
+
+    /// The number of cells, which doesn't fit into 64 bits for the
+    /// largest rectangles.
+    #[must_use]
+    pub fn area(&self) -> u128 {
+        let len = |r: &Range<i64>| u128::from(r.end.abs_diff(r.start));
+
+        if self.is_empty() {
+            0
+        } else {
+            len(&self.rows) * len(&self.cols)
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, row: i64, col: i64) -> bool {
+        self.rows.contains(&row) && self.cols.contains(&col)
+    }
+
+    /// The cells in both rectangles, if there are any.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let overlap = |a: &Range<i64>, b: &Range<i64>| a.start.max(b.start)..a.end.min(b.end);
+        let rect = Self::new(
+            overlap(&self.rows, &other.rows),
+            overlap(&self.cols, &other.cols),
+        );
+
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// The cells of `self` that aren't in `other`, as up to four
+    /// rectangles: the full width above and below `other`, and the rows
+    /// they leave out left and right of it.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() {
+                vec![]
+            } else {
+                vec![self.clone()]
+            };
+        };
+
+        [
+            Self::new(self.rows.start..cut.rows.start, self.cols.clone()),
+            Self::new(cut.rows.end..self.rows.end, self.cols.clone()),
+            Self::new(cut.rows.clone(), self.cols.start..cut.cols.start),
+            Self::new(cut.rows.clone(), cut.cols.end..self.cols.end),
+        ]
+        .into_iter()
+        .filter(|r| !r.is_empty())
+        .collect()
+    }
+}
+
+/// A set of cells as rectangles that don't overlap, so the area is their
+/// sum. Operations cut rectangles into pieces, so sets of many ragged
+/// shapes get slow, but a few hundred rectangles are quick.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct RectSet {
+    rects: Vec<Rect>,
+}
+
+impl RectSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The rectangles the set consists of, in no particular order.
+    pub fn rects(&self) -> impl Iterator<Item = &Rect> {
+        self.rects.iter()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    #[must_use]
+    pub fn area(&self) -> u128 {
+        self.rects.iter().map(Rect::area).sum()
+    }
+
+    #[must_use]
+    pub fn contains(&self, row: i64, col: i64) -> bool {
+        self.rects.iter().any(|r| r.contains(row, col))
+    }
+
+    /// Adds the cells of `rect` that aren't in the set yet.
+    pub fn insert(&mut self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+
+        let pieces = self.rects.iter().fold(vec![rect], |pieces, r| {
+            pieces.iter().flat_map(|p| p.difference(r)).collect()
+        });
+        self.rects.extend(pieces);
+    }
+
+    /// Takes the cells of `rect` out of the set.
+    pub fn remove(&mut self, rect: &Rect) {
+        self.rects = self.rects.iter().flat_map(|r| r.difference(rect)).collect();
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for rect in &other.rects {
+            union.insert(rect.clone());
+        }
+        union
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            rects: self
+                .rects
+                .iter()
+                .flat_map(|a| other.rects.iter().filter_map(|b| a.intersection(b)))
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for rect in &other.rects {
+            difference.remove(rect);
+        }
+        difference
+    }
+}
+
+impl FromIterator<Rect> for RectSet {
+    fn from_iter<I: IntoIterator<Item = Rect>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for rect in iter {
+            set.insert(rect);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::Rng as _;
+
+    use super::*;
+    use crate::gen;
+
+    fn cells(set: &RectSet) -> HashSet<(i64, i64)> {
+        set.rects()
+            .flat_map(|r| {
+                r.rows
+                    .clone()
+                    .flat_map(move |row| r.cols.clone().map(move |col| (row, col)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rects_work() {
+        let a = Rect::new(0..4, 0..5);
+        let b = Rect::new(2..6, 3..4);
+        assert_eq!(a.area(), 20);
+        assert_eq!(a.intersection(&b), Some(Rect::new(2..4, 3..4)));
+        assert_eq!(a.intersection(&Rect::new(4..6, 0..5)), None);
+        assert_eq!(a.difference(&b).iter().map(Rect::area).sum::<u128>(), 18);
+        assert!(a.difference(&Rect::new(-1..9, -1..9)).is_empty());
+        assert_eq!(Rect::new(a.rows.end..a.rows.start, 0..5).area(), 0);
+
+        let huge = Rect::new(-4_000_000_000_000..4_000_000_000_000, 0..3_000_000_000_000);
+        assert_eq!(huge.area(), 24_000_000_000_000_000_000_000_000);
+    }
+
+    #[test]
+    fn sets_match_cells() {
+        let mut rng = gen::rng(18);
+        let mut random_set = || {
+            (0..rng.gen_range(0..6))
+                .map(|_| {
+                    let (row, col) = (rng.gen_range(-10..10), rng.gen_range(-10..10));
+                    Rect::new(
+                        row..row + rng.gen_range(0..8),
+                        col..col + rng.gen_range(0..8),
+                    )
+                })
+                .collect::<RectSet>()
+        };
+
+        for _ in 0..200 {
+            let (a, b) = (random_set(), random_set());
+            let (cells_a, cells_b) = (cells(&a), cells(&b));
+            assert_eq!(a.area(), cells_a.len() as u128);
+            assert_eq!(a.is_empty(), cells_a.is_empty());
+
+            let union = a.union(&b);
+            assert_eq!(cells(&union), &cells_a | &cells_b);
+            assert_eq!(union.area(), (&cells_a | &cells_b).len() as u128);
+
+            let intersection = a.intersection(&b);
+            assert_eq!(cells(&intersection), &cells_a & &cells_b);
+            assert_eq!(intersection.area(), (&cells_a & &cells_b).len() as u128);
+
+            let difference = a.difference(&b);
+            assert_eq!(cells(&difference), &cells_a - &cells_b);
+            assert_eq!(difference.area(), (&cells_a - &cells_b).len() as u128);
+
+            assert_eq!(union.contains(0, 0), cells(&union).contains(&(0, 0)));
+        }
+    }
+}
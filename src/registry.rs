@@ -0,0 +1,286 @@
+//! All solutions of all events, keyed by year and day.
+
+use std::{path::PathBuf, time::Instant};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    aoc2023,
+    dump::Table,
+    error::{ParseError, SolveError},
+    events,
+    gen::Rng,
+    input,
+    lint::Diagnostic,
+    output::{self, Message},
+    profile,
+    results::{self, PartResult},
+    shrink::Fails,
+    solution::{Answer, ExplainQuery, Report, Solution},
+    viz::Drawing,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    /// How many parts are implemented.
+    pub parts: u8,
+    /// Parses the input and prints both answers.
+    pub solve: fn(&str) -> Result<()>,
+    /// Like `solve`, but writes a flamegraph of each part on its own to
+    /// the path it gives for the part.
+    pub profile: fn(&str, &dyn Fn(u8) -> PathBuf) -> Result<()>,
+    /// Parses the input and computes one part without printing it. Gives
+    /// nothing if the part isn't implemented.
+    pub solve_part: fn(&str, u8) -> Result<Option<PartResult>, SolveError>,
+    /// Compares the answers with the reference solvers and fails if they
+    /// disagree.
+    pub check: fn(&str) -> Result<()>,
+    /// Whether the solvers disagree with the references on an input.
+    /// Inputs that don't parse don't count.
+    pub disagrees: fn(&str) -> bool,
+    pub shrink_input: fn(&str, &mut Fails<'_>) -> String,
+    pub explain: fn(&str, &ExplainQuery) -> Result<Report>,
+    pub lint: fn(&str) -> Vec<Diagnostic>,
+    pub dump: fn(&str) -> Result<Table>,
+    pub breakdown: fn(&str) -> Result<Option<Table>>,
+    pub render: fn(&str) -> Result<Drawing>,
+    pub generate: fn(&mut Rng, usize) -> Result<String>,
+}
+
+/// Prints the answer and passes it on to [`results`] and [`events`].
+fn finish_part(part: u8, answer: Answer, start: Instant) {
+    let time = start.elapsed();
+    let result = PartResult { part, answer, time };
+
+    output::solution(part, &result.answer);
+    events::part_finished(&result);
+    results::record(result);
+}
+
+fn solve<S: Solution>(input: &str) -> Result<()> {
+    solve_sampled::<S>(input, None)
+}
+
+fn profile<S: Solution>(input: &str, svg_path: &dyn Fn(u8) -> PathBuf) -> Result<()> {
+    solve_sampled::<S>(input, Some(svg_path))
+}
+
+/// Runs `f` for `part`, sampling it into the flamegraph at `svg_path` if
+/// there is one.
+fn sample<T>(
+    svg_path: Option<&dyn Fn(u8) -> PathBuf>,
+    part: u8,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    match svg_path {
+        Some(svg_path) => profile::flamegraph(&svg_path(part), f),
+        None => f(),
+    }
+}
+
+fn solve_sampled<S: Solution>(input: &str, svg_path: Option<&dyn Fn(u8) -> PathBuf>) -> Result<()> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    results::record_parse(start.elapsed());
+
+    events::part_started(1);
+    let start = Instant::now();
+    finish_part(1, sample(svg_path, 1, || S::part1(&parsed))?, start);
+
+    if S::PARTS >= 2 {
+        events::part_started(2);
+    }
+    let start = Instant::now();
+    match (S::PARTS >= 2)
+        .then(|| sample(svg_path, 2, || S::part2(&parsed)))
+        .transpose()?
+        .flatten()
+    {
+        Some(answer) => finish_part(2, answer, start),
+        None => output::say(Message::PartNotImplemented(2)),
+    }
+
+    Ok(())
+}
+
+fn solve_part<S: Solution>(input: &str, part: u8) -> Result<Option<PartResult>, SolveError> {
+    let start = Instant::now();
+    let parsed = S::parse(input).map_err(|e| ParseError::new(&e, &S::lint(input)))?;
+    results::record_parse(start.elapsed());
+
+    let start = Instant::now();
+    let answer = match part {
+        1 => Some(S::part1(&parsed)?),
+        2 if S::PARTS >= 2 => S::part2(&parsed)?,
+        _ => None,
+    };
+
+    Ok(answer.map(|answer| PartResult {
+        part,
+        answer,
+        time: start.elapsed(),
+    }))
+}
+
+/// The answer of one part next to what its reference solver says.
+struct Comparison {
+    part: u8,
+    answer: Answer,
+    reference: Answer,
+}
+
+fn compare<S: Solution>(input: &str) -> Result<Vec<Comparison>> {
+    let parsed = S::parse(input)?;
+    let [reference1, reference2] = S::reference(&parsed)?;
+
+    // Only run parts that have a reference, the others may be slow.
+    let answers: [Option<Result<_>>; 2] = [
+        reference1.map(|r| Ok((r, Some(S::part1(&parsed)?)))),
+        reference2.map(|r| Ok((r, S::part2(&parsed)?))),
+    ];
+
+    let mut comparisons = Vec::new();
+    for (part, answers) in (1..).zip(answers) {
+        if let Some((reference, Some(answer))) = answers.transpose()? {
+            comparisons.push(Comparison {
+                part,
+                answer,
+                reference,
+            });
+        }
+    }
+
+    Ok(comparisons)
+}
+
+fn disagrees<S: Solution>(input: &str) -> bool {
+    compare::<S>(input).is_ok_and(|c| c.iter().any(|c| c.answer != c.reference))
+}
+
+fn check<S: Solution>(input: &str) -> Result<()> {
+    let comparisons = compare::<S>(input)?;
+
+    if comparisons.is_empty() {
+        output::say(Message::NoReference);
+    }
+
+    for part in S::PARTS + 1..=2 {
+        output::say(Message::PartNotImplemented(part));
+    }
+
+    for Comparison {
+        part,
+        answer,
+        reference,
+    } in comparisons
+    {
+        if answer != reference {
+            bail!(
+                "{}",
+                Message::CheckDisagrees {
+                    part,
+                    answer: &answer,
+                    reference: &reference
+                }
+            );
+        }
+
+        output::say(Message::CheckAgrees {
+            part,
+            answer: &answer,
+        });
+    }
+
+    Ok(())
+}
+
+fn explain<S: Solution>(input: &str, query: &ExplainQuery) -> Result<Report> {
+    S::explain(&S::parse(input)?, query)
+}
+
+fn dump<S: Solution>(input: &str) -> Result<Table> {
+    S::dump(&S::parse(input)?)
+}
+
+fn breakdown<S: Solution>(input: &str) -> Result<Option<Table>> {
+    S::breakdown(&S::parse(input)?)
+}
+
+fn render<S: Solution>(input: &str) -> Result<Drawing> {
+    S::render(&S::parse(input)?)
+}
+
+impl Puzzle {
+    #[must_use]
+    pub fn new<S: Solution>(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            parts: S::PARTS,
+            solve: solve::<S>,
+            profile: profile::<S>,
+            solve_part: solve_part::<S>,
+            check: check::<S>,
+            disagrees: disagrees::<S>,
+            shrink_input: S::shrink_input,
+            explain: explain::<S>,
+            lint: S::lint,
+            dump: dump::<S>,
+            breakdown: breakdown::<S>,
+            render: render::<S>,
+            generate: S::generate,
+        }
+    }
+
+    pub fn input(&self) -> Result<String> {
+        input::load(self.year, self.day)
+    }
+}
+
+/// Returns all puzzles of all events ordered by year and day.
+#[must_use]
+pub fn all() -> Vec<Puzzle> {
+    // Later events go here: aoc2022::puzzles(), aoc2024::puzzles(), ...
+    aoc2023::puzzles()
+}
+
+/// Returns all puzzles of one event.
+#[must_use]
+pub fn year(year: u16) -> Vec<Puzzle> {
+    all().into_iter().filter(|p| p.year == year).collect()
+}
+
+#[must_use]
+pub fn find(year: u16, day: u8) -> Option<Puzzle> {
+    all().into_iter().find(|p| p.year == year && p.day == day)
+}
+
+/// The most recent event we have solutions for.
+#[must_use]
+pub fn latest_year() -> u16 {
+    all().iter().map(|p| p.year).max().unwrap_or(aoc2023::YEAR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzles_are_unique_and_sorted() {
+        let keys = all().iter().map(|p| (p.year, p.day)).collect::<Vec<_>>();
+
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    // Counts on all days being compiled in.
+    #[cfg(feature = "default")]
+    #[test]
+    fn can_find_puzzles() {
+        assert_eq!(find(2023, 1).map(|p| p.day), Some(1));
+        assert!(find(2023, 26).is_none());
+        assert!(find(1999, 1).is_none());
+        assert_eq!(year(2023).len(), 13);
+    }
+}
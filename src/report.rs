@@ -0,0 +1,189 @@
+//! A shareable page with the answers and timings of a whole event.
+
+use std::{fmt::Write, time::Duration};
+
+use anyhow::Result;
+
+use crate::{
+    dump::Table,
+    registry::Puzzle,
+    results::PartResult,
+    template::{self, escape_html},
+};
+
+const TEMPLATE: &str = include_str!("templates/report.html");
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DayResults {
+    pub day: u8,
+    pub parts: Vec<PartResult>,
+    /// How the answers break down, for the days that say.
+    pub breakdown: Option<Table>,
+}
+
+/// The breakdown of `puzzle` on its input. Days without one, or whose
+/// input is missing or broken, have none.
+#[must_use]
+pub fn breakdown(puzzle: &Puzzle) -> Option<Table> {
+    (puzzle.breakdown)(&puzzle.input().ok()?).ok().flatten()
+}
+
+/// Horizontal bars scaled to the slowest part, in tenths of a percent.
+fn chart(days: &[DayResults]) -> String {
+    let slowest = days
+        .iter()
+        .flat_map(|d| &d.parts)
+        .map(|p| p.time.as_nanos())
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let mut chart = String::new();
+
+    for day in days {
+        for part in &day.parts {
+            let permille = part.time.as_nanos() * 1000 / slowest;
+
+            let _ = writeln!(
+                chart,
+                "<div class=\"row\"><span class=\"label\">Day {} part {}</span>\
+                 <div class=\"track\"><div class=\"bar part{}\" style=\"width: {}.{}%\"></div></div>\
+                 <span class=\"time\">{}</span></div>",
+                day.day,
+                part.part,
+                part.part,
+                permille / 10,
+                permille % 10,
+                format_time(part.time)
+            );
+        }
+    }
+
+    chart
+}
+
+fn table(days: &[DayResults]) -> String {
+    let mut table = String::new();
+
+    for day in days {
+        let _ = write!(table, "<tr><td>{}</td>", day.day);
+
+        for part in 1..=2 {
+            match day.parts.iter().find(|p| p.part == part) {
+                Some(p) => {
+                    let _ = write!(
+                        table,
+                        "<td>{}</td><td>{}</td>",
+                        escape_html(&p.answer.to_string()),
+                        format_time(p.time)
+                    );
+                }
+                None => table.push_str("<td>-</td><td></td>"),
+            }
+        }
+
+        table.push_str("</tr>\n");
+    }
+
+    table
+}
+
+/// A table per day with a breakdown, under the day as a heading.
+fn breakdowns(days: &[DayResults]) -> String {
+    let mut html = String::new();
+
+    for day in days {
+        let Some(breakdown) = &day.breakdown else {
+            continue;
+        };
+
+        let _ = writeln!(html, "<h3>Day {}</h3>\n<table>", day.day);
+        let cells = |tag: &str, cells: Vec<String>| {
+            cells
+                .iter()
+                .map(|c| format!("<{tag}>{}</{tag}>", escape_html(c)))
+                .collect::<String>()
+        };
+        let columns = breakdown.columns.iter().map(|c| c.to_string()).collect();
+        let _ = writeln!(html, "<tr>{}</tr>", cells("th", columns));
+        for row in &breakdown.rows {
+            let row = row.iter().map(ToString::to_string).collect();
+            let _ = writeln!(html, "<tr>{}</tr>", cells("td", row));
+        }
+        html.push_str("</table>\n");
+    }
+
+    html
+}
+
+fn format_time(time: Duration) -> String {
+    format!("{time:.1?}")
+}
+
+/// A self-contained HTML page with a timing chart and all answers.
+pub fn render_html(year: u16, days: &[DayResults]) -> Result<String> {
+    template::render(
+        TEMPLATE,
+        &[
+            ("year", &year.to_string()),
+            ("chart", &chart(days)),
+            ("table", &table(days)),
+            ("breakdowns", &breakdowns(days)),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Answer;
+
+    #[test]
+    fn renders_results() -> Result<()> {
+        let days = [
+            DayResults {
+                day: 1,
+                parts: vec![
+                    PartResult {
+                        part: 1,
+                        answer: Answer::UInt(142),
+                        time: Duration::from_millis(2),
+                    },
+                    PartResult {
+                        part: 2,
+                        answer: Answer::Text("<b>".to_owned()),
+                        time: Duration::from_millis(8),
+                    },
+                ],
+                breakdown: None,
+            },
+            DayResults {
+                day: 2,
+                parts: vec![],
+                breakdown: None,
+            },
+            DayResults {
+                day: 3,
+                parts: vec![],
+                breakdown: Some(
+                    Table::new(&["symbol", "sum"])
+                        .row(vec![Answer::Text("*".to_owned()), Answer::UInt(467)])
+                        .clone(),
+                ),
+            },
+        ];
+
+        let html = render_html(2023, &days)?;
+
+        assert!(html.contains("<title>Advent of Code 2023 results</title>"));
+        assert!(html.contains("style=\"width: 25.0%\""));
+        assert!(html.contains("style=\"width: 100.0%\""));
+        assert!(html.contains("<td>142</td><td>2.0ms</td><td>&lt;b&gt;</td>"));
+        assert!(html.contains("<tr><td>2</td><td>-</td><td></td><td>-</td><td></td></tr>"));
+        assert!(html.contains("<h3>Day 3</h3>\n<table>\n<tr><th>symbol</th><th>sum</th></tr>"));
+        assert!(html.contains("<tr><td>*</td><td>467</td></tr>\n</table>"));
+        assert!(!html.contains("<h3>Day 1</h3>"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! Structured results of running solutions, for everything that needs
+//! more than the printed text: stars, reports and so on.
+
+use std::{
+    cell::RefCell,
+    panic,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{cancel, registry::Puzzle, solution::Answer};
+
+/// The answer to one part and how long it took to compute.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = RefCell::default();
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+    parts: Vec<PartResult>,
+    parse_times: Vec<Duration>,
+}
+
+/// Collects the [`PartResult`]s of solutions and how long parsing the
+/// input took.
+#[derive(Debug, Default, Clone)]
+pub struct Recorder(Arc<Mutex<Recording>>);
+
+impl Recorder {
+    /// Records all results on the current thread from now on.
+    pub fn attach(&self) {
+        RECORDER.with(|r| *r.borrow_mut() = Some(self.clone()));
+    }
+
+    #[must_use]
+    pub fn results(&self) -> Vec<PartResult> {
+        self.0.lock().map(|r| r.parts.clone()).unwrap_or_default()
+    }
+
+    /// How long parsing took, once per run.
+    #[must_use]
+    pub fn parse_times(&self) -> Vec<Duration> {
+        self.0
+            .lock()
+            .map(|r| r.parse_times.clone())
+            .unwrap_or_default()
+    }
+}
+
+fn with_recording(f: impl FnOnce(&mut Recording)) {
+    RECORDER.with(|r| {
+        if let Some(recorder) = &*r.borrow() {
+            if let Ok(mut recording) = recorder.0.lock() {
+                f(&mut recording);
+            }
+        }
+    });
+}
+
+/// Hands a result to the recorder attached to this thread, if any.
+pub fn record(result: PartResult) {
+    with_recording(|r| r.parts.push(result));
+}
+
+/// Hands the time it took to parse the input to the recorder attached
+/// to this thread, if any.
+pub fn record_parse(time: Duration) {
+    with_recording(|r| r.parse_times.push(time));
+}
+
+/// Runs a puzzle and returns the results of its parts. Parts solved
+/// before failing or running out of time are still included, a missing
+/// input means no results.
+#[must_use]
+pub fn collect(puzzle: &Puzzle, timeout: Duration) -> Vec<PartResult> {
+    let recorder = Recorder::default();
+    let worker_recorder = recorder.clone();
+    let solve = puzzle.solve;
+
+    let Ok(input) = puzzle.input() else {
+        return vec![];
+    };
+
+    let _ = panic::catch_unwind(|| {
+        cancel::run_with_timeout(timeout, move || {
+            worker_recorder.attach();
+            solve(&input)
+        })
+    });
+
+    recorder.results()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_attached_threads_record() {
+        let result = PartResult {
+            part: 1,
+            answer: Answer::UInt(7),
+            time: Duration::from_millis(1),
+        };
+
+        record(result.clone());
+        record_parse(Duration::from_millis(2));
+
+        let recorder = Recorder::default();
+        recorder.attach();
+        record(result.clone());
+        record_parse(Duration::from_millis(3));
+
+        assert_eq!(recorder.results(), [result]);
+        assert_eq!(recorder.parse_times(), [Duration::from_millis(3)]);
+    }
+}
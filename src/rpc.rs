@@ -0,0 +1,101 @@
+//! Solving over stdin and stdout, for editor plugins and test harnesses
+//! that keep the process around. Every line of input is a request,
+//!
+//! ```text
+//! {"day":5,"part":2,"input":"seeds: 79 14 55 13\n..."}
+//! ```
+//!
+//! and gets a line with the same JSON as [`serve`](crate::serve) sends.
+//! An `id` in the request is passed back, to match up the answers.
+
+use std::{
+    io::{BufRead, Write},
+    time::Duration,
+};
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::serve;
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Option<Value>,
+    day: u8,
+    part: u8,
+    input: String,
+}
+
+/// The reply to one line of input.
+#[must_use]
+pub fn answer(year: u16, line: &str, timeout: Option<Duration>) -> Value {
+    let request = match serde_json::from_str::<Request>(line) {
+        Ok(request) => request,
+        Err(e) => return json!({ "error": format!("Invalid request: {e}") }),
+    };
+
+    let mut reply = match request.part {
+        1..=2 => serve::solve(year, request.day, request.part, &request.input, timeout).body,
+        part => json!({ "error": format!("There is no part {part}") }),
+    };
+
+    if let (Some(id), Some(reply)) = (request.id, reply.as_object_mut()) {
+        reply.insert("id".to_owned(), id);
+    }
+
+    reply
+}
+
+/// Answers requests from `reader` on `writer` until the input ends.
+pub fn run(
+    year: u16,
+    reader: impl BufRead,
+    mut writer: impl Write,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        writeln!(writer, "{}", answer(year, &line, timeout))?;
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+// Counts on day 1 being compiled in.
+#[cfg(all(test, feature = "day1"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_every_line() -> Result<()> {
+        let requests = r#"{"id":7,"day":1,"part":1,"input":"1abc2\npqr3stu8vwx\n"}
+
+{"day":1,"part":3,"input":""}
+not json
+"#;
+        let mut replies = vec![];
+        run(2023, requests.as_bytes(), &mut replies, None)?;
+
+        let replies = String::from_utf8(replies)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<serde_json::Result<Vec<Value>>>()?;
+
+        assert_eq!(replies.len(), 3);
+        assert_eq!(replies[0]["answer"], 50);
+        assert_eq!(replies[0]["id"], 7);
+        assert_eq!(replies[1]["error"], "There is no part 3");
+        assert!(replies[2]["error"]
+            .as_str()
+            .is_some_and(|e| e.starts_with("Invalid request")));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+//! Starts a new day: a module with a skeleton solution, registered in
+//! its event's `mod.rs` behind a feature of the same name.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::template;
+
+const TEMPLATE: &str = include_str!("templates/day.rs");
+
+/// The skeleton solution of `day`.
+pub fn module(day: u8) -> Result<String> {
+    template::render(TEMPLATE, &[("day", &day.to_string())])
+}
+
+/// Inserts `new` in front of the first line for which `key` is greater
+/// than `new_key`, or after the last line that has a key at all.
+/// Attributes stay with the line below them.
+fn insert_sorted<K: PartialOrd>(
+    lines: &mut Vec<String>,
+    key: impl Fn(&str) -> Option<K>,
+    new_key: &K,
+    new: &[String],
+    what: &str,
+) -> Result<()> {
+    let keyed = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| key(l).map(|k| (i, k)))
+        .collect::<Vec<_>>();
+
+    let Some((last, _)) = keyed.last() else {
+        bail!("Found no {what} to add to");
+    };
+
+    let at = match keyed.iter().find(|(_, k)| k > new_key) {
+        Some(&(mut at, _)) => {
+            while at > 0 && lines[at - 1].trim_start().starts_with("#[") {
+                at -= 1;
+            }
+            at
+        }
+        None => last + 1,
+    };
+
+    lines.splice(at..at, new.iter().cloned());
+
+    Ok(())
+}
+
+/// Adds `day` to the `mod.rs` of an event: its module declaration and
+/// its entry in `puzzles()`, both in order and behind its feature.
+pub fn register(mod_rs: &str, day: u8) -> Result<String> {
+    let name = format!("day{day}");
+    let cfg = format!("#[cfg(feature = \"{name}\")]");
+
+    if mod_rs.contains(&format!("pub mod {name};")) {
+        bail!("Day {day} is already registered");
+    }
+
+    let mut lines = mod_rs.lines().map(str::to_owned).collect::<Vec<_>>();
+
+    // Module declarations are sorted like rustfmt does it: by name as
+    // string, so day10 comes before day2.
+    insert_sorted(
+        &mut lines,
+        |l| {
+            l.strip_prefix("pub mod ")?
+                .strip_suffix(';')
+                .map(str::to_owned)
+        },
+        &name,
+        &[cfg.clone(), format!("pub mod {name};")],
+        "day modules",
+    )?;
+
+    // Puzzles are sorted by day.
+    insert_sorted(
+        &mut lines,
+        |l| {
+            l.trim_start()
+                .strip_prefix("Puzzle::new::<")?
+                .strip_suffix("),")?
+                .rsplit_once(", ")?
+                .1
+                .parse::<u8>()
+                .ok()
+        },
+        &day,
+        &[
+            format!("        {cfg}"),
+            format!("        Puzzle::new::<{name}::Day{day}>(YEAR, {day}),"),
+        ],
+        "puzzles",
+    )?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the feature of `day` to `Cargo.toml` and enables it by default.
+pub fn add_feature(cargo_toml: &str, day: u8) -> Result<String> {
+    let mut lines = cargo_toml.lines().map(str::to_owned).collect::<Vec<_>>();
+
+    insert_sorted(
+        &mut lines,
+        |l| {
+            l.trim()
+                .strip_prefix("\"day")?
+                .strip_suffix("\",")?
+                .parse::<u8>()
+                .ok()
+        },
+        &day,
+        &[format!("    \"day{day}\",")],
+        "default days",
+    )?;
+    insert_sorted(
+        &mut lines,
+        |l| {
+            l.strip_prefix("day")?
+                .split_once(" = ")?
+                .0
+                .parse::<u8>()
+                .ok()
+        },
+        &day,
+        &[format!("day{day} = []")],
+        "day features",
+    )?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Writes the module of `day` and an empty example for it into
+/// `src/aoc<year>` below `root`, registers it and adds its feature. Returns the path of the new
+/// module.
+pub fn day(root: &Path, year: u16, day: u8) -> Result<PathBuf> {
+    let dir = root.join("src").join(format!("aoc{year}"));
+    let path = dir.join(format!("day{day}.rs"));
+    let mod_rs = dir.join("mod.rs");
+    let cargo_toml = root.join("Cargo.toml");
+
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+
+    let registered = register(&read(&mod_rs)?, day)?;
+    let features = add_feature(&read(&cargo_toml)?, day)?;
+
+    write(&path, &module(day)?)?;
+    write(&dir.join(format!("day{day}.example")), "")?;
+    write(&mod_rs, &registered)?;
+    write(&cargo_toml, &features)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "use crate::registry::Puzzle;
+
+#[cfg(feature = \"day1\")]
+pub mod day1;
+#[cfg(feature = \"day10\")]
+pub mod day10;
+#[cfg(feature = \"day2\")]
+pub mod day2;
+
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        #[cfg(feature = \"day1\")]
+        Puzzle::new::<day1::Day1>(YEAR, 1),
+        #[cfg(feature = \"day2\")]
+        Puzzle::new::<day2::Day2>(YEAR, 2),
+        #[cfg(feature = \"day10\")]
+        Puzzle::new::<day10::Day10>(YEAR, 10),
+    ]
+}
+";
+
+    const CARGO_TOML: &str = "[features]
+default = [
+    \"day1\",
+    \"day10\",
+]
+day1 = []
+day10 = [\"dep:regex\"]
+profile = [\"dep:pprof\"]
+";
+
+    #[test]
+    fn registers_in_order() -> Result<()> {
+        let registered = register(MOD_RS, 3)?;
+
+        assert!(registered.contains("pub mod day2;\n#[cfg(feature = \"day3\")]\npub mod day3;\n\n"));
+        assert!(registered.contains(
+            "(YEAR, 2),\n        #[cfg(feature = \"day3\")]\n        Puzzle::new::<day3::Day3>(YEAR, 3),\n        #[cfg(feature = \"day10\")]"
+        ));
+
+        let registered = register(MOD_RS, 11)?;
+
+        assert!(registered.contains(
+            "pub mod day10;\n#[cfg(feature = \"day11\")]\npub mod day11;\n#[cfg(feature = \"day2\")]"
+        ));
+        assert!(registered.contains("Puzzle::new::<day11::Day11>(YEAR, 11),\n    ]"));
+
+        assert!(register(MOD_RS, 10).is_err());
+        assert!(module(3)?.contains("impl Solution for Day3 {"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn adds_features() -> Result<()> {
+        assert_eq!(
+            add_feature(CARGO_TOML, 2)?,
+            "[features]
+default = [
+    \"day1\",
+    \"day2\",
+    \"day10\",
+]
+day1 = []
+day2 = []
+day10 = [\"dep:regex\"]
+profile = [\"dep:pprof\"]
+"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+//! A small HTTP server that runs solutions on inputs it is sent, so web
+//! pages and scripts can use them without starting a process each time:
+//!
+//! ```text
+//! POST /solve/{day}/{part}
+//! ```
+//!
+//! The body is the raw input, the reply is JSON with the answer and how
+//! long parsing and solving took. Broken inputs get a 400 with the line
+//! and column of the problem, failing solutions a 500. Only the bits of
+//! HTTP/1.1 that this needs are understood, every connection serves a
+//! single request.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    panic,
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+
+use crate::{
+    cancel,
+    dump::json_value,
+    error::SolveError,
+    output::{self, Message},
+    registry,
+    results::Recorder,
+};
+
+/// Larger inputs than this are refused before reading them.
+const MAX_BODY: usize = 64 << 20;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+/// Reads one request. Bodies need a `Content-Length`.
+pub fn read_request(reader: &mut impl BufRead) -> Result<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        bail!("Malformed request line: {}", line.trim_end());
+    };
+    let (method, path) = (method.to_owned(), path.to_owned());
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Bad Content-Length: {}", value.trim()))?;
+            }
+        }
+    }
+
+    if length > MAX_BODY {
+        bail!("The input is larger than {MAX_BODY} bytes");
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method,
+        path,
+        body: String::from_utf8(body).context("The input is not UTF-8")?,
+    })
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn error(status: u16, error: impl ToString) -> Self {
+        Self {
+            status,
+            body: json!({ "error": error.to_string() }),
+        }
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            204 => "No Content",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        };
+        let body = if self.status == 204 {
+            String::new()
+        } else {
+            self.body.to_string()
+        };
+
+        // Pages served from elsewhere may call us, too.
+        write!(
+            writer,
+            "HTTP/1.1 {} {reason}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type\r\n\
+             Connection: close\r\n\r\n{body}",
+            self.status,
+            body.len()
+        )?;
+
+        Ok(writer.flush()?)
+    }
+}
+
+/// Solves a part, giving up after `timeout` if there is one. Also
+/// answers [`rpc`](crate::rpc) requests.
+pub(crate) fn solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: &str,
+    timeout: Option<Duration>,
+) -> Response {
+    let Some(puzzle) = registry::find(year, day) else {
+        return Response::error(404, Message::InvalidDay);
+    };
+
+    let recorder = Recorder::default();
+    let worker_recorder = recorder.clone();
+    let input: Arc<str> = input.into();
+    let run = move || {
+        worker_recorder.attach();
+        Ok((puzzle.solve_part)(&input, part))
+    };
+
+    let result = match panic::catch_unwind(move || match timeout {
+        Some(limit) => cancel::run_with_timeout(limit, run),
+        None => run(),
+    }) {
+        Ok(Ok(result)) => result,
+        Ok(Err(timed_out)) => return Response::error(503, format!("{timed_out:#}")),
+        Err(_) => return Response::error(500, "panicked"),
+    };
+
+    match result {
+        Ok(Some(result)) => Response {
+            status: 200,
+            body: json!({
+                "year": year,
+                "day": day,
+                "part": part,
+                "answer": json_value(&result.answer),
+                "parse_time_ns": recorder.parse_times().first().map(Duration::as_nanos),
+                "time_ns": result.time.as_nanos(),
+            }),
+        },
+        Ok(None) => Response::error(404, Message::PartNotImplemented(part)),
+        Err(SolveError::Parse(e)) => Response {
+            status: 400,
+            body: json!({ "error": e.to_string(), "line": e.line, "column": e.column }),
+        },
+        Err(e @ SolveError::Cancelled(_)) => Response::error(503, e),
+        Err(e) => Response::error(500, format!("{e:#}")),
+    }
+}
+
+/// Answers a request for the solutions of `year`.
+#[must_use]
+pub fn handle(year: u16, request: &Request, timeout: Option<Duration>) -> Response {
+    let segments = request.path.split('/').skip(1).collect::<Vec<_>>();
+
+    let ["solve", day, part] = segments[..] else {
+        return Response::error(404, format!("No such endpoint: {}", request.path));
+    };
+    let (Ok(day), Ok(part @ 1..=2)) = (day.parse::<u8>(), part.parse::<u8>()) else {
+        return Response::error(404, format!("No such part: {day}/{part}"));
+    };
+
+    match request.method.as_str() {
+        "POST" => solve(year, day, part, &request.body, timeout),
+        "OPTIONS" => Response {
+            status: 204,
+            body: Value::Null,
+        },
+        method => Response::error(405, format!("Use POST, not {method}")),
+    }
+}
+
+fn connection(year: u16, stream: TcpStream, timeout: Option<Duration>) -> Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let response = match read_request(&mut reader) {
+        Ok(request) => handle(year, &request, timeout),
+        Err(e) => Response::error(400, format!("{e:#}")),
+    };
+
+    response.write_to(&mut &stream)
+}
+
+/// Serves the solutions of `year` on `port` of localhost until killed.
+/// Each connection gets its own thread, so a slow day doesn't hold up
+/// the others.
+pub fn serve(year: u16, port: u16, timeout: Option<Duration>) -> Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .with_context(|| format!("Failed to listen on port {port}"))?;
+
+    output::say(Message::Serving(listener.local_addr()?.port()));
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+
+        thread::spawn(move || {
+            // The client hung up or sent garbage, nothing to do about it.
+            let _ = connection(year, stream, timeout);
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request {
+            method: method.to_owned(),
+            path: path.to_owned(),
+            body: body.to_owned(),
+        }
+    }
+
+    #[test]
+    fn reads_requests() -> Result<()> {
+        let raw =
+            "POST /solve/1/2 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 5\r\n\r\nab\ncdef";
+
+        assert_eq!(
+            read_request(&mut raw.as_bytes())?,
+            request("POST", "/solve/1/2", "ab\ncd")
+        );
+        assert!(read_request(&mut "\r\n".as_bytes()).is_err());
+
+        let mut written = vec![];
+        Response::error(404, "gone").write_to(&mut written)?;
+        let written = String::from_utf8(written)?;
+        assert!(written.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(written.ends_with("\r\n\r\n{\"error\":\"gone\"}"));
+
+        Ok(())
+    }
+
+    // Counts on day 1 being compiled in.
+    #[cfg(feature = "default")]
+    #[test]
+    fn solves_parts() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+        let response = handle(2023, &request("POST", "/solve/1/1", input), None);
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["answer"], 142);
+        assert!(response.body["time_ns"].is_u64());
+
+        assert_eq!(
+            handle(2023, &request("GET", "/solve/1/1", ""), None).status,
+            405
+        );
+        assert_eq!(
+            handle(2023, &request("POST", "/solve/1/3", ""), None).status,
+            404
+        );
+        assert_eq!(
+            handle(2023, &request("POST", "/solve/26/1", ""), None).status,
+            404
+        );
+        assert_eq!(
+            handle(2023, &request("POST", "/solve/13/2", "#.\n.#\n"), None).status,
+            404
+        );
+
+        let response = handle(
+            2023,
+            &request("POST", "/solve/2/1", "Game 1: 3 blue\ngarbage"),
+            None,
+        );
+        assert_eq!(response.status, 400);
+        assert_eq!(response.body["line"], 2);
+        assert_eq!(response.body["column"], 1);
+        assert_eq!(
+            response.body["error"],
+            "2:1: Expected a game like `Game 1: 3 blue`, got \"garbage\""
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Shrinks inputs on which a day's solvers disagree to a small
+//! reproducer, by dropping parts of it for as long as the disagreement
+//! persists. Days pick what to drop in
+//! [`Solution::shrink_input`](crate::solution::Solution::shrink_input).
+
+/// Tells whether the solvers still disagree on a smaller input.
+pub type Fails<'a> = dyn FnMut(&str) -> bool + 'a;
+
+/// Drops lines of `input` while `fails` holds.
+pub fn lines(input: &str, fails: &mut Fails<'_>) -> String {
+    shrink(input.lines().collect(), "\n", fails)
+}
+
+/// Drops blocks separated by empty lines while `fails` holds.
+pub fn blocks(input: &str, fails: &mut Fails<'_>) -> String {
+    shrink(
+        input
+            .split("\n\n")
+            .map(|b| b.trim_matches('\n'))
+            .filter(|b| !b.is_empty())
+            .collect(),
+        "\n\n",
+        fails,
+    )
+}
+
+fn join(units: &[&str], separator: &str) -> String {
+    let mut joined = units.join(separator);
+    joined.push('\n');
+    joined
+}
+
+/// Delta debugging: tries to drop chunks of units, starting with halves
+/// and going down to single units until none can be dropped anymore.
+fn shrink(mut units: Vec<&str>, separator: &str, fails: &mut Fails<'_>) -> String {
+    if !fails(&join(&units, separator)) {
+        return join(&units, separator);
+    }
+
+    let mut chunk = (units.len() / 2).max(1);
+    loop {
+        let mut dropped = false;
+        let mut start = 0;
+
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate = [&units[..start], &units[end..]].concat();
+
+            if fails(&join(&candidate, separator)) {
+                units = candidate;
+                dropped = true;
+            } else {
+                start = end;
+            }
+        }
+
+        if !dropped {
+            if chunk == 1 {
+                return join(&units, separator);
+            }
+            chunk /= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_what_fails() {
+        let input = (1..=20).map(|i| format!("{i}\n")).collect::<String>();
+        let mut runs = 0;
+
+        let shrunk = lines(&input, &mut |i| {
+            runs += 1;
+            i.lines().any(|l| l == "3") && i.lines().any(|l| l == "17")
+        });
+
+        assert_eq!(shrunk, "3\n17\n");
+        assert!(runs < 100, "{runs} runs");
+
+        // Inputs that don't fail stay as they are.
+        assert_eq!(lines("a\nb\n", &mut |_| false), "a\nb\n");
+    }
+
+    #[test]
+    fn drops_whole_blocks() {
+        let shrunk = blocks("a\nb\n\nc\nd\n\ne\n", &mut |i| i.contains("c\nd"));
+
+        assert_eq!(shrunk, "c\nd\n");
+    }
+}
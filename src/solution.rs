@@ -0,0 +1,285 @@
+//! The interface every puzzle solution implements.
+
+use std::{collections::BTreeMap, fmt::Display, ops::Range, str::FromStr, sync::OnceLock};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::{
+    dump::Table,
+    error::{DayError, ParseError},
+    gen::Rng,
+    lint::Diagnostic,
+    shrink::{self, Fails},
+    viz::Drawing,
+};
+
+pub trait Solution {
+    /// The puzzle input after parsing.
+    type Parsed;
+
+    /// The ways the input can be broken.
+    type Error: DayError;
+
+    /// How many parts are implemented. Days that only solve part 1 so
+    /// far say so, and the runner reports part 2 as missing.
+    const PARTS: u8 = 2;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error>;
+
+    /// Checks `input` against the expected format and says where it
+    /// deviates. By default it is just parsed, which can't tell where
+    /// the problem is.
+    fn lint(input: &str) -> Vec<Diagnostic> {
+        Self::parse(input)
+            .err()
+            .map(|e| Diagnostic {
+                line: 1,
+                column: 1,
+                message: ParseError::new(&e, &[]).message,
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer>;
+
+    /// `None` while the second part isn't solved yet.
+    fn part2(_parsed: &Self::Parsed) -> Result<Option<Answer>> {
+        Ok(None)
+    }
+
+    /// Produces diagnostics about the input or the way it is solved.
+    /// What is reported can be steered with `query`.
+    fn explain(_parsed: &Self::Parsed, _query: &ExplainQuery) -> Result<Report> {
+        bail!("This day has nothing to explain")
+    }
+
+    /// Exports intermediate state as a table, e.g. per-line results
+    /// that add up to an answer.
+    fn dump(_parsed: &Self::Parsed) -> Result<Table> {
+        bail!("This day has no state to dump")
+    }
+
+    /// A table the HTML report shows next to the answers, for days whose
+    /// answers break down further. Most days have none.
+    fn breakdown(_parsed: &Self::Parsed) -> Result<Option<Table>> {
+        Ok(None)
+    }
+
+    /// Draws the input and what the solution found in it, at a pixel
+    /// per cell or a few. The runner scales it up for `--render`.
+    fn render(_parsed: &Self::Parsed) -> Result<Drawing> {
+        bail!("This day has nothing to render")
+    }
+
+    /// Naive solvers to check the optimized parts against with `verify`.
+    /// Parts without one are `None`.
+    fn reference(_parsed: &Self::Parsed) -> Result<[Option<Answer>; 2]> {
+        Ok([None, None])
+    }
+
+    /// Makes `input` smaller while `fails` still holds for it, to get a
+    /// small reproducer when [`Self::reference`] disagrees. By default
+    /// lines are dropped.
+    fn shrink_input(input: &str, fails: &mut Fails<'_>) -> String {
+        shrink::lines(input, fails)
+    }
+
+    /// Produces a random but valid input. What `size` counts depends on
+    /// the day, usually lines.
+    fn generate(_rng: &mut Rng, _size: usize) -> Result<String> {
+        bail!("This day has no input generator")
+    }
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(v) => v.fmt(f),
+            Answer::UInt(v) => v.fmt(f),
+            Answer::Text(v) => f.pad(v),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Self {
+        Answer::Int(v)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(v: u64) -> Self {
+        Answer::UInt(v)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(v: u32) -> Self {
+        Answer::UInt(v.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::UInt(v as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+
+static ALGO: OnceLock<String> = OnceLock::new();
+
+/// Selects an alternative algorithm for the solutions that have one.
+/// Days that don't have it use their default, so one algorithm can be
+/// selected for all days. Only the first call has an effect.
+pub fn set_algo(algo: impl Into<String>) {
+    let _ = ALGO.set(algo.into());
+}
+
+/// The algorithm selected with `--algo`, if any.
+#[must_use]
+pub fn algo() -> Option<&'static str> {
+    ALGO.get().map(String::as_str)
+}
+
+/// Parameters for [`Solution::explain`], given as `key=value` pairs on
+/// the command line.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ExplainQuery {
+    params: BTreeMap<String, String>,
+}
+
+impl ExplainQuery {
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+
+    /// Parses the value of `key`, or returns `default` if it wasn't given.
+    pub fn get_or<T>(&self, key: &str, default: T) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.get(key).map_or(Ok(default), |v| {
+            v.parse::<T>()
+                .map_err(|e| anyhow!("{e}"))
+                .with_context(|| format!("Invalid value for {key}: {v}"))
+        })
+    }
+
+    /// Parses the value of `key` as a half-open range `a..b`, or returns
+    /// `default` if it wasn't given.
+    pub fn range_or(&self, key: &str, default: Range<usize>) -> Result<Range<usize>> {
+        let Some(v) = self.get(key) else {
+            return Ok(default);
+        };
+
+        let (start, end) = v
+            .split_once("..")
+            .ok_or_else(|| anyhow!("Expected a range a..b for {key}, got: {v}"))?;
+
+        Ok(start.parse()?..end.parse()?)
+    }
+}
+
+impl FromStr for ExplainQuery {
+    type Err = anyhow::Error;
+
+    /// Parses whitespace separated `key=value` pairs.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_ascii_whitespace()
+            .map(|pair| {
+                pair.split_once('=')
+                    .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                    .ok_or_else(|| anyhow!("Expected key=value, got: {pair}"))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()
+            .map(|params| Self { params })
+    }
+}
+
+/// The result of [`Solution::explain`]: a title and lines of text.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Report {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+impl Report {
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            lines: vec![],
+        }
+    }
+
+    pub fn line(&mut self, line: impl Display) -> &mut Self {
+        self.lines.push(line.to_string());
+        self
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.title)?;
+
+        for line in &self.lines {
+            writeln!(f, "  {line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_queries() -> Result<()> {
+        let query = ExplainQuery::from_str("rows=0..5 factor=10")?;
+
+        assert_eq!(query.get("rows"), Some("0..5"));
+        assert_eq!(query.get_or("factor", 1usize)?, 10);
+        assert_eq!(query.get_or("missing", 1usize)?, 1);
+        assert!(query.get_or::<usize>("rows", 0).is_err());
+        assert_eq!(query.range_or("rows", 0..1)?, 0..5);
+        assert_eq!(query.range_or("cols", 0..1)?, 0..1);
+        assert!(query.range_or("factor", 0..1).is_err());
+
+        assert!(ExplainQuery::from_str("rows").is_err());
+        assert_eq!(ExplainQuery::from_str("")?, ExplainQuery::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn answers_display_plainly() {
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from(42usize), Answer::UInt(42));
+        assert_eq!(Answer::from("AAA".to_owned()).to_string(), "AAA");
+    }
+
+    #[test]
+    fn reports_render() {
+        let mut report = Report::new("Title");
+        report.line("a").line(1);
+
+        assert_eq!(report.to_string(), "Title\n  a\n  1\n");
+    }
+}
@@ -0,0 +1,213 @@
+//! Grids without fixed bounds, for puzzles whose positions wander off
+//! the input, like a trench that is dug step by step or a garden that
+//! repeats forever. Only the cells that were set take up memory.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    ops::Index,
+};
+
+use crate::{geometry::Vec2, grid::Grid};
+
+/// Cells at signed positions, so they can lie above or left of where
+/// they started. [`Point2`](crate::geometry::Point2) can't do that: it
+/// isn't generic and only counts from the top left corner, so cells are
+/// keyed by [`Vec2`], which is signed and just as wide as `i64` on the
+/// 64-bit targets we run on.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the cell at `p`, returning what was there before.
+    pub fn insert(&mut self, p: Vec2, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Vec2) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Vec2) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Vec2) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Vec2) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// The number of cells that are set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells that are set with their position, in no particular
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.cells.iter().map(|(p, cell)| (*p, cell))
+    }
+
+    /// The top left and bottom right corner of the smallest rectangle
+    /// around all cells, or `None` if there are none.
+    #[must_use]
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        let mut positions = self.cells.keys();
+        let first = *positions.next()?;
+
+        Some(positions.fold((first, first), |(min, max), p| {
+            (
+                Vec2::new(min.row.min(p.row), min.col.min(p.col)),
+                Vec2::new(max.row.max(p.row), max.col.max(p.col)),
+            )
+        }))
+    }
+
+    /// The cells inside [`bounds`](Self::bounds) as a dense grid, with
+    /// `empty` where nothing is set, and the position of its top left
+    /// cell.
+    #[must_use]
+    pub fn to_grid(&self, empty: T) -> (Vec2, Grid<T>)
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds() else {
+            return (Vec2::default(), Grid::default());
+        };
+
+        let rows = (min.row..=max.row)
+            .map(|row| {
+                (min.col..=max.col)
+                    .map(|col| self.get(Vec2::new(row, col)).unwrap_or(&empty).clone())
+                    .collect()
+            })
+            .collect();
+
+        let grid = Grid::from_rows(rows).expect("rows of the bounds have the same length");
+        (min, grid)
+    }
+}
+
+impl<T> FromIterator<(Vec2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vec2, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(Vec2, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Vec2, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter);
+    }
+}
+
+/// All cells of `grid` at the same positions.
+impl<T: Clone> From<&Grid<T>> for SparseGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        grid.enumerate()
+            .map(|(p, cell)| (Vec2::from(p), cell.clone()))
+            .collect()
+    }
+}
+
+impl<T> Index<Vec2> for SparseGrid<T> {
+    type Output = T;
+
+    /// Panics if the cell isn't set.
+    fn index(&self, p: Vec2) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("Nothing at {p:?}"))
+    }
+}
+
+/// The cells inside the bounds, with spaces where nothing is set.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+
+        for row in min.row..=max.row {
+            for col in min.col..=max.col {
+                match self.get(Vec2::new(row, col)) {
+                    Some(cell) => write!(f, "{cell}")?,
+                    None => write!(f, " ")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::geometry::Direction;
+
+    #[test]
+    fn sparse_grids_work() -> anyhow::Result<()> {
+        // Dig a trench around a 3x3 square, starting at its top right.
+        let mut trench = SparseGrid::new();
+        let mut p = Vec2::default();
+        for d in [
+            Direction::West,
+            Direction::South,
+            Direction::East,
+            Direction::North,
+        ] {
+            for _ in 0..2 {
+                p += d.offset();
+                trench.insert(p, '#');
+            }
+        }
+
+        assert_eq!(trench.len(), 8);
+        assert_eq!(trench.bounds(), Some((Vec2::new(0, -2), Vec2::new(2, 0))));
+        assert_eq!(trench.to_string(), "###\n# #\n###\n");
+        assert_eq!(trench[Vec2::new(1, -2)], '#');
+        assert!(!trench.contains(Vec2::new(1, -1)));
+
+        let (corner, grid) = trench.to_grid('.');
+        assert_eq!(corner, Vec2::new(0, -2));
+        assert_eq!(grid.to_string(), "###\n#.#\n###\n");
+
+        let dense = Grid::<char>::from_str("ab\ncd\n")?;
+        let sparse = SparseGrid::from(&dense);
+        assert_eq!(sparse.get(Vec2::new(1, 0)), Some(&'c'));
+        assert_eq!(sparse.to_grid(' ').1, dense);
+
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+
+        Ok(())
+    }
+}
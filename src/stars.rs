@@ -0,0 +1,286 @@
+//! Season progress: which parts are solved and verified, rendered as an
+//! Advent calendar like the one on the Advent of Code website.
+
+use std::fmt::Write;
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::{answers::Answers, solution::Answer, template};
+
+const TEMPLATE: &str = include_str!("templates/stars.html");
+
+/// Every event has 25 days with two parts each.
+pub const DAYS: u8 = 25;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum PartStatus {
+    /// No solution or it didn't produce an answer.
+    #[default]
+    Missing,
+    /// There is an answer, but no known answer to compare with.
+    Unverified,
+    Correct,
+    Wrong,
+}
+
+impl PartStatus {
+    #[must_use]
+    fn symbol(self) -> char {
+        match self {
+            PartStatus::Missing => '.',
+            PartStatus::Unverified => '?',
+            PartStatus::Correct => '*',
+            PartStatus::Wrong => 'x',
+        }
+    }
+
+    #[must_use]
+    fn css_class(self) -> &'static str {
+        match self {
+            PartStatus::Missing => "missing",
+            PartStatus::Unverified => "unverified",
+            PartStatus::Correct => "correct",
+            PartStatus::Wrong => "wrong",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Progress {
+    pub year: u16,
+    /// Status of both parts for days 1 to 25.
+    pub days: Vec<[PartStatus; 2]>,
+}
+
+impl Progress {
+    /// Compares the answers each day produced (as `(day, part, answer)`)
+    /// with the known ones.
+    #[must_use]
+    pub fn new(year: u16, produced: &[(u8, u8, Answer)], known: &Answers) -> Self {
+        let mut days = vec![[PartStatus::Missing; 2]; usize::from(DAYS)];
+
+        for (day, part, answer) in produced {
+            let Some(status) = days
+                .get_mut(usize::from(*day).wrapping_sub(1))
+                .and_then(|parts| parts.get_mut(usize::from(*part).wrapping_sub(1)))
+            else {
+                continue;
+            };
+
+            *status = match known.get(year, *day, *part) {
+                None => PartStatus::Unverified,
+                Some(expected) if expected == answer.to_string() => PartStatus::Correct,
+                Some(_) => PartStatus::Wrong,
+            };
+        }
+
+        Self { year, days }
+    }
+
+    /// A star for every correct part.
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        self.days
+            .iter()
+            .flatten()
+            .filter(|s| **s == PartStatus::Correct)
+            .count()
+    }
+
+    #[must_use]
+    pub fn max_stars(&self) -> usize {
+        self.days.len() * 2
+    }
+
+    /// Five days per row, each with a symbol per part.
+    #[must_use]
+    pub fn render_terminal(&self) -> String {
+        let mut out = String::new();
+
+        for (i, parts) in self.days.iter().enumerate() {
+            let symbols = parts
+                .iter()
+                .map(|s| {
+                    let symbol = s.symbol().to_string();
+                    match s {
+                        PartStatus::Missing => symbol.dimmed(),
+                        PartStatus::Unverified => symbol.cyan(),
+                        PartStatus::Correct => symbol.yellow().bold(),
+                        PartStatus::Wrong => symbol.red(),
+                    }
+                    .to_string()
+                })
+                .collect::<String>();
+
+            let _ = write!(out, "{:>2} {symbols}", i + 1);
+            out.push_str(if (i + 1) % 5 == 0 { "\n" } else { "   " });
+        }
+
+        out.trim_end().to_owned()
+    }
+
+    /// A self-contained HTML page.
+    pub fn render_html(&self) -> Result<String> {
+        let mut days = String::new();
+
+        for (i, parts) in self.days.iter().enumerate() {
+            let _ = write!(days, "<li><span class=\"day\">{}</span>", i + 1);
+            for status in parts {
+                let _ = write!(
+                    days,
+                    "<span class=\"{}\">{}</span>",
+                    status.css_class(),
+                    status.symbol()
+                );
+            }
+            days.push_str("</li>\n");
+        }
+
+        template::render(
+            TEMPLATE,
+            &[
+                ("year", &self.year.to_string()),
+                ("stars", &self.stars().to_string()),
+                ("max", &self.max_stars().to_string()),
+                ("days", &days),
+            ],
+        )
+    }
+}
+
+/// How far a day got, going by what is recorded rather than by running
+/// it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Solved {
+    Nothing,
+    PartOne,
+    Both,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Calendar {
+    /// Days 1 to 25.
+    pub days: Vec<Solved>,
+}
+
+impl Calendar {
+    /// A part counts as solved if its day is among the `registered` ones
+    /// and its answer is known.
+    #[must_use]
+    pub fn new(year: u16, registered: &[u8], known: &Answers) -> Self {
+        let days = (1..=DAYS)
+            .map(|day| {
+                let solved =
+                    |part| registered.contains(&day) && known.get(year, day, part).is_some();
+
+                match (solved(1), solved(2)) {
+                    (true, true) => Solved::Both,
+                    (true, false) => Solved::PartOne,
+                    (false, _) => Solved::Nothing,
+                }
+            })
+            .collect();
+
+        Self { days }
+    }
+
+    /// Five days per row, colored like the stars on the website.
+    #[must_use]
+    pub fn render_terminal(&self) -> String {
+        let mut out = String::new();
+
+        for (i, solved) in self.days.iter().enumerate() {
+            let cell = format!(
+                " {:>2} {:<2} ",
+                i + 1,
+                match solved {
+                    Solved::Nothing => "",
+                    Solved::PartOne => "*",
+                    Solved::Both => "**",
+                }
+            );
+
+            let _ = write!(
+                out,
+                "{}",
+                match solved {
+                    Solved::Nothing => cell.dimmed(),
+                    Solved::PartOne => cell.black().on_white(),
+                    Solved::Both => cell.black().on_yellow(),
+                }
+            );
+            out.push_str(if (i + 1) % 5 == 0 { "\n" } else { " " });
+        }
+
+        out.trim_end_matches('\n').to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn counts_stars() -> anyhow::Result<()> {
+        colored::control::set_override(false);
+
+        let known =
+            Answers::from_str("[2023.1]\npart1 = \"1\"\npart2 = \"2\"\n[2023.2]\npart1 = \"3\"\n")?;
+        let produced = [
+            (1, 1, Answer::UInt(1)),
+            (1, 2, Answer::Int(2)),
+            (2, 1, Answer::UInt(4)),
+            (2, 2, Answer::UInt(5)),
+            (26, 1, Answer::Text("ignored".to_owned())),
+        ];
+
+        let progress = Progress::new(2023, &produced, &known);
+
+        assert_eq!(progress.stars(), 2);
+        assert_eq!(progress.max_stars(), 50);
+        assert_eq!(
+            progress.days[..3],
+            [
+                [PartStatus::Correct, PartStatus::Correct],
+                [PartStatus::Wrong, PartStatus::Unverified],
+                [PartStatus::Missing, PartStatus::Missing],
+            ]
+        );
+
+        let terminal = progress.render_terminal();
+        assert!(terminal.starts_with(" 1 **    2 x?    3 ..    4 ..    5 ..\n 6 .."));
+        assert_eq!(terminal.lines().count(), 5);
+
+        assert!(progress.render_html()?.contains("<p>2 of 50 stars</p>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn calendar_needs_solution_and_answer() -> anyhow::Result<()> {
+        colored::control::set_override(false);
+
+        let known = Answers::from_str(
+            "[2023.1]\npart1 = \"1\"\npart2 = \"2\"\n[2023.2]\npart1 = \"3\"\n[2023.4]\npart1 = \"4\"\n",
+        )?;
+        let calendar = Calendar::new(2023, &[1, 2, 3], &known);
+
+        assert_eq!(
+            calendar.days[..4],
+            [
+                Solved::Both,
+                Solved::PartOne,
+                Solved::Nothing,
+                Solved::Nothing
+            ]
+        );
+        assert!(calendar
+            .render_terminal()
+            .starts_with("  1 **    2 *     3       4       5    \n  6"));
+
+        Ok(())
+    }
+}
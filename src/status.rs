@@ -0,0 +1,94 @@
+//! How complete each day is: whether its module exists, is registered
+//! with the runner and has both parts and example tests.
+
+use crate::{registry, stars::DAYS};
+
+/// What the build script found in the source of a day module.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ModuleInfo {
+    pub year: u16,
+    pub day: u8,
+    pub part1: bool,
+    pub part2: bool,
+    pub example_tests: bool,
+}
+
+/// All day modules in the source tree, ordered by year and day.
+pub const MODULES: &[ModuleInfo] = include!(concat!(env!("OUT_DIR"), "/modules.rs"));
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct DayStatus {
+    pub day: u8,
+    pub module: bool,
+    pub registered: bool,
+    pub part1: bool,
+    pub part2: bool,
+    pub example_tests: bool,
+}
+
+impl DayStatus {
+    /// A day that was started but isn't finished or not wired up.
+    #[must_use]
+    pub fn has_gap(&self) -> bool {
+        (self.module || self.registered)
+            && !(self.module && self.registered && self.part1 && self.part2 && self.example_tests)
+    }
+}
+
+/// The status of all days of `year`.
+#[must_use]
+pub fn days(year: u16) -> Vec<DayStatus> {
+    (1..=DAYS)
+        .map(|day| {
+            let module = MODULES.iter().find(|m| m.year == year && m.day == day);
+            let puzzle = registry::find(year, day);
+
+            DayStatus {
+                day,
+                module: module.is_some(),
+                registered: puzzle.is_some(),
+                part1: module.is_some_and(|m| m.part1),
+                part2: module.is_some_and(|m| m.part2) && puzzle.is_none_or(|p| p.parts >= 2),
+                example_tests: module.is_some_and(|m| m.example_tests),
+            }
+        })
+        .collect()
+}
+
+// The tests count on all days being compiled in.
+#[cfg(all(test, feature = "default"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_modules() {
+        let days = days(2023);
+
+        assert_eq!(days.len(), 25);
+        assert_eq!(
+            days[0],
+            DayStatus {
+                day: 1,
+                module: true,
+                registered: true,
+                part1: true,
+                part2: true,
+                example_tests: true,
+            }
+        );
+        assert!(!days[0].has_gap());
+
+        // Day 13 has no second part yet.
+        assert!(!days[12].part2);
+        assert!(days[12].has_gap());
+
+        assert_eq!(
+            days[24],
+            DayStatus {
+                day: 25,
+                ..DayStatus::default()
+            }
+        );
+        assert!(!days[24].has_gap());
+    }
+}
@@ -0,0 +1,308 @@
+//! Submitting answers, with a local record of earlier guesses so known
+//! wrong answers are never sent twice and the website's cooldown after a
+//! wrong answer is kept. Stored as TOML next to the answers.
+
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// What the website said about an answer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+/// The website's reply to a submission.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Response {
+    /// The answer was judged. The next one may only be given after the
+    /// wait.
+    Judged(Verdict, Duration),
+    /// The previous answer was given too recently, so this one wasn't
+    /// looked at.
+    TooSoon(Duration),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+}
+
+/// Parses durations like `1m 15s`.
+fn time_left(text: &str) -> Option<Duration> {
+    text.split_whitespace()
+        .map(|part| {
+            let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let factor = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            number.parse::<u64>().ok().map(|n| n * factor)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Parses waits like `please wait 5 minutes`.
+fn please_wait(text: &str) -> Option<Duration> {
+    let lower = text.to_lowercase();
+    let (_, rest) = lower.split_once("please wait ")?;
+    let mut words = rest.split_whitespace();
+
+    let number = match words.next()? {
+        "one" | "a" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        number => number.parse().ok()?,
+    };
+    let factor = match words.next()? {
+        unit if unit.starts_with("second") => 1,
+        unit if unit.starts_with("minute") => 60,
+        unit if unit.starts_with("hour") => 3600,
+        _ => return None,
+    };
+
+    Some(Duration::from_secs(number * factor))
+}
+
+/// Reads the reply page after submitting an answer.
+pub fn parse_response(page: &str) -> Result<Response> {
+    if let Some((_, rest)) = page.split_once("You have ") {
+        if let Some((left, _)) = rest.split_once(" left to wait") {
+            if let Some(wait) = time_left(left) {
+                return Ok(Response::TooSoon(wait));
+            }
+        }
+    }
+
+    if page.contains("You don't seem to be solving the right level") {
+        return Ok(Response::WrongLevel);
+    }
+
+    let verdict = if page.contains("That's the right answer") {
+        Verdict::Right
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else {
+        bail!("Didn't understand the reply to the answer");
+    };
+
+    Ok(Response::Judged(
+        verdict,
+        please_wait(page).unwrap_or_default(),
+    ))
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Guess {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// When it was submitted, in seconds since the epoch.
+    pub timestamp: u64,
+}
+
+/// Why an answer isn't submitted.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Refusal {
+    /// The website still wants us to wait.
+    Cooldown(Duration),
+    /// The part is solved already, with this answer.
+    Solved(String),
+    /// This very answer was wrong before.
+    KnownWrong,
+    /// An answer this high or higher was too high before.
+    TooHigh(String),
+    /// An answer this low or lower was too low before.
+    TooLow(String),
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Guesses {
+    /// No answers before this time, in seconds since the epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait_until: Option<u64>,
+    #[serde(default, rename = "guess")]
+    guesses: Vec<Guess>,
+}
+
+/// Seconds since the epoch.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |t| t.as_secs())
+}
+
+impl Guesses {
+    /// Loads the guesses from `path`. A missing file means there were
+    /// none.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => {
+                toml::from_str(&s).with_context(|| format!("Failed to parse {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Why `answer` must not be submitted at time `now`, if it must not.
+    /// Bounds from too high or too low guesses only apply to numbers.
+    #[must_use]
+    pub fn refusal(&self, year: u16, day: u8, part: u8, answer: &str, now: u64) -> Option<Refusal> {
+        if let Some(wait_until) = self.wait_until.filter(|&t| t > now) {
+            return Some(Refusal::Cooldown(Duration::from_secs(wait_until - now)));
+        }
+
+        let number = answer.parse::<i128>().ok();
+
+        self.guesses
+            .iter()
+            .filter(|g| (g.year, g.day, g.part) == (year, day, part))
+            .find_map(|g| {
+                let bound = number.zip(g.answer.parse::<i128>().ok());
+
+                match g.verdict {
+                    Verdict::Right => Some(Refusal::Solved(g.answer.clone())),
+                    _ if g.answer == answer => Some(Refusal::KnownWrong),
+                    Verdict::TooHigh
+                        if bound.is_some_and(|(answer, guessed)| answer >= guessed) =>
+                    {
+                        Some(Refusal::TooHigh(g.answer.clone()))
+                    }
+                    Verdict::TooLow if bound.is_some_and(|(answer, guessed)| answer <= guessed) => {
+                        Some(Refusal::TooLow(g.answer.clone()))
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    /// Remembers what the website said about `answer`.
+    pub fn record(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+        response: Response,
+        now: u64,
+    ) {
+        let wait = match response {
+            Response::Judged(verdict, wait) => {
+                self.guesses.push(Guess {
+                    year,
+                    day,
+                    part,
+                    answer: answer.to_owned(),
+                    verdict,
+                    timestamp: now,
+                });
+                wait
+            }
+            Response::TooSoon(wait) => wait,
+            Response::WrongLevel => Duration::ZERO,
+        };
+
+        self.wait_until = (wait > Duration::ZERO).then(|| now + wait.as_secs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_responses() -> Result<()> {
+        assert_eq!(
+            parse_response("<p>That's the right answer!  You are one gold star closer.</p>")?,
+            Response::Judged(Verdict::Right, Duration::ZERO)
+        );
+        assert_eq!(
+            parse_response(
+                "<p>That's not the right answer; your answer is too high.  \
+                 Please wait one minute before trying again.</p>"
+            )?,
+            Response::Judged(Verdict::TooHigh, Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_response(
+                "<p>That's not the right answer.  please wait 5 minutes before trying again.</p>"
+            )?,
+            Response::Judged(Verdict::Wrong, Duration::from_secs(300))
+        );
+        assert_eq!(
+            parse_response(
+                "<p>You gave an answer too recently.  You have 1m 15s left to wait.</p>"
+            )?,
+            Response::TooSoon(Duration::from_secs(75))
+        );
+        assert_eq!(
+            parse_response("<p>You don't seem to be solving the right level.</p>")?,
+            Response::WrongLevel
+        );
+        assert!(parse_response("<p>Something else</p>").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_what_cannot_be_right() -> Result<()> {
+        let mut guesses = Guesses::default();
+        let judged = |verdict| Response::Judged(verdict, Duration::from_secs(60));
+
+        guesses.record(2023, 1, 1, "100", judged(Verdict::TooHigh), 1000);
+        assert_eq!(
+            guesses.refusal(2023, 1, 1, "50", 1030),
+            Some(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+
+        guesses.record(2023, 1, 1, "10", judged(Verdict::TooLow), 1100);
+        guesses.record(2023, 1, 1, "abc", judged(Verdict::Wrong), 1200);
+
+        let refusal = |answer| guesses.refusal(2023, 1, 1, answer, 2000);
+        assert_eq!(refusal("100"), Some(Refusal::KnownWrong));
+        assert_eq!(refusal("120"), Some(Refusal::TooHigh("100".to_owned())));
+        assert_eq!(refusal("7"), Some(Refusal::TooLow("10".to_owned())));
+        assert_eq!(refusal("abc"), Some(Refusal::KnownWrong));
+        assert_eq!(refusal("50"), None);
+        assert_eq!(refusal("xyz"), None);
+        assert_eq!(guesses.refusal(2023, 1, 2, "100", 2000), None);
+
+        guesses.record(2023, 1, 1, "50", judged(Verdict::Right), 2000);
+        assert_eq!(
+            guesses.refusal(2023, 1, 1, "51", 3000),
+            Some(Refusal::Solved("50".to_owned()))
+        );
+
+        assert_eq!(
+            toml::from_str::<Guesses>(&toml::to_string(&guesses)?)?,
+            guesses
+        );
+
+        Ok(())
+    }
+}
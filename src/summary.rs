@@ -0,0 +1,363 @@
+//! Everything a run of several days found out, collected by the runner
+//! and handed to a [`Reporter`] at the end. New output formats only need
+//! a new reporter.
+
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+
+use crate::{
+    bench::{self, Stats},
+    dump::{csv_field, json_value},
+    output::Message,
+    registry,
+    report::{self, DayResults},
+    results::{PartResult, Recorder},
+};
+
+/// The outcome of one day.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DaySummary {
+    pub day: u8,
+    /// The parts solved, in order. Benchmarks only keep the first run.
+    pub parts: Vec<PartResult>,
+    /// How long parsing the input took in the first run.
+    pub parse_time: Option<Duration>,
+    /// The timing statistics, if the day was benchmarked.
+    pub stats: Option<Stats>,
+    /// Why the day failed, if it did.
+    pub error: Option<String>,
+}
+
+impl DaySummary {
+    /// Sums up a day from everything its runs recorded.
+    #[must_use]
+    pub fn new(
+        day: u8,
+        recorder: &Recorder,
+        stats: Option<Stats>,
+        error: Option<&anyhow::Error>,
+    ) -> Self {
+        let mut parts: Vec<PartResult> = vec![];
+        for result in recorder.results() {
+            if parts.iter().all(|p| p.part != result.part) {
+                parts.push(result);
+            }
+        }
+
+        Self {
+            day,
+            parts,
+            parse_time: recorder.parse_times().first().copied(),
+            stats,
+            error: error.map(|e| format!("{e:#}")),
+        }
+    }
+
+    #[must_use]
+    pub fn part(&self, part: u8) -> Option<&PartResult> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Summary {
+    pub year: u16,
+    pub days: Vec<DaySummary>,
+    /// How long the whole run took, including everything around the
+    /// solutions.
+    pub wall_time: Duration,
+    /// Days that were left out, so the run is incomplete.
+    pub skipped: Vec<u8>,
+}
+
+impl Summary {
+    #[must_use]
+    pub fn new(year: u16) -> Self {
+        Self {
+            year,
+            days: vec![],
+            wall_time: Duration::ZERO,
+            skipped: vec![],
+        }
+    }
+
+    pub fn push(&mut self, day: DaySummary) {
+        self.days.push(day);
+    }
+
+    /// The `n` parts that took longest, slowest first, with their day.
+    #[must_use]
+    pub fn slowest_parts(&self, n: usize) -> Vec<(u8, &PartResult)> {
+        let mut parts = self
+            .days
+            .iter()
+            .flat_map(|d| d.parts.iter().map(move |p| (d.day, p)))
+            .collect::<Vec<_>>();
+
+        parts.sort_by_key(|(_, p)| std::cmp::Reverse(p.time));
+        parts.truncate(n);
+        parts
+    }
+
+    /// The time spent parsing inputs, over all days.
+    #[must_use]
+    pub fn parse_time(&self) -> Duration {
+        self.days.iter().filter_map(|d| d.parse_time).sum()
+    }
+
+    /// The time spent solving parts, over all days.
+    #[must_use]
+    pub fn solve_time(&self) -> Duration {
+        self.days
+            .iter()
+            .flat_map(|d| &d.parts)
+            .map(|p| p.time)
+            .sum()
+    }
+}
+
+/// Turns a [`Summary`] into text in some format.
+pub trait Reporter {
+    fn report(&self, summary: &Summary) -> Result<String>;
+}
+
+/// Aligned columns for the terminal.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TableReporter;
+
+impl Reporter for TableReporter {
+    fn report(&self, summary: &Summary) -> Result<String> {
+        let mut table = Message::SummaryHeader.to_string() + "\n";
+
+        for day in &summary.days {
+            let _ = writeln!(table, "{}", Message::SummaryRow(day));
+        }
+
+        Ok(table)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&self, summary: &Summary) -> Result<String> {
+        let days = summary
+            .days
+            .iter()
+            .map(|day| {
+                let parts = day
+                    .parts
+                    .iter()
+                    .map(|p| {
+                        json!({
+                            "part": p.part,
+                            "answer": json_value(&p.answer),
+                            "time_ns": p.time.as_nanos(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                json!({
+                    "day": day.day,
+                    "parse_time_ns": day.parse_time.map(|t| t.as_nanos()),
+                    "parts": parts,
+                    "stats": day.stats.as_ref().map(bench::stats_json),
+                    "error": day.error,
+                })
+            })
+            .collect::<Vec<Value>>();
+
+        Ok(serde_json::to_string_pretty(&json!({
+            "year": summary.year,
+            "wall_time_ns": summary.wall_time.as_nanos(),
+            "days": days,
+            "skipped": summary.skipped,
+        }))? + "\n")
+    }
+}
+
+/// The page of [`report`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HtmlReporter;
+
+impl Reporter for HtmlReporter {
+    fn report(&self, summary: &Summary) -> Result<String> {
+        let days = summary
+            .days
+            .iter()
+            .map(|d| DayResults {
+                day: d.day,
+                parts: d.parts.clone(),
+                breakdown: registry::find(summary.year, d.day)
+                    .as_ref()
+                    .and_then(report::breakdown),
+            })
+            .collect::<Vec<_>>();
+
+        report::render_html(summary.year, &days)
+    }
+}
+
+/// One row per part, for spreadsheets.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CsvReporter;
+
+impl Reporter for CsvReporter {
+    fn report(&self, summary: &Summary) -> Result<String> {
+        let mut csv = "year,day,part,answer,time_ns,error\n".to_owned();
+
+        for day in &summary.days {
+            let error = day.error.as_deref().map(csv_field).unwrap_or_default();
+
+            if day.parts.is_empty() {
+                let _ = writeln!(csv, "{},{},,,,{error}", summary.year, day.day);
+            }
+
+            for part in &day.parts {
+                let _ = writeln!(
+                    csv,
+                    "{},{},{},{},{},{error}",
+                    summary.year,
+                    day.day,
+                    part.part,
+                    csv_field(&part.answer.to_string()),
+                    part.time.as_nanos()
+                );
+            }
+        }
+
+        Ok(csv)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Table,
+    Json,
+    Html,
+    Csv,
+}
+
+impl Format {
+    #[must_use]
+    pub fn reporter(self) -> Box<dyn Reporter> {
+        match self {
+            Format::Table => Box::new(TableReporter),
+            Format::Json => Box::new(JsonReporter),
+            Format::Html => Box::new(HtmlReporter),
+            Format::Csv => Box::new(CsvReporter),
+        }
+    }
+
+    /// Whether the format is meant for programs rather than people, so
+    /// the regular output would get in the way.
+    #[must_use]
+    pub fn is_machine_readable(self) -> bool {
+        self != Format::Table
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "html" => Ok(Format::Html),
+            "csv" => Ok(Format::Csv),
+            _ => bail!("Unsupported format: {s}, use table, json, html or csv"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{results, solution::Answer};
+
+    fn summary() -> Summary {
+        let recorder = Recorder::default();
+        recorder.attach();
+        for (part, answer, millis) in [
+            (1, Answer::UInt(142), 2),
+            (2, Answer::Text("a,b".to_owned()), 8),
+            (1, Answer::UInt(142), 1),
+        ] {
+            results::record_parse(Duration::from_millis(millis));
+            results::record(PartResult {
+                part,
+                answer,
+                time: Duration::from_millis(millis),
+            });
+        }
+
+        let mut summary = Summary::new(2023);
+        summary.push(DaySummary::new(1, &recorder, None, None));
+        summary.push(DaySummary::new(
+            2,
+            &Recorder::default(),
+            None,
+            Some(&anyhow::anyhow!("timed out")),
+        ));
+        summary.skipped.push(12);
+        summary
+    }
+
+    #[test]
+    fn sums_up_times() {
+        let summary = summary();
+
+        assert_eq!(summary.days[0].parts.len(), 2);
+        assert_eq!(summary.parse_time(), Duration::from_millis(2));
+        assert_eq!(summary.solve_time(), Duration::from_millis(10));
+        assert_eq!(
+            summary
+                .slowest_parts(3)
+                .iter()
+                .map(|(day, p)| (*day, p.part))
+                .collect::<Vec<_>>(),
+            [(1, 2), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn reports_in_all_formats() -> Result<()> {
+        colored::control::set_override(false);
+        let summary = summary();
+
+        let table = Format::Table.reporter().report(&summary)?;
+        assert_eq!(table.lines().count(), 3);
+        assert!(table.lines().nth(1).is_some_and(|l| l.contains("142")));
+        assert!(table
+            .lines()
+            .nth(2)
+            .is_some_and(|l| l.contains("timed out")));
+
+        let json = serde_json::from_str::<Value>(&Format::Json.reporter().report(&summary)?)?;
+        assert_eq!(json["days"][0]["parts"][1]["answer"], "a,b");
+        assert_eq!(json["days"][0]["parts"][0]["time_ns"], 2_000_000);
+        assert_eq!(json["days"][1]["error"], "timed out");
+        assert_eq!(json["skipped"], json!([12]));
+
+        assert_eq!(
+            Format::Csv.reporter().report(&summary)?,
+            "year,day,part,answer,time_ns,error
+2023,1,1,142,2000000,
+2023,1,2,\"a,b\",8000000,
+2023,2,,,,timed out
+"
+        );
+
+        let html = Format::Html.reporter().report(&summary)?;
+        assert!(html.contains("<td>142</td>"));
+
+        assert_eq!(Format::from_str("CSV")?, Format::Csv);
+        assert!(Format::from_str("xml").is_err());
+
+        Ok(())
+    }
+}
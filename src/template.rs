@@ -0,0 +1,68 @@
+//! A tiny templating layer for the generated HTML pages. Templates
+//! contain `{{name}}` placeholders that are replaced by variables.
+
+use anyhow::{anyhow, bail, Result};
+
+/// Fills in all placeholders in `template`. Values are inserted as they
+/// are, so escape them with [`escape_html`] where needed.
+pub fn render(template: &str, vars: &[(&str, &str)]) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .ok_or_else(|| anyhow!("Unterminated placeholder in template"))?;
+        let name = rest[start + 2..start + end].trim();
+
+        let Some((_, value)) = vars.iter().find(|(var, _)| *var == name) else {
+            bail!("Template variable {name} has no value");
+        };
+
+        out.push_str(&rest[..start]);
+        out.push_str(value);
+        rest = &rest[start + end + 2..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[must_use]
+pub fn escape_html(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_owned(),
+            '<' => "&lt;".to_owned(),
+            '>' => "&gt;".to_owned(),
+            '"' => "&quot;".to_owned(),
+            '\'' => "&#39;".to_owned(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_placeholders() -> Result<()> {
+        assert_eq!(
+            render(
+                "<b>{{ who }}</b> got {{n}}{{n}}",
+                &[("who", "me"), ("n", "4")]
+            )?,
+            "<b>me</b> got 44"
+        );
+
+        assert!(render("{{missing}}", &[]).is_err());
+        assert!(render("{{open", &[("open", "")]).is_err());
+        assert_eq!(
+            escape_html("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+
+        Ok(())
+    }
+}
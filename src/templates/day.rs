@@ -0,0 +1,36 @@
+use std::convert::Infallible;
+
+use anyhow::Result;
+
+use crate::solution::{Answer, Solution};
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    type Parsed = Vec<String>;
+    type Error = Infallible;
+
+    const PARTS: u8 = 1;
+
+    fn parse(input: &str) -> Result<Vec<String>, Infallible> {
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(input: &Vec<String>) -> Result<Answer> {
+        Ok(input.len().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("day{{day}}.example");
+
+    #[test]
+    fn can_parse() -> Result<()> {
+        assert_eq!(Day{{day}}::parse(EXAMPLE)?.len(), 0);
+
+        Ok(())
+    }
+}
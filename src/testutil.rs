@@ -0,0 +1,64 @@
+//! Proptest strategies for the pieces that puzzle inputs are made of.
+//! They generate text, so properties checked with them go through the
+//! parsers of the days, too.
+
+use std::ops::Range;
+
+use itertools::Itertools;
+use proptest::prelude::*;
+
+/// A hand of camel cards like `T55J5`.
+pub fn hand() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select("23456789TJQKA".as_bytes()), 5)
+        .prop_map(|faces| faces.into_iter().map(char::from).collect())
+}
+
+/// What the elf shows at once, like `3 blue, 4 red`. Each color appears
+/// at most once, in any order.
+pub fn reveal() -> impl Strategy<Value = String> {
+    (
+        prop::sample::subsequence(vec!["red", "green", "blue"], 1..=3).prop_shuffle(),
+        prop::array::uniform3(0..20u32),
+    )
+        .prop_map(|(colors, counts)| {
+            colors
+                .into_iter()
+                .zip(counts)
+                .map(|(color, count)| format!("{count} {color}"))
+                .join(", ")
+        })
+}
+
+/// A game of cubes like `Game 4: 1 green, 3 red; 6 blue`.
+pub fn game() -> impl Strategy<Value = String> {
+    (1..1000u32, prop::collection::vec(reveal(), 1..6))
+        .prop_map(|(id, reveals)| format!("Game {id}: {}", reveals.join("; ")))
+}
+
+/// A grid with a number of rows and columns from the given ranges and
+/// cells drawn from `cells`. Every row ends with a newline.
+pub fn grid(
+    cells: &'static str,
+    rows: Range<usize>,
+    cols: Range<usize>,
+) -> impl Strategy<Value = String> {
+    let cells = cells.chars().collect_vec();
+
+    (rows, cols)
+        .prop_flat_map(move |(rows, cols)| {
+            prop::collection::vec(
+                prop::collection::vec(prop::sample::select(cells.clone()), cols),
+                rows,
+            )
+        })
+        .prop_map(|rows| {
+            rows.into_iter()
+                .map(|row| row.into_iter().collect::<String>() + "\n")
+                .collect()
+        })
+}
+
+/// A non-empty range that starts in `starts` and has a length in `lens`.
+pub fn range(starts: Range<u64>, lens: Range<u64>) -> impl Strategy<Value = Range<u64>> {
+    (starts, lens).prop_map(|(start, len)| start..start + len.max(1))
+}
@@ -0,0 +1,124 @@
+//! Small utilities for poking at a single day, e.g. classifying one hand
+//! of cards. Days register their tools here like their solutions in
+//! [`crate::registry`], and each tool becomes a subcommand of `tool`.
+
+use anyhow::{anyhow, Result};
+use clap::{ArgMatches, Command};
+
+use crate::{
+    aoc2023, input,
+    solution::{Report, Solution},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Tool {
+    pub year: u16,
+    pub day: u8,
+    /// The name of the subcommand.
+    pub name: &'static str,
+    pub about: &'static str,
+    /// Declares the arguments of the tool.
+    pub args: fn(Command) -> Command,
+    pub run: fn(&Invocation) -> Result<Report>,
+}
+
+/// What a tool gets to work with: its parsed arguments and, on request,
+/// the input of its day.
+#[derive(Debug)]
+pub struct Invocation<'a> {
+    pub matches: &'a ArgMatches,
+    year: u16,
+    day: u8,
+}
+
+impl Invocation<'_> {
+    /// The value of argument `id`. Arguments need a default value or
+    /// must be required, so there always is one.
+    pub fn arg<T: Clone + Send + Sync + 'static>(&self, id: &str) -> Result<T> {
+        self.matches
+            .get_one::<T>(id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Missing argument: {id}"))
+    }
+
+    /// The day's input, parsed by its solution.
+    pub fn parsed<S: Solution>(&self) -> Result<S::Parsed> {
+        Ok(S::parse(&input::load(self.year, self.day)?)?)
+    }
+}
+
+/// Returns all tools of all events ordered by year and day.
+#[must_use]
+pub fn all() -> Vec<Tool> {
+    aoc2023::tools()
+}
+
+#[must_use]
+pub fn year(year: u16) -> Vec<Tool> {
+    all().into_iter().filter(|t| t.year == year).collect()
+}
+
+/// The `tool` command with a subcommand for each tool of `year`.
+#[must_use]
+pub fn command(year: u16) -> Command {
+    Command::new("tool")
+        .bin_name("aoc2023 tool")
+        .about("Utilities for investigating a single day")
+        .subcommand_required(true)
+        .subcommands(
+            self::year(year).into_iter().map(|t| {
+                (t.args)(Command::new(t.name).about(format!("Day {}: {}", t.day, t.about)))
+            }),
+        )
+}
+
+/// Runs the tool selected in `matches`, which come from [`command`].
+pub fn run(year: u16, matches: &ArgMatches) -> Result<Report> {
+    let (name, matches) = matches
+        .subcommand()
+        .ok_or_else(|| anyhow!("No tool selected"))?;
+    let tool = self::year(year)
+        .into_iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| anyhow!("Unknown tool: {name}"))?;
+
+    (tool.run)(&Invocation {
+        matches,
+        year: tool.year,
+        day: tool.day,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_names_are_unique() {
+        let mut names = year(2023).iter().map(|t| t.name).collect::<Vec<_>>();
+        let count = names.len();
+
+        names.sort_unstable();
+        names.dedup();
+
+        assert_eq!(names.len(), count);
+        command(2023).debug_assert();
+    }
+
+    #[cfg(feature = "day7")]
+    #[test]
+    fn can_run_tools() -> Result<()> {
+        let matches = command(2023).try_get_matches_from(["tool", "classify-hand", "KTJJT"])?;
+        assert_eq!(run(2023, &matches)?.lines, ["TwoPair"]);
+
+        let matches =
+            command(2023).try_get_matches_from(["tool", "classify-hand", "--jokers", "KTJJT"])?;
+        assert_eq!(run(2023, &matches)?.lines, ["FourOfAKind"]);
+
+        assert!(command(2023)
+            .try_get_matches_from(["tool", "no-such-tool"])
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Disjoint sets of the numbers `0..n`, for telling which nodes are
+//! connected while edges are added one by one.
+
+/// Each set is a tree whose root stands for it. Finding roots shortens
+/// the paths to them, and unions hang the smaller tree below the larger
+/// one, so both stay close to constant time.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// `n` sets with one element each.
+    #[must_use]
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            sets: n,
+        }
+    }
+
+    /// The number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The element that stands for the set of `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = x;
+        while current != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+
+        root
+    }
+
+    /// Merges the sets of `a` and `b`. Returns whether they were apart.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (large, small) = if self.sizes[a] < self.sizes[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.sets -= 1;
+
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set of `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+
+    /// The number of disjoint sets.
+    #[must_use]
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// The elements of each set, ordered by their smallest element.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut index = vec![None; self.len()];
+        let mut groups: Vec<Vec<usize>> = vec![];
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let i = *index[root].get_or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+            groups[i].push(x);
+        }
+
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_find_works() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.sets(), 6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(4, 1));
+        assert!(sets.union(2, 3));
+        assert!(!sets.union(0, 4));
+
+        assert!(sets.same(0, 4));
+        assert!(!sets.same(0, 2));
+        assert_eq!(sets.size(1), 3);
+        assert_eq!(sets.size(5), 1);
+        assert_eq!(sets.sets(), 3);
+        assert_eq!(sets.groups(), [vec![0, 1, 4], vec![2, 3], vec![5]]);
+
+        // Joining a chain element by element keeps the tree flat.
+        let mut chain = UnionFind::new(1000);
+        for x in 1..1000 {
+            chain.union(x - 1, x);
+        }
+        let root = chain.find(999);
+        assert!((0..1000).all(|x| chain.parents[x] == root));
+        assert_eq!(chain.groups().len(), 1);
+    }
+}
@@ -0,0 +1,124 @@
+//! Integer helpers for solver cores.
+//!
+//! Solvers must not use floating point: answers have to be
+//! bit-identical on every platform. Everything that would naturally
+//! reach for `f64` (square roots, rounding divisions) lives here in
+//! an exact integer form instead.
+
+use num_integer::{Integer, Roots};
+
+/// Marker for the numeric types solver cores may use.
+///
+/// Floats don't implement [`Integer`], so passing one to any helper
+/// in this module is a compile error.
+pub trait Exact: Integer + Roots + Copy {}
+
+impl<T: Integer + Roots + Copy> Exact for T {}
+
+/// The integer square root, i.e. the largest `r` with `r * r <= n`.
+///
+/// Panics for negative `n`.
+#[must_use]
+pub fn isqrt<T: Exact>(n: T) -> T {
+    n.sqrt()
+}
+
+/// Returns the square root of `n` if `n` is a perfect square.
+#[must_use]
+pub fn exact_sqrt<T: Exact>(n: T) -> Option<T> {
+    let r = isqrt(n);
+    (r * r == n).then_some(r)
+}
+
+/// Divides `a` by `b` and rounds to the nearest integer. Ties round
+/// towards positive infinity.
+#[must_use]
+pub fn div_round<T: Exact>(a: T, b: T) -> T {
+    assert!(b != T::zero(), "division by zero");
+
+    let (a, b) = if b < T::zero() {
+        (T::zero() - a, T::zero() - b)
+    } else {
+        (a, b)
+    };
+
+    let (quotient, remainder) = a.div_mod_floor(&b);
+
+    // Doubling the remainder could overflow.
+    if remainder >= b - remainder {
+        quotient + T::one()
+    } else {
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use regex::Regex;
+
+    use super::*;
+
+    #[test]
+    fn isqrt_works() {
+        assert_eq!(isqrt(0u64), 0);
+        assert_eq!(isqrt(15u64), 3);
+        assert_eq!(isqrt(16u64), 4);
+        assert_eq!(isqrt(u64::MAX), u64::from(u32::MAX));
+        assert_eq!(isqrt(17i64), 4);
+    }
+
+    #[test]
+    fn exact_sqrt_works() {
+        assert_eq!(exact_sqrt(49u32), Some(7));
+        assert_eq!(exact_sqrt(50u32), None);
+    }
+
+    #[test]
+    fn div_round_works() {
+        assert_eq!(div_round(7, 2), 4);
+        assert_eq!(div_round(6, 4), 2);
+        assert_eq!(div_round(5, 4), 1);
+        assert_eq!(div_round(-7, 2), -3);
+        assert_eq!(div_round(7, -2), -3);
+        assert_eq!(div_round(-5, -4), 1);
+        assert_eq!(div_round(9u8, 10), 1);
+        assert_eq!(div_round(200u8, 201), 1);
+        assert_eq!(div_round(100u8, 201), 0);
+    }
+
+    #[test]
+    fn solvers_are_float_free() -> anyhow::Result<()> {
+        let float_re = Regex::new(r"\bf(32|64)\b")?;
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+        for event in fs::read_dir(src)? {
+            let event = event?.path();
+            let event_name = event.file_name().unwrap().to_string_lossy();
+
+            if !(event.is_dir() && event_name.starts_with("aoc")) {
+                continue;
+            }
+
+            for entry in fs::read_dir(&event)? {
+                let path = entry?.path();
+                let name = path.file_name().unwrap().to_string_lossy();
+
+                if !(name.starts_with("day") && name.ends_with(".rs")) {
+                    continue;
+                }
+
+                for (lineno, line) in fs::read_to_string(&path)?.lines().enumerate() {
+                    assert!(
+                        !float_re.is_match(line),
+                        "{event_name}/{name}:{}: solvers must not use floating point",
+                        lineno + 1
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
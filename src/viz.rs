@@ -0,0 +1,598 @@
+//! Pictures of puzzle grids as PNG or SVG files, to see a whole input at
+//! once when the terminal is too small for it, or to share it. Solvers
+//! can also hand out frames while they work, which become an animated
+//! GIF.
+
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Display, Write as _},
+    fs,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use itertools::Itertools;
+
+use crate::{geometry::Point2, grid::Grid, template::escape_html};
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0x00, 0x00, 0x00);
+    pub const WHITE: Rgb = Rgb::new(0xff, 0xff, 0xff);
+    pub const GRAY: Rgb = Rgb::new(0x60, 0x60, 0x60);
+    /// A background that bright colors stand out on.
+    pub const NIGHT: Rgb = Rgb::new(0x0f, 0x0f, 0x23);
+
+    #[must_use]
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// As `#rrggbb`, like in HTML and SVG.
+impl Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Colors that are easy to tell apart, for labeling things like regions.
+pub const PALETTE: [Rgb; 6] = [
+    Rgb::new(0xff, 0xd7, 0x00),
+    Rgb::new(0x00, 0xcc, 0xcc),
+    Rgb::new(0xdd, 0x44, 0xdd),
+    Rgb::new(0x33, 0xcc, 0x33),
+    Rgb::new(0x44, 0x77, 0xff),
+    Rgb::new(0xee, 0x33, 0x33),
+];
+
+/// Pixels addressed like grid cells, with the row going down.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Image {
+    pixels: Grid<Rgb>,
+}
+
+impl Image {
+    /// An image filled with `background`.
+    #[must_use]
+    pub fn new(rows: usize, cols: usize, background: Rgb) -> Self {
+        Self::from_fn(rows, cols, |_| background)
+    }
+
+    /// Colors each pixel by its position.
+    #[must_use]
+    pub fn from_fn(rows: usize, cols: usize, mut color: impl FnMut(Point2) -> Rgb) -> Self {
+        let pixels = (0..rows)
+            .map(|row| (0..cols).map(|col| color(Point2::new(row, col))).collect())
+            .collect();
+
+        Self {
+            pixels: Grid::from_rows(pixels).expect("rows have the same length"),
+        }
+    }
+
+    /// A pixel for each cell of `grid`.
+    #[must_use]
+    pub fn from_grid<T>(grid: &Grid<T>, color: impl FnMut(&T) -> Rgb) -> Self {
+        Self {
+            pixels: grid.map(color),
+        }
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.pixels.rows()
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.pixels.cols()
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Point2) -> Option<Rgb> {
+        self.pixels.get(p).copied()
+    }
+
+    /// Pixels outside of the image are ignored.
+    pub fn set(&mut self, p: Point2, color: Rgb) {
+        if let Some(pixel) = self.pixels.get_mut(p) {
+            *pixel = color;
+        }
+    }
+
+    /// Each pixel becomes a square of `factor` by `factor` pixels.
+    #[must_use]
+    pub fn scaled(&self, factor: usize) -> Self {
+        Self::from_fn(self.rows() * factor, self.cols() * factor, |p| {
+            self.pixels[Point2::new(p.row / factor, p.col / factor)]
+        })
+    }
+
+    /// Scales the image up as far as it fits into `size` pixels both
+    /// ways, as puzzle grids are too small to see at a pixel per cell.
+    #[must_use]
+    pub fn fit(&self, size: usize) -> Self {
+        let longest = self.rows().max(self.cols()).max(1);
+        self.scaled((size / longest).max(1))
+    }
+
+    /// `images` in rows of `columns`, `gap` pixels apart. Each takes up
+    /// as much space as the largest, with the image in its top left.
+    #[must_use]
+    pub fn tile(images: &[Image], columns: usize, gap: usize, background: Rgb) -> Self {
+        assert!(columns > 0, "images need at least one column");
+
+        let height = images.iter().map(Image::rows).max().unwrap_or(0) + gap;
+        let width = images.iter().map(Image::cols).max().unwrap_or(0) + gap;
+        let (rows, cols) = (images.len().div_ceil(columns), columns.min(images.len()));
+        let mut tiled = Self::new(
+            (rows * height).saturating_sub(gap),
+            (cols * width).saturating_sub(gap),
+            background,
+        );
+
+        for (i, image) in images.iter().enumerate() {
+            let corner = Point2::new(i / columns * height, i % columns * width);
+            for (p, color) in image.pixels.enumerate() {
+                tiled.set(Point2::new(corner.row + p.row, corner.col + p.col), *color);
+            }
+        }
+
+        tiled
+    }
+
+    /// The image encoded as an 8 bit RGB PNG.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        let mut encoder = png::Encoder::new(
+            &mut bytes,
+            u32::try_from(self.cols())?,
+            u32::try_from(self.rows())?,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let data = self
+            .pixels
+            .iter()
+            .flat_map(|c| [c.r, c.g, c.b])
+            .collect::<Vec<_>>();
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+
+        Ok(bytes)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_png()?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Png,
+    Svg,
+}
+
+impl Format {
+    /// Picks the format by the extension of `path`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Can't tell the format of {} without extension",
+                    path.display()
+                )
+            })?
+            .parse()
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(Format::Png),
+            "svg" => Ok(Format::Svg),
+            _ => bail!("Unsupported format: {s}, use png or svg"),
+        }
+    }
+}
+
+/// Text centered on `width` pixels to the right of `at`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Label {
+    at: Point2,
+    width: usize,
+    text: String,
+    color: Rgb,
+}
+
+/// A line through the centers of pixels.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Line {
+    points: Vec<Point2>,
+    color: Rgb,
+    closed: bool,
+}
+
+/// An image with text and lines on top. Those stay sharp at any zoom in
+/// an SVG, but PNGs can't show them and only have the image.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Drawing {
+    pub image: Image,
+    labels: Vec<Label>,
+    lines: Vec<Line>,
+}
+
+impl From<Image> for Drawing {
+    fn from(image: Image) -> Self {
+        Self {
+            image,
+            labels: vec![],
+            lines: vec![],
+        }
+    }
+}
+
+impl Drawing {
+    /// Writes `text` centered on `width` pixels to the right of `at`, so
+    /// a number can label all of its digits.
+    pub fn label(&mut self, at: Point2, width: usize, text: &str, color: Rgb) -> &mut Self {
+        self.labels.push(Label {
+            at,
+            width,
+            text: text.to_owned(),
+            color,
+        });
+        self
+    }
+
+    /// Draws a line through the centers of `points`.
+    pub fn polyline(&mut self, points: Vec<Point2>, color: Rgb) -> &mut Self {
+        self.lines.push(Line {
+            points,
+            color,
+            closed: false,
+        });
+        self
+    }
+
+    /// Like [`Self::polyline`], but back to the first point at the end.
+    pub fn polygon(&mut self, points: Vec<Point2>, color: Rgb) -> &mut Self {
+        self.lines.push(Line {
+            points,
+            color,
+            closed: true,
+        });
+        self
+    }
+
+    /// The drawing as SVG with a unit per pixel, shown as large as it
+    /// fits into `size` pixels like [`Image::fit`]. Runs of equal pixels
+    /// in a row become one rectangle, and the most common color is the
+    /// background.
+    #[must_use]
+    pub fn to_svg(&self, size: usize) -> String {
+        let (rows, cols) = (self.image.rows(), self.image.cols());
+        let scale = (size / rows.max(cols).max(1)).max(1);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {cols} {rows}\" \
+             width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">\n",
+            cols * scale,
+            rows * scale
+        );
+
+        let mut counts = HashMap::new();
+        for color in self.image.pixels.iter() {
+            *counts.entry(*color).or_insert(0) += 1;
+        }
+        let background = counts
+            .into_iter()
+            .max_by_key(|(color, count)| (*count, *color == Rgb::NIGHT))
+            .map_or(Rgb::NIGHT, |(color, _)| color);
+        let _ = writeln!(
+            svg,
+            "<rect width=\"{cols}\" height=\"{rows}\" fill=\"{background}\"/>"
+        );
+
+        for (row, pixels) in self.image.pixels.iter_rows().enumerate() {
+            let mut col = 0;
+            for (color, run) in &pixels.iter().group_by(|c| **c) {
+                let width = run.count();
+                if color != background {
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{col}\" y=\"{row}\" width=\"{width}\" height=\"1\" fill=\"{color}\"/>"
+                    );
+                }
+                col += width;
+            }
+        }
+
+        for line in &self.lines {
+            let points = line
+                .points
+                .iter()
+                .map(|p| format!("{}.5,{}.5", p.col, p.row))
+                .join(" ");
+            let _ = writeln!(
+                svg,
+                "<{} points=\"{points}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.3\" \
+                 stroke-linejoin=\"round\" shape-rendering=\"geometricPrecision\"/>",
+                if line.closed { "polygon" } else { "polyline" },
+                line.color
+            );
+        }
+
+        for label in &self.labels {
+            // The center in half pixels, to stay with integers.
+            let center = 2 * label.at.col + label.width;
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}{}\" y=\"{}.5\" fill=\"{}\" font-family=\"monospace\" font-size=\"0.9\" \
+                 text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+                center / 2,
+                if center % 2 == 1 { ".5" } else { "" },
+                label.at.row,
+                label.color,
+                escape_html(&label.text)
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the drawing as PNG or SVG, depending on the extension, at
+    /// up to `size` pixels both ways.
+    pub fn save(&self, path: &Path, size: usize) -> Result<()> {
+        match Format::from_path(path)? {
+            Format::Png => self.image.fit(size).save(path),
+            Format::Svg => fs::write(path, self.to_svg(size))
+                .with_context(|| format!("Failed to write {}", path.display())),
+        }
+    }
+}
+
+thread_local! {
+    static ANIMATION: RefCell<Option<Animation>> = RefCell::default();
+}
+
+/// Collects the frames that solvers hand to [`frame`].
+#[derive(Debug, Default, Clone)]
+pub struct Animation(Arc<Mutex<Vec<Image>>>);
+
+impl Animation {
+    /// Collects the frames of the current thread from now on.
+    pub fn attach(&self) {
+        ANIMATION.with(|a| *a.borrow_mut() = Some(self.clone()));
+    }
+
+    #[must_use]
+    pub fn frames(&self) -> Vec<Image> {
+        self.0.lock().map(|f| f.clone()).unwrap_or_default()
+    }
+
+    /// Writes the frames as a GIF, scaled up like [`Image::fit`].
+    pub fn save(&self, path: &Path, size: usize, delay: Duration) -> Result<()> {
+        let frames = self
+            .frames()
+            .iter()
+            .map(|f| f.fit(size))
+            .collect::<Vec<_>>();
+
+        fs::write(path, to_gif(&frames, delay)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Whether frames are collected on this thread, for solvers that need to
+/// keep track of something only to draw it.
+#[must_use]
+pub fn animating() -> bool {
+    ANIMATION.with(|a| a.borrow().is_some())
+}
+
+/// Hands what `draw` draws to the animation attached to this thread.
+/// Without one, `draw` isn't called, so frames cost nothing in normal
+/// runs.
+pub fn frame(draw: impl FnOnce() -> Image) {
+    ANIMATION.with(|a| {
+        if let Some(animation) = &*a.borrow() {
+            let image = draw();
+            if let Ok(mut frames) = animation.0.lock() {
+                frames.push(image);
+            }
+        }
+    });
+}
+
+/// `frames` as a GIF that shows each for `delay` and starts over after
+/// the last. They need to have the same size and at most 256 colors
+/// between them, which is plenty for puzzles.
+pub fn to_gif(frames: &[Image], delay: Duration) -> Result<Vec<u8>> {
+    let first = frames
+        .first()
+        .ok_or_else(|| anyhow!("There are no frames to animate"))?;
+    ensure!(
+        frames
+            .iter()
+            .all(|f| (f.rows(), f.cols()) == (first.rows(), first.cols())),
+        "All frames need to have the same size"
+    );
+
+    let mut indices = HashMap::new();
+    let mut palette = vec![];
+    let indexed = frames
+        .iter()
+        .map(|f| {
+            f.pixels
+                .iter()
+                .map(|c| {
+                    let next = indices.len();
+                    let index = *indices.entry(*c).or_insert_with(|| {
+                        palette.extend([c.r, c.g, c.b]);
+                        next
+                    });
+                    u8::try_from(index).map_err(|_| anyhow!("Frames can only have 256 colors"))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let (width, height) = (u16::try_from(first.cols())?, u16::try_from(first.rows())?);
+    let delay = u16::try_from(delay.as_millis() / 10)?;
+    let mut bytes = vec![];
+    let mut encoder = gif::Encoder::new(&mut bytes, width, height, &palette)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for buffer in indexed {
+        encoder.write_frame(&gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: Cow::Owned(buffer),
+            ..gif::Frame::default()
+        })?;
+    }
+    drop(encoder);
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> Result<(u32, u32, Vec<u8>)> {
+        let mut reader = png::Decoder::new(bytes).read_info()?;
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data)?;
+        data.truncate(info.buffer_size());
+
+        Ok((info.width, info.height, data))
+    }
+
+    #[test]
+    fn images_are_encoded() -> Result<()> {
+        let grid = Grid::from_rows(vec![vec![true, false, false], vec![false, false, true]])?;
+        let image = Image::from_grid(&grid, |&on| if on { Rgb::WHITE } else { PALETTE[0] });
+
+        let (width, height, data) = decode(&image.to_png()?)?;
+        assert_eq!((width, height), (3, 2));
+        assert_eq!(data[..6], [0xff, 0xff, 0xff, 0xff, 0xd7, 0x00]);
+        assert_eq!(data[15..], [0xff, 0xff, 0xff]);
+
+        let big = image.fit(10);
+        assert_eq!((big.rows(), big.cols()), (6, 9));
+        assert_eq!(big.get(Point2::new(5, 8)), Some(Rgb::WHITE));
+        assert_eq!(big.get(Point2::new(2, 5)), Some(PALETTE[0]));
+
+        let gray = Image::new(1, 4, Rgb::GRAY);
+        let stacked = Image::tile(&[image.clone(), gray.clone()], 1, 1, Rgb::BLACK);
+        assert_eq!((stacked.rows(), stacked.cols()), (5, 4));
+        assert_eq!(stacked.get(Point2::new(0, 3)), Some(Rgb::BLACK));
+        assert_eq!(stacked.get(Point2::new(2, 0)), Some(Rgb::BLACK));
+        assert_eq!(stacked.get(Point2::new(3, 3)), Some(Rgb::GRAY));
+
+        let tiled = Image::tile(&[image.clone(), gray, image], 2, 1, Rgb::BLACK);
+        assert_eq!((tiled.rows(), tiled.cols()), (5, 9));
+        assert_eq!(tiled.get(Point2::new(0, 5)), Some(Rgb::GRAY));
+        assert_eq!(tiled.get(Point2::new(4, 2)), Some(Rgb::WHITE));
+        assert_eq!(tiled.get(Point2::new(4, 5)), Some(Rgb::BLACK));
+
+        Ok(())
+    }
+
+    #[test]
+    fn drawings_are_svgs() -> Result<()> {
+        let image = Image::from_fn(2, 4, |p| {
+            if p.row == 1 && p.col > 0 {
+                PALETTE[2]
+            } else {
+                Rgb::NIGHT
+            }
+        });
+        let mut drawing = Drawing::from(image);
+        drawing
+            .label(Point2::new(1, 1), 3, "a<b", Rgb::WHITE)
+            .polyline(vec![Point2::new(0, 0), Point2::new(0, 3)], PALETTE[0]);
+
+        let svg = drawing.to_svg(10);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 4 2\" width=\"8\" height=\"4\""));
+        assert!(svg.contains("<rect width=\"4\" height=\"2\" fill=\"#0f0f23\"/>"));
+        assert!(svg.contains("<rect x=\"1\" y=\"1\" width=\"3\" height=\"1\" fill=\"#dd44dd\"/>"));
+        assert!(svg.contains("<polyline points=\"0.5,0.5 3.5,0.5\""));
+        assert!(svg.contains("<text x=\"2.5\" y=\"1.5\""));
+        assert!(svg.contains(">a&lt;b</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+
+        assert_eq!(Format::from_path(Path::new("day3.SVG"))?, Format::Svg);
+        assert_eq!(Format::from_path(Path::new("out/day3.png"))?, Format::Png);
+        assert!(Format::from_path(Path::new("day3")).is_err());
+        assert!(Format::from_path(Path::new("day3.jpg")).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn frames_are_animated() -> Result<()> {
+        let draw = |on: bool| {
+            Image::from_fn(2, 3, |p| {
+                if on && p.col == 1 {
+                    PALETTE[1]
+                } else {
+                    Rgb::NIGHT
+                }
+            })
+        };
+
+        frame(|| unreachable!("nothing is attached"));
+        assert!(!animating());
+
+        let animation = Animation::default();
+        animation.attach();
+        assert!(animating());
+        frame(|| draw(false));
+        frame(|| draw(true));
+
+        let frames = animation.frames();
+        assert_eq!(frames.len(), 2);
+
+        let gif = to_gif(&frames, Duration::from_millis(50))?;
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = decoder.read_info(&gif[..])?;
+
+        let mut decoded = vec![];
+        while let Some(frame) = decoder.read_next_frame()? {
+            assert_eq!((frame.width, frame.height, frame.delay), (3, 2, 5));
+            decoded.push(frame.buffer.to_vec());
+        }
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0][4..8], [0x0f, 0x0f, 0x23, 0xff]);
+        assert_eq!(decoded[1][4..8], [0x00, 0xcc, 0xcc, 0xff]);
+
+        assert!(to_gif(&[draw(true), Image::new(1, 1, Rgb::BLACK)], Duration::ZERO).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+//! Every way of asking for an answer has to give the same one: the text
+//! the binary prints, its JSON summary and progress events, the HTTP
+//! server and the JSON lines mode. They are compared byte for byte with
+//! the answer of the library, so new surfaces can't drift apart from
+//! the old ones.
+//!
+//! There is no WebAssembly binding to compare: the crate doesn't build
+//! one. It belongs here once it does.
+
+use std::{env, fs, process::Command};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use aoc2023::{dump::json_value, registry, rpc, serve};
+
+const YEAR: u16 = 2023;
+
+/// Inputs with small answers, a negative one and one that is too large
+/// for any integer, so every kind of answer is covered.
+const CASES: &[(u8, &str)] = &[
+    (1, include_str!("../src/aoc2023/day1.example")),
+    (5, include_str!("../src/aoc2023/day5.example")),
+    (6, include_str!("../src/aoc2023/day6.example")),
+    (
+        6,
+        "Time: 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100 100\n\
+         Distance: 99 99 99 99 99 99 99 99 99 99 99 99 99 99 99 99 99 99 99 99\n",
+    ),
+    (7, include_str!("../src/aoc2023/day7.example")),
+    (9, "5 3 1\n-2 -4 -6\n"),
+];
+
+/// What the binary says about `day` on `input`: its text output, the
+/// JSON summary and the progress events.
+fn run_binary(day: u8, input: &str) -> Result<(String, Value, Vec<Value>)> {
+    let path = env::temp_dir().join(format!("aoc-frontends-{}-{day}.txt", std::process::id()));
+    fs::write(&path, input)?;
+
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+            .arg(day.to_string())
+            .arg("--input")
+            .arg(&path)
+            .args(["--lang", "en"])
+            .args(args)
+            .output()
+    };
+    let text = run(&[])?;
+    let json = run(&["--summary", "json", "--progress-json"])?;
+    fs::remove_file(&path)?;
+
+    let events = String::from_utf8(json.stderr)?
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect();
+
+    Ok((
+        String::from_utf8(text.stdout)?,
+        serde_json::from_slice(&json.stdout).context("Summary isn't JSON")?,
+        events,
+    ))
+}
+
+#[test]
+fn frontends_agree() -> Result<()> {
+    for (day, input) in CASES {
+        let Some(puzzle) = registry::find(YEAR, *day) else {
+            eprintln!("Skipping day {day}: not compiled in");
+            continue;
+        };
+        let (text, summary, events) = run_binary(*day, input)?;
+
+        for part in 1..=puzzle.parts {
+            // Parts may fail, e.g. part 2 of day 6 on races that don't
+            // fit together. Then there's nothing to compare.
+            let Ok(Some(result)) = (puzzle.solve_part)(input, part) else {
+                continue;
+            };
+            let expected_text = result.answer.to_string();
+            let expected = json_value(&result.answer).to_string();
+            let case = format!("day {day} part {part}");
+
+            assert!(
+                text.lines()
+                    .any(|l| l.ends_with(&format!("Part {part} Solution: {expected_text}"))),
+                "{case}: text output {text}"
+            );
+
+            let summary_answer = summary["days"][0]["parts"]
+                .as_array()
+                .and_then(|parts| parts.iter().find(|p| p["part"] == part))
+                .map(|p| p["answer"].to_string());
+            assert_eq!(
+                summary_answer.as_deref(),
+                Some(&*expected),
+                "{case}: summary"
+            );
+
+            let event_answer = events
+                .iter()
+                .find(|e| e["event"] == "part_finished" && e["part"] == part)
+                .map(|e| e["answer"].to_string());
+            assert_eq!(event_answer.as_deref(), Some(&*expected), "{case}: events");
+
+            let response = serve::handle(
+                YEAR,
+                &serve::Request {
+                    method: "POST".to_owned(),
+                    path: format!("/solve/{day}/{part}"),
+                    body: (*input).to_owned(),
+                },
+                None,
+            );
+            assert_eq!(
+                response.body["answer"].to_string(),
+                expected,
+                "{case}: server"
+            );
+
+            let request = json!({ "day": day, "part": part, "input": input }).to_string();
+            assert_eq!(
+                rpc::answer(YEAR, &request, None)["answer"].to_string(),
+                expected,
+                "{case}: rpc"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// `--algo` applies to all days, so days without the selected algorithm
+/// have to fall back to their default instead of failing.
+#[test]
+fn unknown_algorithms_fall_back_to_the_default() -> Result<()> {
+    let examples = [
+        include_str!("../src/aoc2023/day1.example"),
+        include_str!("../src/aoc2023/day2.example"),
+        include_str!("../src/aoc2023/day3.example"),
+        include_str!("../src/aoc2023/day4.example"),
+        include_str!("../src/aoc2023/day5.example"),
+        include_str!("../src/aoc2023/day6.example"),
+        include_str!("../src/aoc2023/day7.example"),
+        include_str!("../src/aoc2023/day8.example"),
+        include_str!("../src/aoc2023/day9.example"),
+        include_str!("../src/aoc2023/day10.example"),
+        include_str!("../src/aoc2023/day11.example"),
+        include_str!("../src/aoc2023/day12.example"),
+        include_str!("../src/aoc2023/day13.example"),
+    ];
+    let dir = env::temp_dir().join(format!("aoc-frontends-algo-{}", std::process::id()));
+    fs::create_dir_all(dir.join(YEAR.to_string()))?;
+    for puzzle in registry::year(YEAR) {
+        fs::write(
+            dir.join(format!("{YEAR}/day{}.txt", puzzle.day)),
+            examples[usize::from(puzzle.day) - 1],
+        )?;
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+        .args(["run", "--algo", "rle", "--lang", "en", "--input-dir"])
+        .arg(&dir)
+        .output()?;
+    fs::remove_dir_all(&dir)?;
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    Ok(())
+}
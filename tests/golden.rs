@@ -0,0 +1,78 @@
+//! Runs each day on its real input and compares the answers with the
+//! known correct ones in `answers.toml`, so refactoring shared code
+//! can't quietly break a day. Days without input or answers are skipped:
+//! both are personal and may be missing from a checkout.
+//!
+//! `AOC_INPUT_DIR` and `AOC_ANSWERS` point elsewhere, like the options
+//! of the binary.
+
+use std::{env, path::PathBuf};
+
+use anyhow::Result;
+
+use aoc2023::{answers::Answers, input, registry};
+
+const YEAR: u16 = 2023;
+
+fn check_day(day: u8) -> Result<()> {
+    if let Ok(dir) = env::var("AOC_INPUT_DIR") {
+        input::set_dir(dir);
+    }
+    let answers = Answers::load(&PathBuf::from(
+        env::var("AOC_ANSWERS").unwrap_or_else(|_| "answers.toml".to_owned()),
+    ))?;
+
+    let Some(puzzle) = registry::find(YEAR, day) else {
+        eprintln!("Skipping day {day}: not compiled in");
+        return Ok(());
+    };
+    let Ok(input) = puzzle.input() else {
+        eprintln!("Skipping day {day}: no input");
+        return Ok(());
+    };
+
+    for part in 1..=puzzle.parts {
+        let Some(expected) = answers.get(YEAR, day, part) else {
+            eprintln!("Skipping day {day} part {part}: no known answer");
+            continue;
+        };
+        let result = (puzzle.solve_part)(&input, part)?;
+
+        assert_eq!(
+            result.map(|r| r.answer.to_string()).as_deref(),
+            Some(expected),
+            "day {day} part {part}"
+        );
+    }
+
+    Ok(())
+}
+
+macro_rules! golden {
+    ($($name:ident: $day:literal $(, $slow:literal)?;)*) => {
+        $(
+            #[test]
+            $(#[cfg_attr(debug_assertions, ignore = $slow)])?
+            fn $name() -> Result<()> {
+                check_day($day)
+            }
+        )*
+    };
+}
+
+// Slow days only run with `cargo test --release`.
+golden! {
+    day1: 1;
+    day2: 2;
+    day3: 3;
+    day4: 4;
+    day5: 5;
+    day6: 6;
+    day7: 7;
+    day8: 8;
+    day9: 9;
+    day10: 10;
+    day11: 11;
+    day12: 12, "takes minutes without optimizations";
+    day13: 13;
+}